@@ -1,6 +1,10 @@
 use std::future::Future;
 use crate::context::error::AppError;
+use crate::context::query_registry::{
+    next_query_id, register_query, with_cancel_flag, CancelFlag,
+};
 use crate::context::schema::AppResult;
+use futures::channel::oneshot;
 use tokio::task;
 
 pub mod app;
@@ -32,4 +36,44 @@ where
         .map_err(|e| AppError::InternalServer {
             message: e.to_string(),
         })?
-}
\ No newline at end of file
+}
+
+/// Run a query on the async runtime and track it in the running-query registry
+/// under `query_id` (a fresh ID when `None`) so `cancel_query` can abort it.
+///
+/// Aborting only takes effect at the query's next await point; synchronous
+/// work in between stops where it calls `check_cancelled`. The query stays
+/// listed until its task has ended.
+///
+/// The task waits for the query to be registered before calling `f`, so a
+/// query rejected as a duplicate never runs.
+pub async fn run_cancellable<F, Fut, T>(query_id: Option<String>, sql: String, f: F) -> AppResult<T>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = AppResult<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    let query_id = query_id.unwrap_or_else(next_query_id);
+    let cancel_flag = CancelFlag::default();
+    let (start, started) = oneshot::channel::<()>();
+    let handle = task::spawn(with_cancel_flag(cancel_flag.clone(), async move {
+        started.await.map_err(|_| AppError::InternalServer {
+            message: "Query was not registered".to_string(),
+        })?;
+        f().await
+    }));
+    let _registered = register_query(&query_id, &sql, handle.abort_handle(), cancel_flag.clone())
+        .inspect_err(|_| handle.abort())?;
+    let _ = start.send(());
+
+    match handle.await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(_)) | Err(_) if cancel_flag.is_cancelled() => Err(AppError::BadRequest {
+            message: format!("Query '{}' was cancelled", query_id),
+        }),
+        Ok(Err(err)) => Err(err),
+        Err(e) => Err(AppError::InternalServer {
+            message: e.to_string(),
+        }),
+    }
+}
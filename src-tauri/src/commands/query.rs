@@ -1,32 +1,92 @@
-use crate::commands::{run_blocking, run_blocking_async};
-use crate::context::context::{collect, get_data_frame, get_sql_context, register};
+use crate::commands::{run_blocking, run_blocking_async, run_cancellable};
+use crate::context::context::{
+    collect_with_metrics, column_type_infos, get_data_frame, get_sql_context, paginate,
+    ScanMetrics,
+};
+use crate::context::encoding::{EncodedRows, ResultEncoding};
 use crate::context::error::AppError;
+use crate::context::query_registry::{self, RunningQueryInfo};
+use crate::context::result_cache::{cache_result, cached_page, result_cache, CachedPage};
 use crate::context::schema::AppResult;
+use crate::sql::ddl::{
+    generate_create_table, generate_create_table_with_widths, ColumnWidths, CreateTableOptions,
+};
 use crate::sql::generator::{
-    generate_sql_inserts, generate_sql_inserts_from_batches, generate_sql_update,
-    generate_sql_update_from_batches, truncate_record_batches, ExportColumnConfig,
+    schema_headers, truncate_record_batches, where_column_refs, write_sql_statements_to_string,
+    DeleteWriter, ExportColumnConfig, InsertWriter, UpdateWriter,
 };
+use crate::sql::script::{SqlScript, SqlScriptOptions};
+use crate::utils::app_data_utils::get_result_cache_dir;
 use crate::utils::date_utils::time_difference_from_now;
 use crate::utils::db_utils;
 use crate::utils::db_utils::insert_query_history;
-use chrono::Utc;
+use crate::writer::compression::{bundle_zip, ExportCompression, OutputFile};
+use crate::writer::csv::{CsvExportOptions, CsvWriter};
+use crate::writer::destination::ExportDestination;
+use crate::writer::excel::ExcelWriter;
+use crate::writer::ipc::ArrowIpcWriter;
+use crate::writer::json::JsonWriter;
+use crate::writer::parquet::{ParquetExportOptions, ParquetWriter};
+use crate::writer::split::{FileSplitter, SplitOptions};
+use crate::writer::spool::Spool;
+use crate::writer::sql::SqlFileWriter;
+use chrono::{DateTime, Local, Utc};
 use datafusion::arrow::error::ArrowError;
+use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
-use datafusion::config::CsvOptions;
-use datafusion::dataframe::DataFrameWriteOptions;
-use dirs;
+use datafusion::physical_plan::execute_stream;
+use futures::StreamExt;
 use serde::Serialize;
 use std::fs;
-use std::fs::File;
 use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::ipc::Channel;
 use tauri::{command, AppHandle};
 
 #[derive(Serialize)]
 pub struct FetchResult {
     pub header: Vec<String>,
     pub columns: Vec<ColumnTypeInfo>,
-    pub rows: Vec<Vec<String>>,
+    #[serde(flatten)]
+    pub rows: EncodedRows,
     pub query_time: String,
+    /// Row count of the whole result, not just this page. Always set by
+    /// `fetch_page`; set by `fetch` only when `with_total` is requested.
+    pub total_rows: Option<usize>,
+    #[serde(flatten)]
+    pub metrics: ScanMetrics,
+}
+
+/// A page returned by `fetch_page`.
+#[derive(Serialize)]
+pub struct PageResult {
+    #[serde(flatten)]
+    pub page: FetchResult,
+    /// ID of the cached full result, for serving further pages.
+    pub result_id: String,
+}
+
+/// Messages pushed over the `fetch_stream` channel: the column metadata first,
+/// then one `batch` per record batch as DataFusion produces it, and finally a
+/// `finished` summary.
+#[derive(Serialize, Clone)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum FetchStreamEvent {
+    Started {
+        header: Vec<String>,
+        columns: Vec<ColumnTypeInfo>,
+    },
+    Batch {
+        #[serde(flatten)]
+        rows: EncodedRows,
+    },
+    Finished {
+        row_count: usize,
+        query_time: String,
+        #[serde(flatten)]
+        metrics: ScanMetrics,
+    },
 }
 
 #[derive(Serialize)]
@@ -47,7 +107,10 @@ pub struct SavedQueryItem {
 #[derive(Serialize)]
 pub struct WriterResult {
     pub query_time: String,
+    /// The first file written.
     pub file_name: String,
+    /// Every file written, in the order they were created.
+    pub files: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -59,7 +122,7 @@ pub struct SqlContentResult {
 
 const SQL_COPY_ROW_LIMIT: usize = 10_000;
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, Debug)]
 pub struct ColumnTypeInfo {
     pub column_name: String,
     pub arrow_type: String,
@@ -73,7 +136,14 @@ pub struct ColumnTypesResult {
 
 pub enum Dialect {
     MySQL,
+    /// MySQL with the `NO_BACKSLASH_ESCAPES` SQL mode, where a backslash in a
+    /// string literal is an ordinary character.
+    MySQLNoBackslashEscapes,
     PostgreSQL,
+    SQLite,
+    SqlServer,
+    Oracle,
+    ClickHouse,
 }
 
 impl Dialect {
@@ -81,14 +151,26 @@ impl Dialect {
         match s {
             "MySQL" => Ok(Dialect::MySQL),
             "PostgreSQL" => Ok(Dialect::PostgreSQL),
+            "SQLite" => Ok(Dialect::SQLite),
+            "SQLServer" | "SQL Server" => Ok(Dialect::SqlServer),
+            "Oracle" => Ok(Dialect::Oracle),
+            "ClickHouse" => Ok(Dialect::ClickHouse),
             _ => Err(AppError::BadRequest {
                 message: format!(
-                    "Invalid dialect: '{}'. Please use 'MySQL' or 'PostgreSQL'.",
+                    "Invalid dialect: '{}'. Please use 'MySQL', 'PostgreSQL', 'SQLite', 'SQLServer', 'Oracle' or 'ClickHouse'.",
                     s
                 ),
             }),
         }
     }
+
+    /// Apply the `no_backslash_escapes` export option; it only affects MySQL.
+    fn with_no_backslash_escapes(self, no_backslash_escapes: bool) -> Self {
+        match self {
+            Dialect::MySQL if no_backslash_escapes => Dialect::MySQLNoBackslashEscapes,
+            dialect => dialect,
+        }
+    }
 }
 
 /// Map Arrow DataType to a simplified default SQL type string
@@ -102,10 +184,17 @@ pub(crate) fn arrow_type_to_sql_type(arrow_type: &datafusion::arrow::datatypes::
         | DataType::UInt8 | DataType::UInt16 | DataType::UInt32
         | DataType::UInt64 => "INT".to_string(),
         DataType::Float16 | DataType::Float32 | DataType::Float64 => "DOUBLE".to_string(),
-        DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => "TEXT".to_string(),
+        DataType::Timestamp(_, _) => "TIMESTAMP".to_string(),
+        DataType::Date32 | DataType::Date64 => "DATE".to_string(),
         DataType::Utf8 | DataType::LargeUtf8 => "TEXT".to_string(),
-        DataType::Binary | DataType::LargeBinary => "TEXT".to_string(),
-        DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => "DOUBLE".to_string(),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => "BINARY".to_string(),
+        DataType::Decimal32(_, _)
+        | DataType::Decimal64(_, _)
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _) => "DECIMAL".to_string(),
         _ => "TEXT".to_string(),
     }
 }
@@ -129,25 +218,41 @@ pub fn is_sql_numeric_type(sql_type: &str) -> bool {
     false
 }
 
+/// Render record batches as display strings, one `Vec<String>` per row.
+pub(crate) fn format_rows(records: &[RecordBatch]) -> AppResult<Vec<Vec<String>>> {
+    // Pre-calculate the total number of rows to avoid frequent reallocation
+    let total_rows: usize = records.iter().map(|r| r.num_rows()).sum();
+    let mut rows: Vec<Vec<String>> = Vec::with_capacity(total_rows);
+    let options = FormatOptions::default().with_null("NULL");
+
+    for record in records {
+        let formatters = record
+            .columns()
+            .iter()
+            .map(|c| ArrayFormatter::try_new(c.as_ref(), &options))
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        for row in 0..record.num_rows() {
+            let mut cells = Vec::with_capacity(formatters.len());
+            for formatter in &formatters {
+                cells.push(formatter.value(row).to_string());
+            }
+            rows.push(cells);
+        }
+    }
+
+    Ok(rows)
+}
+
 #[command]
 pub async fn fetch_column_types(sql: String) -> AppResult<ColumnTypesResult> {
     run_blocking_async(move || async move {
         let mut context = get_sql_context();
-        let new_sql = register(&mut context, &sql, None, None).await?;
-        let df = get_data_frame(&mut context, &new_sql).await?;
-
-        let columns = df
-            .schema()
-            .fields()
-            .iter()
-            .map(|f| ColumnTypeInfo {
-                column_name: f.name().to_string(),
-                arrow_type: f.data_type().to_string(),
-                default_sql_type: arrow_type_to_sql_type(f.data_type()),
-            })
-            .collect();
+        let df = get_data_frame(&mut context, &sql).await?;
 
-        Ok(ColumnTypesResult { columns })
+        Ok(ColumnTypesResult {
+            columns: column_type_infos(&df),
+        })
     })
     .await
 }
@@ -158,45 +263,40 @@ pub async fn fetch(
     sql: String,
     offset: usize,
     limit: usize,
+    query_id: Option<String>,
+    with_total: Option<bool>,
+    encoding: Option<String>,
 ) -> AppResult<FetchResult> {
-    run_blocking_async(move || async move {
+    let encoding = ResultEncoding::parse(encoding.as_deref())?;
+    run_cancellable(query_id, sql.clone(), move || async move {
         let start = Utc::now();
         let mut context = get_sql_context();
 
-        let new_sql = register(&mut context, &sql, Some(limit), Some(offset))
-            .await
-            .map_err(|err| {
+        let df = get_data_frame(&mut context, &sql).await.inspect_err(|_| {
+            let _ = insert_query_history(&app, &sql, "fail");
+        })?;
+
+        // `DataFrame::count` wraps the user query in a `COUNT(*)` aggregate,
+        // so the total costs a second execution; hence opt-in.
+        let total_rows = if with_total.unwrap_or(false) {
+            Some(df.clone().count().await.map_err(|err| {
                 let _ = insert_query_history(&app, &sql, "fail");
-                err
-            })?;
+                AppError::from(err)
+            })?)
+        } else {
+            None
+        };
+
+        let df = paginate(df, Some(limit), Some(offset)).inspect_err(|_| {
+            let _ = insert_query_history(&app, &sql, "fail");
+        })?;
+        let schema = df.schema().inner().clone();
 
-        let (columns, records) = collect(&mut context, &new_sql).await.map_err(|err| {
+        let (columns, records, metrics) = collect_with_metrics(df).await.inspect_err(|_| {
             let _ = insert_query_history(&app, &sql, "fail");
-            err
         })?;
         let header: Vec<String> = columns.iter().map(|c| c.column_name.clone()).collect();
-        let width = header.len();
-
-        // Pre-calculate the total number of rows to avoid frequent reallocation
-        let total_rows: usize = records.iter().map(|r| r.num_rows()).sum();
-        let mut rows: Vec<Vec<String>> = Vec::with_capacity(total_rows);
-        let options = FormatOptions::default().with_null("NULL");
-
-        for record in records {
-            let formatters = record
-                .columns()
-                .iter()
-                .map(|c| ArrayFormatter::try_new(c.as_ref(), &options))
-                .collect::<Result<Vec<_>, ArrowError>>()?;
-
-            for row in 0..record.num_rows() {
-                let mut cells = Vec::with_capacity(width);
-                for (_, formatter) in formatters.iter().enumerate() {
-                    cells.push(formatter.value(row).to_string());
-                }
-                rows.push(cells);
-            }
-        }
+        let rows = EncodedRows::encode(encoding, &schema, &records)?;
 
         insert_query_history(&app, &sql, "successful")?;
 
@@ -205,72 +305,182 @@ pub async fn fetch(
             columns,
             rows,
             query_time: time_difference_from_now(start),
+            total_rows,
+            metrics,
         })
     })
     .await
 }
 
-/// Fetch paginated data: wrap original SQL as `SELECT * FROM ( $SQL ) LIMIT $limit OFFSET $offset`
+/// Stream query results to the frontend as they are produced, so the first
+/// rows of a large scan show up before the whole result has been computed.
 #[command]
-pub async fn fetch_page(
+pub async fn fetch_stream(
     app: AppHandle,
     sql: String,
-    offset: usize,
-    limit: usize,
-) -> AppResult<FetchResult> {
-    run_blocking_async(move || async move {
+    limit: Option<usize>,
+    query_id: Option<String>,
+    encoding: Option<String>,
+    on_event: Channel<FetchStreamEvent>,
+) -> AppResult<()> {
+    let encoding = ResultEncoding::parse(encoding.as_deref())?;
+    run_cancellable(query_id, sql.clone(), move || async move {
         let start = Utc::now();
         let mut context = get_sql_context();
 
-        let new_sql = format!(
-            "SELECT * FROM ({}) AS _sub LIMIT {} OFFSET {}",
-            sql.trim().trim_end_matches(';'),
-            limit,
-            offset
-        );
-
-        let new_sql = register(&mut context, &new_sql, None, None)
+        let df = get_data_frame(&mut context, &sql)
             .await
-            .map_err(|err| {
+            .and_then(|df| paginate(df, limit, None))
+            .inspect_err(|_| {
                 let _ = insert_query_history(&app, &sql, "fail");
-                err
             })?;
 
-        let (columns, records) = collect(&mut context, &new_sql).await.map_err(|err| {
+        let columns = column_type_infos(&df);
+        let header: Vec<String> = columns.iter().map(|c| c.column_name.clone()).collect();
+        let schema = df.schema().inner().clone();
+        on_event.send(FetchStreamEvent::Started { header, columns })?;
+
+        // Keep a handle on the physical plan so its metrics can be read once
+        // the stream is exhausted.
+        let task_ctx = Arc::new(df.task_ctx());
+        let plan = df.create_physical_plan().await.map_err(|err| {
             let _ = insert_query_history(&app, &sql, "fail");
-            err
+            AppError::from(err)
         })?;
-        let header: Vec<String> = columns.iter().map(|c| c.column_name.clone()).collect();
-        let width = header.len();
-
-        let total_rows: usize = records.iter().map(|r| r.num_rows()).sum();
-        let mut rows: Vec<Vec<String>> = Vec::with_capacity(total_rows);
-        let options = FormatOptions::default().with_null("NULL");
-
-        for record in records {
-            let formatters = record
-                .columns()
-                .iter()
-                .map(|c| ArrayFormatter::try_new(c.as_ref(), &options))
-                .collect::<Result<Vec<_>, ArrowError>>()?;
-
-            for row in 0..record.num_rows() {
-                let mut cells = Vec::with_capacity(width);
-                for (_, formatter) in formatters.iter().enumerate() {
-                    cells.push(formatter.value(row).to_string());
-                }
-                rows.push(cells);
+        let mut stream = execute_stream(plan.clone(), task_ctx).map_err(|err| {
+            let _ = insert_query_history(&app, &sql, "fail");
+            AppError::from(err)
+        })?;
+
+        let mut row_count = 0;
+        while let Some(batch) = stream.next().await {
+            let batch = batch.map_err(|err| {
+                let _ = insert_query_history(&app, &sql, "fail");
+                AppError::from(err)
+            })?;
+            if batch.num_rows() == 0 {
+                continue;
             }
+            row_count += batch.num_rows();
+            on_event.send(FetchStreamEvent::Batch {
+                rows: EncodedRows::encode(encoding, &schema, std::slice::from_ref(&batch))?,
+            })?;
         }
 
-        Ok(FetchResult {
+        insert_query_history(&app, &sql, "successful")?;
+
+        on_event.send(FetchStreamEvent::Finished {
+            row_count,
+            query_time: time_difference_from_now(start),
+            metrics: ScanMetrics::from_plan(plan.as_ref()),
+        })?;
+
+        Ok(())
+    })
+    .await
+}
+
+/// Fetch paginated data: skip `offset` rows of the query result and return the next `limit`.
+///
+/// The first call runs the query once and streams the whole result into the
+/// result cache; later pages pass the returned `result_id` and are served
+/// from the cache without re-running the query. An unknown or evicted
+/// `result_id` falls back to running the query again.
+#[command]
+pub async fn fetch_page(
+    app: AppHandle,
+    sql: String,
+    offset: usize,
+    limit: usize,
+    query_id: Option<String>,
+    result_id: Option<String>,
+    encoding: Option<String>,
+) -> AppResult<PageResult> {
+    let encoding = ResultEncoding::parse(encoding.as_deref())?;
+    run_cancellable(query_id, sql.clone(), move || async move {
+        let start = Utc::now();
+
+        if let Some(result_id) = result_id {
+            if let Some(page) = cached_page(&result_id, offset, limit)? {
+                return cached_page_result(page, result_id, encoding, start);
+            }
+        }
+
+        let mut context = get_sql_context();
+
+        let df = get_data_frame(&mut context, &sql).await.inspect_err(|_| {
+            let _ = insert_query_history(&app, &sql, "fail");
+        })?;
+        let columns = column_type_infos(&df);
+        let schema = df.schema().inner().clone();
+
+        // Stream the result into the cache, which spills it to disk once it
+        // outgrows the memory budget.
+        let spill_dir = get_result_cache_dir(&app)?;
+        let mut result = result_cache().start(&spill_dir, schema);
+        let streamed = async {
+            let task_ctx = Arc::new(df.task_ctx());
+            let plan = df.create_physical_plan().await?;
+            let mut stream = execute_stream(plan.clone(), task_ctx)?;
+            while let Some(batch) = stream.next().await {
+                result.push(batch?)?;
+            }
+            Ok::<_, AppError>(ScanMetrics::from_plan(plan.as_ref()))
+        }
+        .await;
+        let metrics = streamed.inspect_err(|_| {
+            let _ = insert_query_history(&app, &sql, "fail");
+        })?;
+
+        let result_id = cache_result(&spill_dir, result, columns, metrics)?;
+        let page = cached_page(&result_id, offset, limit)?
+            .ok_or_else(|| AppError::InternalServer {
+                message: "Cached result was evicted before it could be read".to_string(),
+            })?;
+
+        cached_page_result(page, result_id, encoding, start)
+    })
+    .await
+}
+
+fn cached_page_result(
+    page: CachedPage,
+    result_id: String,
+    encoding: ResultEncoding,
+    start: DateTime<Utc>,
+) -> AppResult<PageResult> {
+    let header: Vec<String> = page.columns.iter().map(|c| c.column_name.clone()).collect();
+    let rows = EncodedRows::encode(encoding, &page.schema, &page.batches)?;
+
+    Ok(PageResult {
+        page: FetchResult {
             header,
-            columns,
+            columns: page.columns,
             rows,
             query_time: time_difference_from_now(start),
-        })
+            total_rows: Some(page.total_rows),
+            metrics: page.metrics,
+        },
+        result_id,
     })
-    .await
+}
+
+/// Drop a cached result once the frontend no longer pages through it.
+#[command]
+pub async fn release_result(result_id: String) -> AppResult<bool> {
+    Ok(result_cache().remove(&result_id))
+}
+
+/// Abort a query started by `fetch`, `fetch_page`, `writer` or
+/// `generate_sql_content`. Returns `false` if it is no longer running.
+#[command]
+pub async fn cancel_query(query_id: String) -> AppResult<bool> {
+    Ok(query_registry::cancel_query(&query_id))
+}
+
+#[command]
+pub async fn running_queries() -> AppResult<Vec<RunningQueryInfo>> {
+    Ok(query_registry::list_running_queries())
 }
 
 #[command]
@@ -327,7 +537,7 @@ pub async fn delete_sql_history_before(
 ) -> AppResult<usize> {
     run_blocking(move || {
         if let Some(days) = days_ago {
-            if days < 0 || days > 36500 {
+            if !(0..=36500).contains(&days) {
                 return Err(AppError::BadRequest {
                     message: "days_ago must be non-negative and within a reasonable range"
                         .to_string(),
@@ -344,8 +554,9 @@ pub async fn delete_sql_history_before(
 }
 
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn writer(
-    _app: AppHandle,
+    app: AppHandle,
     file_type: String,
     sql: String,
     table_name: Option<String>,
@@ -355,16 +566,22 @@ pub async fn writer(
     dialect: Option<String>,
     export_columns: Option<Vec<ExportColumnConfig>>,
     empty_text_as_null: Option<bool>,
+    no_backslash_escapes: Option<bool>,
+    create_table_options: Option<CreateTableOptions>,
+    script_options: Option<SqlScriptOptions>,
+    parquet_options: Option<ParquetExportOptions>,
+    csv_options: Option<CsvExportOptions>,
+    export_destination: Option<ExportDestination>,
+    split_options: Option<SplitOptions>,
+    compression: Option<String>,
+    query_id: Option<String>,
 ) -> AppResult<WriterResult> {
-    run_blocking_async(move || async move {
-        let mut downloads_dir = dirs::download_dir().ok_or_else(|| AppError::BadRequest {
-            message: "Couldn't find the current working directory".to_string(),
-        })?;
-
+    run_cancellable(query_id, sql.clone(), move || async move {
         let db_dialect = match dialect {
             Some(dialect) => Dialect::from_str(&dialect)?,
             None => Dialect::MySQL,
-        };
+        }
+        .with_no_backslash_escapes(no_backslash_escapes.unwrap_or(false));
 
         let start = Utc::now();
 
@@ -381,7 +598,7 @@ pub async fn writer(
                 .map(|s| s.to_uppercase())
                 .unwrap_or_else(|| "INSERT".to_string());
             match statement_type.as_str() {
-                "INSERT" => {
+                "INSERT" | "CREATE_INSERT" => {
                     if max_values_per_insert.is_none() {
                         return Err(AppError::BadRequest {
                             message: "Max values per insert is required for INSERT statements"
@@ -389,60 +606,144 @@ pub async fn writer(
                         });
                     }
                 }
-                "UPDATE" => {
-                    let has_where_columns = where_columns
-                        .as_ref()
-                        .is_some_and(|cols| cols.iter().any(|c| !c.trim().is_empty()));
-                    if !has_where_columns {
+                "UPDATE" | "UPSERT" | "DELETE" => {
+                    let where_columns = where_columns.as_deref().unwrap_or_default();
+                    where_column_refs(where_columns, &statement_type)?;
+                    if statement_type == "UPSERT" && max_values_per_insert.is_none() {
                         return Err(AppError::BadRequest {
-                            message: "At least one WHERE column is required for UPDATE statements"
+                            message: "Max values per insert is required for UPSERT statements"
                                 .to_string(),
                         });
                     }
                 }
+                "CREATE" => {}
                 _ => {
                     return Err(AppError::BadRequest {
-                        message: "Invalid SQL statement type. Supported types: INSERT, UPDATE"
+                        message: "Invalid SQL statement type. Supported types: INSERT, UPDATE, UPSERT, DELETE, CREATE, CREATE_INSERT"
                             .to_string(),
                     });
                 }
             }
         }
 
-        let mut context = get_sql_context();
-        let new_sql = register(&mut context, &sql, None, None).await?;
-        let df = get_data_frame(&mut context, &new_sql).await?;
-
         // Determine file extension
         let file_extension = match file_type.to_lowercase().as_str() {
             "csv" => "csv",
             "tsv" => "tsv",
             "sql" => "sql",
+            "xlsx" => "xlsx",
+            "parquet" => "parquet",
+            "json" => "json",
+            "ndjson" => "ndjson",
+            "arrow" => "arrow",
             _ => {
                 return Err(AppError::BadRequest {
-                    message: "Unsupported file type. Supported types: csv, tsv, sql".to_string(),
+                    message: "Unsupported file type. Supported types: csv, tsv, sql, xlsx, parquet, json, ndjson, arrow"
+                        .to_string(),
                 })
             }
         };
 
-        downloads_dir.push(format!(
-            "easydb_{}.{}",
-            Utc::now().format("%Y%m%d%H%M%S").to_string(),
-            file_extension
-        ));
-        let file_path = downloads_dir.to_string_lossy().to_string();
-
-        match file_type.to_lowercase().as_str() {
-            "csv" => {
-                df.write_csv(&file_path, DataFrameWriteOptions::new(), None)
-                    .await?;
+        // Compressed files are named `.csv.gz` etc., and the writers pick
+        // the encoder from that name.
+        let compression = ExportCompression::parse(compression.as_deref())?;
+        let mut export_destination = export_destination.unwrap_or_default();
+        let extension = match compression.suffix() {
+            Some(_) if matches!(file_extension, "xlsx" | "parquet") => {
+                return Err(AppError::BadRequest {
+                    message: format!(
+                        "{} files are already compressed; use zip to bundle them",
+                        file_extension
+                    ),
+                });
+            }
+            Some(suffix) => {
+                if let Some(file_path) = export_destination
+                    .file_path
+                    .as_mut()
+                    .filter(|file_path| !file_path.trim().is_empty())
+                {
+                    if !file_path.to_lowercase().ends_with(&format!(".{}", suffix)) {
+                        *file_path = format!("{}.{}", file_path.trim(), suffix);
+                    }
+                }
+                format!("{}.{}", file_extension, suffix)
+            }
+            None => file_extension.to_string(),
+        };
+
+        // Check the destination before running the query.
+        let target = export_destination.resolve(
+            &extension,
+            table_name.as_deref(),
+            compression != ExportCompression::Zip
+                && matches!(file_extension, "csv" | "tsv" | "sql" | "ndjson"),
+            Local::now(),
+        )?;
+        // A zip bundle replaces the files it holds.
+        let archive = if compression == ExportCompression::Zip {
+            let archive = target.path.with_extension("zip");
+            target.check_new_file(&archive)?;
+            Some(archive)
+        } else {
+            None
+        };
+        let split_options = split_options.unwrap_or_default();
+
+        let mut context = get_sql_context();
+        let df = get_data_frame(&mut context, &sql).await?;
+        let schema = df.schema().inner().clone();
+
+        // Each file type streams through a FileSplitter, which removes the
+        // files of a failed export.
+        let files = match file_type.to_lowercase().as_str() {
+            "csv" | "tsv" => {
+                let default_delimiter = if file_extension == "tsv" { b'\t' } else { b',' };
+                let csv_options = csv_options.unwrap_or_default();
+                let open = if target.append {
+                    CsvWriter::append
+                } else {
+                    CsvWriter::new
+                };
+                FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                    open(path, &schema, default_delimiter, &csv_options)
+                })?
+                .run(df)
+                .await?
+            }
+            "xlsx" => FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                ExcelWriter::new(path.to_string(), schema.clone())
+            })?
+            .run(df)
+            .await?,
+            "parquet" => {
+                let parquet_options = parquet_options.unwrap_or_default();
+                // Reject bad options before the query runs.
+                parquet_options.writer_properties()?;
+                FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                    ParquetWriter::new(path, schema.clone(), &parquet_options)
+                })?
+                .run(df)
+                .await?
             }
-            "tsv" => {
-                let mut options = CsvOptions::default();
-                options.delimiter = b'\t';
-                df.write_csv(&file_path, DataFrameWriteOptions::new(), None)
-                    .await?;
+            "json" => FileSplitter::new(&target, &schema, &split_options, JsonWriter::array)?
+                .run(df)
+                .await?,
+            "ndjson" => {
+                let open = if target.append {
+                    JsonWriter::append_lines
+                } else {
+                    JsonWriter::lines
+                };
+                FileSplitter::new(&target, &schema, &split_options, open)?
+                    .run(df)
+                    .await?
             }
+            "arrow" => FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                ArrowIpcWriter::new(path, &schema)
+            })?
+            .run(df)
+            .await?,
             "sql" => {
                 // Generate SQL statements based on statement type
                 let table_name_value = table_name.unwrap();
@@ -451,51 +752,213 @@ pub async fn writer(
                     .map(|s| s.to_uppercase())
                     .unwrap_or_else(|| "INSERT".to_string());
                 let empty_as_null = empty_text_as_null.unwrap_or(false);
+                let script_options = script_options.unwrap_or_default();
+
+                let where_column_values = where_columns.unwrap_or_default();
+                let key_column_refs = match statement_type.as_str() {
+                    "UPDATE" | "UPSERT" | "DELETE" => {
+                        where_column_refs(&where_column_values, &statement_type)?
+                    }
+                    _ => Vec::new(),
+                };
+                let headers = schema_headers(&schema);
 
-                let sql_content = match statement_type.as_str() {
-                    "INSERT" => {
-                        let max_values = max_values_per_insert.unwrap();
-                        generate_sql_inserts(df, &table_name_value, max_values, &db_dialect, export_columns.as_deref(), empty_as_null).await?
+                // Appended statements start on a line of their own.
+                let open_file = |path: &str| -> AppResult<OutputFile> {
+                    let mut out = if target.append {
+                        target.open()?
+                    } else {
+                        OutputFile::create(path)?
+                    };
+                    if target.append {
+                        writeln!(out)?;
                     }
-                    "UPDATE" => {
-                        let where_column_values = where_columns.unwrap_or_default();
-                        let where_column_refs: Vec<&str> = where_column_values
-                            .iter()
-                            .map(|s| s.trim())
-                            .filter(|s| !s.is_empty())
-                            .collect();
-                        generate_sql_update(
-                            df,
+                    Ok(out)
+                };
+                let open_script =
+                    |path: &str| SqlScript::new(open_file(path)?, &db_dialect, &script_options);
+
+                match statement_type.as_str() {
+                    "INSERT" => FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                        let statements = InsertWriter::new(
+                            &headers,
                             &table_name_value,
-                            &where_column_refs,
+                            max_values_per_insert.unwrap(),
                             &db_dialect,
                             export_columns.as_deref(),
                             empty_as_null,
-                        )
-                        .await?
+                        )?
+                        .with_max_statement_bytes(script_options.max_statement_bytes);
+                        Ok(SqlFileWriter::new(statements, open_script(path)?))
+                    })?
+                    .run(df)
+                    .await?,
+                    "CREATE" | "CREATE_INSERT" => {
+                        let inserts = statement_type == "CREATE_INSERT" && !headers.is_empty();
+                        if !inserts && split_options.is_split() {
+                            return Err(AppError::BadRequest {
+                                message: "CREATE statements have no rows to partition or split"
+                                    .to_string(),
+                            });
+                        }
+                        // Column sizes depend on every value: unless the
+                        // export config spells them out, measure them before
+                        // writing the DDL, spooling the rows so the INSERTs
+                        // that follow don't run the query again.
+                        let mut widths = ColumnWidths::default();
+                        let mut spool = None;
+                        if ColumnWidths::needed(&schema, export_columns.as_deref()) {
+                            if inserts {
+                                let dir = get_result_cache_dir(&app)?;
+                                spool = Some(Spool::create(&dir, &schema)?);
+                            }
+                            let mut stream = df.clone().execute_stream().await?;
+                            while let Some(batch) = stream.next().await {
+                                let batch = batch?;
+                                widths.update(&batch);
+                                if let Some(spool) = &mut spool {
+                                    spool.write(&batch)?;
+                                }
+                            }
+                        }
+                        let ddl = generate_create_table_with_widths(
+                            &schema,
+                            &widths,
+                            &table_name_value,
+                            &db_dialect,
+                            export_columns.as_deref(),
+                            &create_table_options.unwrap_or_default(),
+                        )?;
+
+                        if !inserts {
+                            let mut file = open_file(&target.path.to_string_lossy())?;
+                            let result = write!(file, "{}", ddl)
+                                .map_err(AppError::from)
+                                .and_then(|()| file.finish());
+                            if result.is_err() {
+                                target.discard();
+                            }
+                            result?;
+                            vec![target.path.clone()]
+                        } else {
+                            // Every file starts with the DDL, so each one can
+                            // be loaded on its own.
+                            let splitter = FileSplitter::new(
+                                &target,
+                                &schema,
+                                &split_options,
+                                |path: &str| {
+                                    let mut out = open_file(path)?;
+                                    write!(out, "{}", ddl)?;
+                                    let statements = InsertWriter::new(
+                                        &headers,
+                                        &table_name_value,
+                                        max_values_per_insert.unwrap(),
+                                        &db_dialect,
+                                        export_columns.as_deref(),
+                                        empty_as_null,
+                                    )?
+                                    .with_max_statement_bytes(script_options.max_statement_bytes);
+                                    let script = SqlScript::new(out, &db_dialect, &script_options)?
+                                        .with_lead("\n\n");
+                                    Ok(SqlFileWriter::new(statements, script))
+                                },
+                            )?;
+                            match spool {
+                                Some(mut spool) => splitter.run_batches(spool.read()?)?,
+                                None => splitter.run(df).await?,
+                            }
+                        }
                     }
+                    "UPDATE" => FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                        let statements = UpdateWriter::new(
+                            &headers,
+                            &table_name_value,
+                            &key_column_refs,
+                            &db_dialect,
+                            export_columns.as_deref(),
+                            empty_as_null,
+                        )?;
+                        Ok(SqlFileWriter::new(statements, open_script(path)?))
+                    })?
+                    .run(df)
+                    .await?,
+                    "UPSERT" => FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                        let statements = InsertWriter::upsert(
+                            &headers,
+                            &table_name_value,
+                            max_values_per_insert.unwrap(),
+                            &key_column_refs,
+                            &db_dialect,
+                            export_columns.as_deref(),
+                            empty_as_null,
+                        )?
+                        .with_max_statement_bytes(script_options.max_statement_bytes);
+                        Ok(SqlFileWriter::new(statements, open_script(path)?))
+                    })?
+                    .run(df)
+                    .await?,
+                    "DELETE" => FileSplitter::new(&target, &schema, &split_options, |path: &str| {
+                        let statements = DeleteWriter::new(
+                            &headers,
+                            &table_name_value,
+                            &key_column_refs,
+                            max_values_per_insert,
+                            &db_dialect,
+                            export_columns.as_deref(),
+                            empty_as_null,
+                        )?;
+                        Ok(SqlFileWriter::new(statements, open_script(path)?))
+                    })?
+                    .run(df)
+                    .await?,
                     _ => {
                         return Err(AppError::BadRequest {
                             message: "Invalid SQL statement type".to_string(),
                         });
                     }
-                };
-
-                let mut file = File::create(&downloads_dir)?;
-                write!(file, "{}", sql_content)?;
+                }
             }
             _ => unreachable!(), // This case is handled above
-        }
+        };
+
+        let files = match archive {
+            Some(archive) => {
+                let directory = target.path.parent().unwrap_or(Path::new(""));
+                bundle_zip(&archive, directory, &files)?;
+                vec![archive]
+            }
+            None => files,
+        };
+        let files = files
+            .iter()
+            .map(|file| Ok(fs::canonicalize(file)?.display().to_string()))
+            .collect::<AppResult<Vec<_>>>()?;
+        // A partitioned export without rows writes no files; point at the
+        // directory it would have used.
+        let file_name = match files.first() {
+            Some(file) => file.clone(),
+            None => {
+                let directory = target
+                    .path
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .unwrap_or(Path::new("."));
+                fs::canonicalize(directory)?.display().to_string()
+            }
+        };
 
         Ok(WriterResult {
             query_time: time_difference_from_now(start),
-            file_name: fs::canonicalize(&downloads_dir)?.display().to_string(),
+            file_name,
+            files,
         })
     })
     .await
 }
 
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_sql_content(
     sql: String,
     table_name: String,
@@ -505,8 +968,12 @@ pub async fn generate_sql_content(
     dialect: Option<String>,
     export_columns: Option<Vec<ExportColumnConfig>>,
     empty_text_as_null: Option<bool>,
+    no_backslash_escapes: Option<bool>,
+    create_table_options: Option<CreateTableOptions>,
+    script_options: Option<SqlScriptOptions>,
+    query_id: Option<String>,
 ) -> AppResult<SqlContentResult> {
-    run_blocking_async(move || async move {
+    run_cancellable(query_id, sql.clone(), move || async move {
         let trimmed_table = table_name.trim();
         if trimmed_table.is_empty() {
             return Err(AppError::BadRequest {
@@ -522,18 +989,14 @@ pub async fn generate_sql_content(
         let db_dialect = match dialect {
             Some(dialect) => Dialect::from_str(&dialect)?,
             None => Dialect::MySQL,
-        };
+        }
+        .with_no_backslash_escapes(no_backslash_escapes.unwrap_or(false));
 
         let start = Utc::now();
         let mut context = get_sql_context();
-        let new_sql = register(
-            &mut context,
-            &sql,
-            Some(SQL_COPY_ROW_LIMIT + 1),
-            None,
-        )
-        .await?;
-        let df = get_data_frame(&mut context, &new_sql).await?;
+        let df = get_data_frame(&mut context, &sql).await?;
+        let df = paginate(df, Some(SQL_COPY_ROW_LIMIT + 1), None)?;
+        let schema = df.schema().inner().clone();
         let batches = df
             .collect()
             .await
@@ -548,46 +1011,105 @@ pub async fn generate_sql_content(
             batches
         };
         let empty_as_null = empty_text_as_null.unwrap_or(false);
+        let headers = schema_headers(&schema);
+        let script_options = script_options.unwrap_or_default();
+        let script = SqlScript::new(Vec::new(), &db_dialect, &script_options)?;
 
         let sql_content = match statement_type.as_str() {
             "INSERT" => {
                 let max_values = max_values_per_insert.ok_or_else(|| AppError::BadRequest {
                     message: "Max values per insert is required for INSERT statements".to_string(),
                 })?;
-                generate_sql_inserts_from_batches(
-                    batches,
+                let writer = InsertWriter::new(
+                    &headers,
                     trimmed_table,
                     max_values,
                     &db_dialect,
                     export_columns.as_deref(),
                     empty_as_null,
                 )?
+                .with_max_statement_bytes(script_options.max_statement_bytes);
+                write_sql_statements_to_string(&batches, writer, script)?
             }
             "UPDATE" => {
                 let where_column_values = where_columns.unwrap_or_default();
-                let where_column_refs: Vec<&str> = where_column_values
-                    .iter()
-                    .map(|s| s.trim())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                if where_column_refs.is_empty() {
-                    return Err(AppError::BadRequest {
-                        message: "At least one WHERE column is required for UPDATE statements"
-                            .to_string(),
-                    });
-                }
-                generate_sql_update_from_batches(
-                    batches,
+                let where_column_names = where_column_refs(&where_column_values, "UPDATE")?;
+                let writer = UpdateWriter::new(
+                    &headers,
+                    trimmed_table,
+                    &where_column_names,
+                    &db_dialect,
+                    export_columns.as_deref(),
+                    empty_as_null,
+                )?;
+                write_sql_statements_to_string(&batches, writer, script)?
+            }
+            "UPSERT" => {
+                let max_values = max_values_per_insert.ok_or_else(|| AppError::BadRequest {
+                    message: "Max values per insert is required for UPSERT statements".to_string(),
+                })?;
+                let where_column_values = where_columns.unwrap_or_default();
+                let conflict_column_refs = where_column_refs(&where_column_values, "UPSERT")?;
+                let writer = InsertWriter::upsert(
+                    &headers,
                     trimmed_table,
-                    &where_column_refs,
+                    max_values,
+                    &conflict_column_refs,
                     &db_dialect,
                     export_columns.as_deref(),
                     empty_as_null,
                 )?
+                .with_max_statement_bytes(script_options.max_statement_bytes);
+                write_sql_statements_to_string(&batches, writer, script)?
+            }
+            "DELETE" => {
+                let where_column_values = where_columns.unwrap_or_default();
+                let key_column_refs = where_column_refs(&where_column_values, "DELETE")?;
+                let writer = DeleteWriter::new(
+                    &headers,
+                    trimmed_table,
+                    &key_column_refs,
+                    max_values_per_insert,
+                    &db_dialect,
+                    export_columns.as_deref(),
+                    empty_as_null,
+                )?;
+                write_sql_statements_to_string(&batches, writer, script)?
+            }
+            "CREATE" | "CREATE_INSERT" => {
+                let mut content = generate_create_table(
+                    &schema,
+                    &batches,
+                    trimmed_table,
+                    &db_dialect,
+                    export_columns.as_deref(),
+                    &create_table_options.unwrap_or_default(),
+                )?;
+                if statement_type == "CREATE_INSERT" {
+                    let max_values = max_values_per_insert.ok_or_else(|| AppError::BadRequest {
+                        message: "Max values per insert is required for INSERT statements"
+                            .to_string(),
+                    })?;
+                    let writer = InsertWriter::new(
+                        &headers,
+                        trimmed_table,
+                        max_values,
+                        &db_dialect,
+                        export_columns.as_deref(),
+                        empty_as_null,
+                    )?
+                    .with_max_statement_bytes(script_options.max_statement_bytes);
+                    let inserts = write_sql_statements_to_string(&batches, writer, script)?;
+                    if !inserts.is_empty() {
+                        content.push_str("\n\n");
+                        content.push_str(&inserts);
+                    }
+                }
+                content
             }
             _ => {
                 return Err(AppError::BadRequest {
-                    message: "Invalid SQL statement type. Supported types: INSERT, UPDATE"
+                    message: "Invalid SQL statement type. Supported types: INSERT, UPDATE, UPSERT, DELETE, CREATE, CREATE_INSERT"
                         .to_string(),
                 });
             }
@@ -1,13 +1,16 @@
 use crate::commands::query::{arrow_type_to_sql_type, ColumnTypeInfo};
 use crate::context::error::AppError;
 use crate::context::schema::AppResult;
+use crate::context::table_function::{normalize_named_args, register_table_functions, ReaderArgs};
+use crate::reader::encoding::{detect_encoding, parse_encoding};
 use crate::reader::excel::ExcelReader;
-use crate::sql::parse::{get_function_args, parse_statements};
-use crate::utils::file_utils::ensure_path_exists;
-use async_recursion::async_recursion;
 use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::catalog::TableProvider;
+use datafusion::common::GetExt;
 use datafusion::dataframe::DataFrame;
-use datafusion::prelude::{CsvReadOptions, JsonReadOptions, ParquetReadOptions, SessionContext};
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
+use datafusion::physical_plan::{self, ExecutionPlan};
+use datafusion::prelude::{CsvReadOptions, JsonReadOptions, SessionContext};
 use datafusion::sql::TableReference;
 use datafusion_table_providers::{
     mysql::MySQLTableFactory,
@@ -17,33 +20,56 @@ use datafusion_table_providers::{
     },
     util::secrets::to_secret_map,
 };
-use sqlparser::ast::SetExpr::Select;
-use sqlparser::ast::{
-    Expr, FunctionArg, FunctionArgExpr, Offset, OffsetRows, Query, Statement, TableFactor,
-    TableFunctionArgs, Value,
-};
+use encoding_rs::Encoding;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
+/// Create a session with every `read_*` reader registered as a table function,
+/// so any statement DataFusion can parse (`EXPLAIN`, `CREATE VIEW`, `COPY`,
+/// CTEs, sub-queries, ...) can call them directly.
 pub fn get_sql_context() -> SessionContext {
-    SessionContext::new()
+    let ctx = SessionContext::new();
+    register_table_functions(&ctx);
+    ctx
 }
 
-pub async fn get_data_frame(ctx: &mut SessionContext, sql: &String) -> AppResult<DataFrame> {
-    ctx.sql(sql).await.map_err(AppError::from)
+/// Plan `sql` with DataFusion's own parser.
+///
+/// The statement is not re-serialized: the only adjustment made to the parsed
+/// AST is mapping `name => value` reader arguments onto their positional slots
+/// (see [`normalize_named_args`]), because DataFusion's planner only accepts
+/// positional table function arguments.
+pub async fn get_data_frame(ctx: &mut SessionContext, sql: &str) -> AppResult<DataFrame> {
+    let state = ctx.state();
+    let dialect = state.config().options().sql_parser.dialect;
+    let mut statement = state.sql_to_statement(sql, &dialect)?;
+    normalize_named_args(&mut statement)?;
+    let plan = state.statement_to_plan(statement).await?;
+    ctx.execute_logical_plan(plan).await.map_err(AppError::from)
 }
 
-pub async fn collect(
-    ctx: &mut SessionContext,
-    sql: &String,
-) -> AppResult<(Vec<ColumnTypeInfo>, Vec<RecordBatch>)> {
-    let df = get_data_frame(ctx, sql).await?;
-
-    // Get column metadata (name + type) from DataFrame's schema so the frontend can
-    // reuse the type information (e.g. for SQL export) without a second round-trip.
-    // Column information can be retrieved even if there's no data.
-    let columns: Vec<ColumnTypeInfo> = df
-        .schema()
+/// Restrict a DataFrame to one page of `limit` rows. `page` is the zero-based
+/// page index, so the first skipped row is `page * limit`.
+///
+/// A query with its own `LIMIT` / `OFFSET` is paged within that window, so
+/// `SELECT ... LIMIT 5000` never yields more than `limit` rows per page.
+pub fn paginate(df: DataFrame, limit: Option<usize>, page: Option<usize>) -> AppResult<DataFrame> {
+    match limit {
+        Some(limit) => {
+            let skip = limit * page.unwrap_or(0);
+            df.limit(skip, Some(limit)).map_err(AppError::from)
+        }
+        None => Ok(df),
+    }
+}
+
+/// Column metadata (name + type) from a DataFrame's schema, so the frontend
+/// can reuse the type information (e.g. for SQL export) without a second
+/// round-trip. Available even when the query returns no rows.
+pub fn column_type_infos(df: &DataFrame) -> Vec<ColumnTypeInfo> {
+    df.schema()
         .fields()
         .iter()
         .map(|f| ColumnTypeInfo {
@@ -51,13 +77,67 @@ pub async fn collect(
             arrow_type: f.data_type().to_string(),
             default_sql_type: arrow_type_to_sql_type(f.data_type()),
         })
-        .collect();
+        .collect()
+}
 
+pub async fn collect(df: DataFrame) -> AppResult<(Vec<ColumnTypeInfo>, Vec<RecordBatch>)> {
+    let columns = column_type_infos(&df);
     let records = df.collect().await.map_err(AppError::from)?;
 
     Ok((columns, records))
 }
 
+/// Rows and bytes read by the scan operators of an executed plan, taken from
+/// DataFusion's execution metrics. `None` when no operator reported them
+/// (e.g. `bytes_scanned` is only tracked by some file formats).
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScanMetrics {
+    pub rows_scanned: Option<usize>,
+    pub bytes_scanned: Option<usize>,
+}
+
+impl ScanMetrics {
+    pub fn from_plan(plan: &dyn ExecutionPlan) -> Self {
+        let mut metrics = ScanMetrics::default();
+        metrics.accumulate(plan);
+        metrics
+    }
+
+    fn accumulate(&mut self, plan: &dyn ExecutionPlan) {
+        let children = plan.children();
+        if let Some(set) = plan.metrics() {
+            if let Some(bytes) = set.sum_by_name("bytes_scanned") {
+                add_metric(&mut self.bytes_scanned, bytes.as_usize());
+            }
+            // Leaf operators are the scans, so their output is what was read.
+            if children.is_empty() {
+                if let Some(rows) = set.output_rows() {
+                    add_metric(&mut self.rows_scanned, rows);
+                }
+            }
+        }
+        for child in children {
+            self.accumulate(child.as_ref());
+        }
+    }
+}
+
+fn add_metric(total: &mut Option<usize>, value: usize) {
+    *total = Some(total.unwrap_or(0) + value);
+}
+
+/// Like [`collect`], but also reports the [`ScanMetrics`] of the execution.
+pub async fn collect_with_metrics(
+    df: DataFrame,
+) -> AppResult<(Vec<ColumnTypeInfo>, Vec<RecordBatch>, ScanMetrics)> {
+    let columns = column_type_infos(&df);
+    let task_ctx = Arc::new(df.task_ctx());
+    let plan = df.create_physical_plan().await?;
+    let records = physical_plan::collect(plan.clone(), task_ctx).await?;
+
+    Ok((columns, records, ScanMetrics::from_plan(plan.as_ref())))
+}
+
 /// Parse delimiter string to u8 byte value
 /// Supports escape sequences like \t, \n, \r, \\, and single characters
 fn parse_delimiter(value: &str) -> AppResult<u8> {
@@ -89,56 +169,43 @@ fn parse_delimiter(value: &str) -> AppResult<u8> {
 }
 
 pub fn get_csv_read_options<'a>(
-    args: &'a mut Option<TableFunctionArgs>,
+    args: &'a ReaderArgs,
     mut options: CsvReadOptions<'a>,
 ) -> AppResult<CsvReadOptions<'a>> {
-    let args = get_function_args(args);
-    if let Some(args) = args {
-        for arg in args {
-            if let FunctionArg::Named { name, arg, .. } = arg {
-                match name.value.as_str() {
-                    "infer_schema" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::Boolean(value))) = arg {
-                            if !value {
-                                options.schema_infer_max_records = 0;
-                            }
-                        }
-                    }
-                    "has_header" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::Boolean(value))) = arg {
-                            if !value {
-                                options.has_header = *value;
-                            }
-                        }
-                    }
-                    "delimiter" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
-                            value,
-                        ))) = arg
-                        {
-                            options.delimiter = parse_delimiter(&value)?;
-                        }
-                    }
-                    "file_extension" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
-                            value,
-                        ))) = arg
-                        {
-                            options.file_extension = value;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
+    if args.get_bool("infer_schema")? == Some(false) {
+        options.schema_infer_max_records = 0;
+    }
+    if args.get_bool("has_header")? == Some(false) {
+        options.has_header = false;
+    }
+    if let Some(value) = args.get_str("delimiter")? {
+        options.delimiter = parse_delimiter(value)?;
     }
+    if let Some(value) = args.get_str("file_extension")? {
+        options.file_extension = value;
+    }
+    options.file_compression_type = file_compression_type(args)?;
 
     Ok(options)
 }
 
+/// Character encoding of CSV input: the `encoding` argument (`gbk`,
+/// `gb18030`, `utf-16le`, `latin1`, ...) when given, otherwise detected from
+/// the start of `content`, the first file read.
+pub(crate) fn csv_encoding(
+    args: &ReaderArgs,
+    content: impl std::io::Read,
+) -> AppResult<&'static Encoding> {
+    match args.get_str("encoding")? {
+        Some(label) => parse_encoding(label),
+        None => detect_encoding(content),
+    }
+}
+
 /// Extract the leading-dot file extension (e.g. `.json`, `.ndjson`) from a
 /// path or glob pattern. Returns `None` for directories or paths without an
-/// extension.
+/// extension. A compression extension is kept together with the one before
+/// it, so `logs.ndjson.zst` gives `.ndjson.zst`.
 ///
 /// DataFusion filters listed objects by `file_extension` even when a single
 /// file is targeted (see `ListingTableUrl::list_prefixed_files`). Deriving the
@@ -146,10 +213,72 @@ pub fn get_csv_read_options<'a>(
 /// conventional extension — e.g. `read_ndjson('data.json')` and
 /// `read_ndjson('data.ndjson')` both match.
 pub(crate) fn path_file_extension(path: &str) -> Option<String> {
-    std::path::Path::new(path)
+    let path = std::path::Path::new(path);
+    let ext = path.extension().and_then(|ext| ext.to_str())?;
+    if path_compression(path).is_compressed() {
+        let inner = path
+            .file_stem()
+            .map(std::path::Path::new)
+            .and_then(|stem| stem.extension())
+            .and_then(|ext| ext.to_str());
+        if let Some(inner) = inner {
+            return Some(format!(".{}.{}", inner, ext));
+        }
+    }
+    Some(format!(".{}", ext))
+}
+
+/// Compression of the files a reader reads: the `compression` argument
+/// (`gzip`, `bzip2`, `xz`, `zstd` or `uncompressed`) when given, otherwise
+/// detected from the path's extension (`.gz`, `.bz2`, `.xz`, `.zst`).
+pub(crate) fn file_compression_type(args: &ReaderArgs) -> AppResult<FileCompressionType> {
+    let Some(value) = args.get_str("compression")? else {
+        return Ok(path_compression(std::path::Path::new(args.path())));
+    };
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(FileCompressionType::UNCOMPRESSED);
+    }
+    FileCompressionType::from_str(value).map_err(|_| AppError::BadRequest {
+        message: format!(
+            "Unsupported compression '{}'. Supported: gzip, bzip2, xz, zstd, uncompressed",
+            value
+        ),
+    })
+}
+
+fn path_compression(path: &std::path::Path) -> FileCompressionType {
+    let ext = path
         .extension()
         .and_then(|ext| ext.to_str())
-        .map(|ext| format!(".{}", ext))
+        .map(|ext| ext.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("gz") => FileCompressionType::GZIP,
+        Some("bz2") => FileCompressionType::BZIP2,
+        Some("xz") => FileCompressionType::XZ,
+        Some("zst") => FileCompressionType::ZSTD,
+        _ => FileCompressionType::UNCOMPRESSED,
+    }
+}
+
+/// The extension DataFusion lists the files of `path` by: `extension`
+/// followed by the extension of `compression`, e.g. `.csv.gz`, since
+/// compressed files are skipped without it. A path whose own extension is not
+/// a compression one (a gzipped `export.csv` read with `compression =>
+/// 'gzip'`) keeps `extension` as is.
+pub(crate) fn listing_extension(
+    extension: &str,
+    compression: &FileCompressionType,
+    path: &str,
+) -> String {
+    let suffix = compression.get_ext();
+    let path = std::path::Path::new(path);
+    let names_uncompressed = path.extension().is_some() && !path_compression(path).is_compressed();
+    if names_uncompressed || extension.to_ascii_lowercase().ends_with(&suffix) {
+        extension.to_string()
+    } else {
+        format!("{}{}", extension, suffix)
+    }
 }
 
 /// Decide whether a JSON source should be parsed as newline-delimited JSON
@@ -160,38 +289,52 @@ pub(crate) fn path_file_extension(path: &str) -> Option<String> {
 /// NDJSON / a single object — which is reliable regardless of the file's
 /// extension. When the path can't be opened as a single file (globs,
 /// directories, remote URLs) it falls back to the extension: only an explicit
-/// `.ndjson` is treated as newline-delimited.
+/// `.ndjson` is treated as newline-delimited. Compressed files are
+/// decompressed with `compression` before sniffing.
 ///
 /// Returns `true` for newline-delimited (NDJSON) and `false` for a JSON array.
-pub(crate) fn detect_json_newline_delimited(path: &str) -> bool {
+pub(crate) fn detect_json_newline_delimited(path: &str, compression: &FileCompressionType) -> bool {
+    let sniffed = std::fs::File::open(path)
+        .ok()
+        .and_then(|file| compression.convert_read(file).ok())
+        .and_then(sniff_json_newline_delimited);
+    if let Some(newline_delimited) = sniffed {
+        return newline_delimited;
+    }
+
+    // Fallback for paths we can't sniff (globs, directories, URLs):
+    // only treat an explicit `.ndjson` extension as newline-delimited.
+    path.to_ascii_lowercase()
+        .ends_with(&listing_extension(".ndjson", compression, path))
+}
+
+/// The content sniffing of [`detect_json_newline_delimited`], for content
+/// that is already open such as a zip archive member. Returns `None` when
+/// there is nothing but whitespace to go by.
+pub(crate) fn sniff_json_newline_delimited(content: impl std::io::Read) -> Option<bool> {
     use std::io::{BufRead, Read};
 
-    if let Ok(file) = std::fs::File::open(path) {
-        let mut reader = std::io::BufReader::new(file);
+    let mut reader = std::io::BufReader::new(content);
 
-        // Skip a leading UTF-8 BOM (0xEF, 0xBB, 0xBF) if present, otherwise it
-        // would be misread as the first content byte and classified as NDJSON.
-        if let Ok(buf) = reader.fill_buf() {
-            if buf.starts_with(&[0xEF, 0xBB, 0xBF]) {
-                reader.consume(3);
-            }
+    // Skip a leading UTF-8 BOM (0xEF, 0xBB, 0xBF) if present, otherwise it
+    // would be misread as the first content byte and classified as NDJSON.
+    if let Ok(buf) = reader.fill_buf() {
+        if buf.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            reader.consume(3);
         }
+    }
 
-        let mut byte = [0u8; 1];
-        while let Ok(1) = reader.read(&mut byte) {
-            match byte[0] {
-                b' ' | b'\t' | b'\r' | b'\n' => continue,
-                // A JSON array starts with '[' -> not newline-delimited.
-                b'[' => return false,
-                // '{' (NDJSON/single object) or anything else -> treat as NDJSON.
-                _ => return true,
-            }
+    let mut byte = [0u8; 1];
+    while let Ok(1) = reader.read(&mut byte) {
+        match byte[0] {
+            b' ' | b'\t' | b'\r' | b'\n' => continue,
+            // A JSON array starts with '[' -> not newline-delimited.
+            b'[' => return Some(false),
+            // '{' (NDJSON/single object) or anything else -> treat as NDJSON.
+            _ => return Some(true),
         }
     }
-
-    // Fallback for paths we can't sniff (globs, directories, URLs):
-    // only treat an explicit `.ndjson` extension as newline-delimited.
-    path.to_ascii_lowercase().ends_with(".ndjson")
+    None
 }
 
 /// Build [`JsonReadOptions`] for `read_json` / `read_ndjson`.
@@ -203,156 +346,41 @@ pub(crate) fn detect_json_newline_delimited(path: &str) -> bool {
 ///   supported natively since DataFusion 53)
 ///
 /// This helper then applies any user-supplied named arguments. Only
-/// `file_extension` is currently honored, which lets callers point a reader at
-/// files that don't use the conventional extension (e.g. NDJSON stored in a
-/// `.json` file).
+/// `file_extension` and `compression` are currently honored; the former lets
+/// callers point a reader at files that don't use the conventional extension
+/// (e.g. NDJSON stored in a `.json` file).
 pub fn get_json_read_options<'a>(
-    args: &'a mut Option<TableFunctionArgs>,
+    args: &'a ReaderArgs,
     mut options: JsonReadOptions<'a>,
 ) -> AppResult<JsonReadOptions<'a>> {
-    let args = get_function_args(args);
-    if let Some(args) = args {
-        for arg in args {
-            if let FunctionArg::Named { name, arg, .. } = arg {
-                match name.value.as_str() {
-                    "file_extension" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
-                            value,
-                        ))) = arg
-                        {
-                            options.file_extension = value;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
+    if let Some(value) = args.get_str("file_extension")? {
+        options.file_extension = value;
     }
+    options.file_compression_type = file_compression_type(args)?;
 
     Ok(options)
 }
 
-pub fn get_table_path(args: &mut Option<TableFunctionArgs>) -> AppResult<String> {
-    if args.is_none() {
-        return Err(AppError::BadRequest {
-            message: "The file path is missing.".to_string(),
-        });
-    }
-
-    let value = &args
-        .as_ref()
-        .unwrap()
-        .args
-        .get(0)
-        .ok_or(AppError::BadRequest {
-            message: "The file path is missing. 2".to_string(),
-        })?;
-
-    match value {
-        FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
-            value,
-        )))) => Ok(value.to_string()),
-        _ => Err(AppError::BadRequest {
-            message: "The file path is missing. 3".to_string(),
-        }),
-    }
-}
-
-pub fn get_path(args: &mut Option<TableFunctionArgs>) -> AppResult<String> {
-    if args.is_none() {
-        return Err(AppError::BadRequest {
-            message: "The file path is missing.".to_string(),
-        });
+pub fn read_excel(mut reader: ExcelReader, args: &ReaderArgs) -> AppResult<RecordBatch> {
+    if let Some(value) = args.get_str("sheet_name")? {
+        reader = reader.with_sheet_name(value.to_string());
     }
-
-    let value = &args
-        .as_ref()
-        .unwrap()
-        .args
-        .get(0)
-        .ok_or(AppError::BadRequest {
-            message: "The file path is missing. 2".to_string(),
-        })?;
-
-    match value {
-        FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
-            value,
-        )))) => Ok(value.to_string()),
-        _ => Err(AppError::BadRequest {
-            message: "The file path is missing. 3".to_string(),
-        }),
+    if args.get_bool("infer_schema")? == Some(false) {
+        reader = reader.with_infer_schema_length(0);
     }
-}
 
-pub fn read_excel(
-    mut reader: ExcelReader,
-    args: &mut Option<TableFunctionArgs>,
-) -> AppResult<RecordBatch> {
-    let args = get_function_args(args);
-
-    if let Some(args) = args {
-        for arg in args {
-            if let FunctionArg::Named { name, arg, .. } = arg {
-                match name.value.as_str() {
-                    "sheet_name" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
-                            value,
-                        ))) = arg
-                        {
-                            reader = reader.with_sheet_name(value.to_string());
-                        }
-                    }
-                    "infer_schema" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::Boolean(value))) = arg {
-                            if !value {
-                                reader = reader.with_infer_schema_length(0);
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
-
-    reader.finish().map_err(|e| e.into())
+    reader.finish()
 }
 
-pub async fn register_mysql(
-    ctx: &mut SessionContext,
-    table_name: &String,
-    table_path: &String,
-    args: &mut Option<TableFunctionArgs>,
-) -> AppResult<()> {
-    let args = get_function_args(args);
-    let mut conn: Option<String> = None;
-
-    if let Some(args) = args {
-        for arg in args {
-            if let FunctionArg::Named { name, arg, .. } = arg {
-                match name.value.as_str() {
-                    "conn" => {
-                        if let FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
-                            value,
-                        ))) = arg
-                        {
-                            conn = Some(value.to_string());
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
-
-    if conn.is_none() {
-        return Err(AppError::BadRequest {
+pub async fn mysql_table_provider(args: &ReaderArgs) -> AppResult<Arc<dyn TableProvider>> {
+    let conn = args
+        .get_str("conn")?
+        .ok_or_else(|| AppError::BadRequest {
             message: "'conn' parameter is required".to_string(),
-        });
-    }
+        })?;
 
     let mysql_params = to_secret_map(HashMap::from([
-        ("connection_string".to_string(), conn.unwrap()),
+        ("connection_string".to_string(), conn.to_string()),
         ("sslmode".to_string(), "disabled".to_string()),
     ]));
 
@@ -363,80 +391,36 @@ pub async fn register_mysql(
     // Used to generate TableProvider instances that can read MySQL table data
     let table_factory = MySQLTableFactory::new(mysql_pool);
 
-    ctx.register_table(
-        table_name,
-        table_factory
-            .table_provider(TableReference::bare(table_path.clone()))
-            .await?,
-    )?;
-
-    Ok(())
+    Ok(table_factory
+        .table_provider(TableReference::bare(args.path().to_string()))
+        .await?)
 }
 
-pub async fn register_postgres(
-    ctx: &mut SessionContext,
-    table_name: &String,
-    table_path: &String,
-    args: &mut Option<TableFunctionArgs>,
-) -> AppResult<()> {
-    let args = get_function_args(args);
-    let mut host: Option<String> = None;
-    let mut username: Option<String> = None;
-    let mut db: Option<String> = None;
-    let mut pass: Option<String> = None;
-    let mut port: Option<String> = None;
-    let mut sslmode: Option<String> = None;
-
-    if let Some(args) = args {
-        for arg in args {
-            if let FunctionArg::Named { name, arg, .. } = arg {
-                if let FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(value))) = arg {
-                    match name.value.as_str() {
-                        "host" => host = Some(value.to_string()),
-                        "username" => username = Some(value.to_string()),
-                        "db" => db = Some(value.to_string()),
-                        "pass" => pass = Some(value.to_string()),
-                        "port" => port = Some(value.to_string()),
-                        "sslmode" => sslmode = Some(value.to_string()),
-                        _ => {}
-                    }
-                }
-            }
-        }
-    }
-
-    if host.is_none() {
-        return Err(AppError::BadRequest {
-            message: "'host' parameter is required".to_string(),
-        });
-    }
-    if username.is_none() {
-        return Err(AppError::BadRequest {
-            message: "'username' parameter is required".to_string(),
-        });
-    }
-    if db.is_none() {
-        return Err(AppError::BadRequest {
-            message: "'db' parameter is required".to_string(),
-        });
-    }
+pub async fn postgres_table_provider(args: &ReaderArgs) -> AppResult<Arc<dyn TableProvider>> {
+    let required = |name: &str| -> AppResult<String> {
+        args.get_str(name)?
+            .map(|value| value.to_string())
+            .ok_or_else(|| AppError::BadRequest {
+                message: format!("'{}' parameter is required", name),
+            })
+    };
 
     let mut params = HashMap::from([
-        ("host".to_string(), host.unwrap()),
-        ("user".to_string(), username.unwrap()),
-        ("db".to_string(), db.unwrap()),
+        ("host".to_string(), required("host")?),
+        ("user".to_string(), required("username")?),
+        ("db".to_string(), required("db")?),
     ]);
 
-    if let Some(pass) = pass {
-        params.insert("pass".to_string(), pass);
+    if let Some(pass) = args.get_str("pass")? {
+        params.insert("pass".to_string(), pass.to_string());
     }
     params.insert(
         "port".to_string(),
-        port.unwrap_or_else(|| "5432".to_string()),
+        args.get_str("port")?.unwrap_or("5432").to_string(),
     );
     params.insert(
         "sslmode".to_string(),
-        sslmode.unwrap_or_else(|| "disable".to_string()),
+        args.get_str("sslmode")?.unwrap_or("disable").to_string(),
     );
 
     let postgres_params = to_secret_map(params);
@@ -445,193 +429,7 @@ pub async fn register_postgres(
 
     let table_factory = PostgresTableFactory::new(postgres_pool);
 
-    ctx.register_table(
-        table_name,
-        table_factory
-            .table_provider(TableReference::bare(table_path.clone()))
-            .await?,
-    )?;
-
-    Ok(())
-}
-
-pub async fn register_table(
-    ctx: &mut SessionContext,
-    relation: &mut TableFactor,
-    table_count: i32,
-) -> AppResult<i32> {
-    if let TableFactor::Table { name, args, .. } = relation {
-        let table_name = format!("table{}", table_count);
-        let table_path = get_table_path(args)?;
-        let reader_name = name.to_string();
-
-        if matches!(
-            reader_name.as_str(),
-            "read_csv"
-                | "read_tsv"
-                | "read_json"
-                | "read_ndjson"
-                | "read_parquet"
-                | "read_text"
-                | "read_excel"
-                | "read_xlsx"
-        ) {
-            ensure_path_exists(&table_path)?;
-        }
-
-        match reader_name.as_str() {
-            "read_csv" => {
-                ctx.register_csv(
-                    &table_name,
-                    &table_path,
-                    get_csv_read_options(args, CsvReadOptions::default())?,
-                )
-                .await?
-            }
-            "read_tsv" => {
-                let mut options = CsvReadOptions::default();
-                options.delimiter = b'\t';
-                options.file_extension = ".tsv";
-                ctx.register_csv(
-                    &table_name,
-                    &table_path,
-                    get_csv_read_options(args, options)?,
-                )
-                .await?
-            }
-            "read_json" => {
-                // Accepts both a standard JSON array `[{...}, {...}]` (parsed
-                // natively since DataFusion 53) and newline-delimited JSON.
-                // The format is auto-detected from the file content, so a
-                // `.ndjson` file works through `read_json` too.
-                let json_ext = path_file_extension(&table_path);
-                let newline_delimited = detect_json_newline_delimited(&table_path);
-                let mut options = JsonReadOptions::default().newline_delimited(newline_delimited);
-                if let Some(ext) = json_ext.as_deref() {
-                    options.file_extension = ext;
-                }
-                ctx.register_json(&table_name, &table_path, get_json_read_options(args, options)?)
-                    .await?
-            }
-            "read_ndjson" => {
-                // Newline-delimited JSON: one JSON object per line.
-                let json_ext = path_file_extension(&table_path);
-                let mut options = JsonReadOptions::default()
-                    .file_extension(".ndjson")
-                    .newline_delimited(true);
-                if let Some(ext) = json_ext.as_deref() {
-                    options.file_extension = ext;
-                }
-                ctx.register_json(&table_name, &table_path, get_json_read_options(args, options)?)
-                    .await?
-            }
-            "read_parquet" => {
-                ctx.register_parquet(&table_name, &table_path, ParquetReadOptions::default())
-                    .await?
-            }
-            "read_excel" | "read_xlsx" => {
-                ctx.register_batch(&table_name, read_excel(ExcelReader::new(table_path), args)?)?;
-            }
-            "read_mysql" => {
-                register_mysql(ctx, &table_name, &table_path, args).await?;
-            }
-            "read_postgres" => {
-                register_postgres(ctx, &table_name, &table_path, args).await?;
-            }
-            "read_text" => {
-                let mut options = CsvReadOptions::default();
-                options.delimiter = b'\t';
-                options.file_extension = ".txt";
-                ctx.register_csv(
-                    &table_name,
-                    &table_path,
-                    get_csv_read_options(args, options)?,
-                )
-                .await?
-            }
-            _ => {
-                return Err(AppError::BadRequest {
-                    message: format!("'{}' is not a supported table function", table_name),
-                })
-            }
-        }
-
-        *name = sqlparser::ast::ObjectName(vec![table_name.as_str().into()]);
-        *args = None;
-    }
-
-    Ok(table_count + 1)
-}
-
-#[async_recursion]
-pub async fn convert_table_name(
-    ctx: &mut SessionContext,
-    query: &mut Box<Query>,
-    mut table_count: i32,
-) -> AppResult<i32> {
-    if let Select(select) = &mut *query.body {
-        for table_with_joins in &mut select.from {
-            match &mut table_with_joins.relation {
-                TableFactor::Derived { subquery, .. } => {
-                    table_count = convert_table_name(ctx, subquery, table_count).await?;
-                }
-                relation => {
-                    table_count = register_table(ctx, relation, table_count).await?;
-                }
-            }
-            for join in &mut table_with_joins.joins {
-                match &mut join.relation {
-                    TableFactor::Derived { subquery, .. } => {
-                        table_count = convert_table_name(ctx, subquery, table_count).await?;
-                    }
-                    relation => {
-                        table_count = register_table(ctx, &mut join.relation, table_count).await?;
-                    }
-                }
-            }
-        }
-    }
-
-    Ok(table_count)
-}
-
-pub async fn register(
-    ctx: &mut SessionContext,
-    sql: &str,
-    limit: Option<usize>,
-    offset: Option<usize>,
-) -> AppResult<String> {
-    let mut ast = parse_statements(sql)?;
-
-    let statement = ast.get_mut(0).ok_or(AppError::BadRequest {
-        message: "invalid SQL statement".to_string(),
-    })?;
-
-    if let Statement::Query(query) = statement {
-        convert_table_name(ctx, query, 0).await?;
-        if limit.is_some() && query.limit.is_none() {
-            query.limit = Some(Expr::Value(Value::Number(limit.unwrap().to_string(), true)));
-        }
-        if offset.is_some()
-            && query.limit.is_some()
-            && query.offset.is_none()
-            && offset.unwrap() > 0
-        {
-            if let Some(Expr::Value(Value::Number(value, _))) = &query.limit {
-                query.offset = Some(Offset {
-                    value: Expr::Value(Value::Number(
-                        (value.parse::<i64>().unwrap() * offset.unwrap() as i64).to_string(),
-                        true,
-                    )),
-                    rows: OffsetRows::None,
-                });
-            }
-        }
-
-        Ok(query.to_string())
-    } else {
-        Err(AppError::BadRequest {
-            message: "Only supports Select statements.".to_string(),
-        })
-    }
+    Ok(table_factory
+        .table_provider(TableReference::bare(args.path().to_string()))
+        .await?)
 }
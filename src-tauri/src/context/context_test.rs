@@ -1,15 +1,17 @@
 use super::context::{
-    collect, detect_json_newline_delimited, get_json_read_options, get_sql_context,
-    path_file_extension, register,
+    collect, collect_with_metrics, detect_json_newline_delimited, get_data_frame,
+    get_json_read_options, get_sql_context, paginate, path_file_extension,
 };
+use super::table_function::{reader_parameters, ReaderArgs};
 use crate::commands::query::ColumnTypeInfo;
+use crate::context::error::AppError;
 use crate::context::schema::AppResult;
-use crate::sql::parse::parse_statements;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
-use datafusion::prelude::JsonReadOptions;
-use sqlparser::ast::SetExpr::Select;
-use sqlparser::ast::{Statement, TableFactor, TableFunctionArgs};
+use datafusion::common::ScalarValue;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
+use datafusion::prelude::{lit, Expr, JsonReadOptions};
+use std::io::Write;
 
 // ═══════════════════════════════════════════════════════════════════════
 // Test helpers
@@ -25,12 +27,51 @@ fn write_temp(suffix: &str, content: &str) -> AppResult<std::path::PathBuf> {
     Ok(path)
 }
 
-/// Run a query through the same pipeline the Tauri commands use: rewrite the
-/// `read_*` table functions into registered tables, then collect the results.
+/// Like [`write_temp`], compressing `content` with gzip or zstd to match the
+/// suffix (`.gz` / `.zst`).
+fn write_temp_compressed(suffix: &str, content: &str) -> AppResult<std::path::PathBuf> {
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_ctx_{}_{}", std::process::id(), suffix));
+    let bytes = if suffix.ends_with(".zst") {
+        zstd::encode_all(content.as_bytes(), 0)?
+    } else {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content.as_bytes())?;
+        encoder.finish()?
+    };
+    std::fs::write(&path, bytes)?;
+    Ok(path)
+}
+
+/// Write a zip archive holding `members` as `(name, content)` pairs.
+fn write_temp_zip(suffix: &str, members: &[(&str, &str)]) -> AppResult<std::path::PathBuf> {
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_ctx_{}_{}", std::process::id(), suffix));
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&path)?);
+    for (name, content) in members {
+        zip.start_file(*name, zip::write::SimpleFileOptions::default())?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish()?;
+    Ok(path)
+}
+
+/// Run a query through the same pipeline the Tauri commands use: plan it on a
+/// session with the `read_*` table functions registered, then collect the
+/// results.
 async fn run_query(sql: &str) -> AppResult<(Vec<ColumnTypeInfo>, Vec<RecordBatch>)> {
     let mut ctx = get_sql_context();
-    let rewritten = register(&mut ctx, sql, None, None).await?;
-    collect(&mut ctx, &rewritten).await
+    let df = get_data_frame(&mut ctx, sql).await?;
+    collect(df).await
+}
+
+/// Like [`run_query`], fetching page `page` of `limit` rows the way `fetch`
+/// does.
+async fn run_page(sql: &str, limit: usize, page: usize) -> AppResult<Vec<RecordBatch>> {
+    let mut ctx = get_sql_context();
+    let df = get_data_frame(&mut ctx, sql).await?;
+    let (_columns, batches) = collect(paginate(df, Some(limit), Some(page))?).await?;
+    Ok(batches)
 }
 
 /// Index of the column named `name` within the collected metadata.
@@ -59,22 +100,11 @@ fn total_rows(batches: &[RecordBatch]) -> usize {
     batches.iter().map(|b| b.num_rows()).sum()
 }
 
-/// Pull the `TableFunctionArgs` out of the first table function in `sql`.
-fn first_table_args(sql: &str) -> AppResult<Option<TableFunctionArgs>> {
-    let mut ast = parse_statements(sql)?;
-    if ast.is_empty() {
-        return Ok(None);
-    }
-    if let Statement::Query(query) = ast.remove(0) {
-        if let Select(select) = *query.body {
-            if let Some(twj) = select.from.into_iter().next() {
-                if let TableFactor::Table { args, .. } = twj.relation {
-                    return Ok(args);
-                }
-            }
-        }
-    }
-    Ok(None)
+/// Build the arguments of a `function` call from literal expressions, the
+/// same way DataFusion hands them to the table function.
+fn reader_args(function: &str, exprs: Vec<Expr>) -> AppResult<ReaderArgs> {
+    let parameters = reader_parameters(function).expect("known reader");
+    ReaderArgs::try_new(function, parameters, &exprs)
 }
 
 /// Shared sample matching `fixtures/users.*`.
@@ -127,6 +157,19 @@ fn test_path_file_extension_multiple_dots_uses_last() {
     );
 }
 
+#[test]
+fn test_path_file_extension_keeps_compressed_file_type() {
+    assert_eq!(
+        path_file_extension("orders.csv.gz").as_deref(),
+        Some(".csv.gz")
+    );
+    assert_eq!(
+        path_file_extension("logs/*.ndjson.zst").as_deref(),
+        Some(".ndjson.zst")
+    );
+    assert_eq!(path_file_extension("dump.gz").as_deref(), Some(".gz"));
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Stability Tests — detect_json_newline_delimited
 // ═══════════════════════════════════════════════════════════════════════
@@ -135,7 +178,8 @@ fn test_path_file_extension_multiple_dots_uses_last() {
 fn test_detect_json_newline_delimited_sniffs_array_content() -> AppResult<()> {
     // Leading whitespace before `[` must still be detected as an array.
     let path = write_temp("detect_array.json", "  \n  [ {\"a\":1} ]")?;
-    let result = detect_json_newline_delimited(&path.to_string_lossy());
+    let result =
+        detect_json_newline_delimited(&path.to_string_lossy(), &FileCompressionType::UNCOMPRESSED);
     let _ = std::fs::remove_file(&path);
     assert!(!result, "array content should not be newline-delimited");
     Ok(())
@@ -145,7 +189,8 @@ fn test_detect_json_newline_delimited_sniffs_array_content() -> AppResult<()> {
 fn test_detect_json_newline_delimited_sniffs_ndjson_content() -> AppResult<()> {
     // NDJSON content inside a `.json` file must be detected as newline-delimited.
     let path = write_temp("detect_nd.json", "{\"a\":1}\n{\"a\":2}\n")?;
-    let result = detect_json_newline_delimited(&path.to_string_lossy());
+    let result =
+        detect_json_newline_delimited(&path.to_string_lossy(), &FileCompressionType::UNCOMPRESSED);
     let _ = std::fs::remove_file(&path);
     assert!(result, "ndjson content should be newline-delimited");
     Ok(())
@@ -155,7 +200,8 @@ fn test_detect_json_newline_delimited_sniffs_ndjson_content() -> AppResult<()> {
 fn test_detect_json_newline_delimited_skips_utf8_bom() -> AppResult<()> {
     // A UTF-8 BOM before a `[` must not be mistaken for NDJSON content.
     let path = write_temp("detect_bom_array.json", "\u{feff}[ {\"a\":1} ]")?;
-    let result = detect_json_newline_delimited(&path.to_string_lossy());
+    let result =
+        detect_json_newline_delimited(&path.to_string_lossy(), &FileCompressionType::UNCOMPRESSED);
     let _ = std::fs::remove_file(&path);
     assert!(!result, "BOM-prefixed array should not be newline-delimited");
     Ok(())
@@ -164,9 +210,23 @@ fn test_detect_json_newline_delimited_skips_utf8_bom() -> AppResult<()> {
 #[test]
 fn test_detect_json_newline_delimited_falls_back_to_extension() {
     // Unreadable paths (e.g. globs) fall back to the extension.
-    assert!(detect_json_newline_delimited("missing/dir/*.ndjson"));
-    assert!(!detect_json_newline_delimited("missing/dir/*.json"));
-    assert!(!detect_json_newline_delimited("missing/dir/"));
+    let uncompressed = FileCompressionType::UNCOMPRESSED;
+    assert!(detect_json_newline_delimited("missing/dir/*.ndjson", &uncompressed));
+    assert!(!detect_json_newline_delimited("missing/dir/*.json", &uncompressed));
+    assert!(!detect_json_newline_delimited("missing/dir/", &uncompressed));
+    assert!(detect_json_newline_delimited(
+        "missing/dir/*.ndjson.gz",
+        &FileCompressionType::GZIP
+    ));
+}
+
+#[test]
+fn test_detect_json_newline_delimited_sniffs_compressed_content() -> AppResult<()> {
+    let path = write_temp_compressed("detect_array.json.gz", USERS_JSON_ARRAY)?;
+    let result = detect_json_newline_delimited(&path.to_string_lossy(), &FileCompressionType::GZIP);
+    let _ = std::fs::remove_file(&path);
+    assert!(!result, "gzipped array should not be newline-delimited");
+    Ok(())
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -175,9 +235,9 @@ fn test_detect_json_newline_delimited_falls_back_to_extension() {
 
 #[test]
 fn test_get_json_read_options_preserves_base_when_no_args() -> AppResult<()> {
-    let mut args: Option<TableFunctionArgs> = None;
+    let args = reader_args("read_json", vec![lit("x")])?;
     let base = JsonReadOptions::default().newline_delimited(false);
-    let result = get_json_read_options(&mut args, base)?;
+    let result = get_json_read_options(&args, base)?;
 
     // The array-format flag set by the caller must survive untouched.
     assert!(!result.newline_delimited);
@@ -187,23 +247,31 @@ fn test_get_json_read_options_preserves_base_when_no_args() -> AppResult<()> {
 
 #[test]
 fn test_get_json_read_options_overrides_file_extension() -> AppResult<()> {
-    let mut args =
-        first_table_args("SELECT * FROM read_ndjson('x', file_extension => '.custom')")?;
+    let args = reader_args("read_ndjson", vec![lit("x"), lit(".custom")])?;
     let base = JsonReadOptions::default();
-    let result = get_json_read_options(&mut args, base)?;
+    let result = get_json_read_options(&args, base)?;
 
     assert_eq!(result.file_extension, ".custom");
     Ok(())
 }
 
-#[test]
-fn test_get_json_read_options_ignores_unknown_args() -> AppResult<()> {
-    let mut args = first_table_args("SELECT * FROM read_json('x', unknown_opt => 'value')")?;
-    let base = JsonReadOptions::default().newline_delimited(false);
-    let result = get_json_read_options(&mut args, base)?;
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_json_rejects_unknown_args() -> AppResult<()> {
+    let path = write_temp("unknown_arg.json", USERS_JSON_ARRAY)?;
+    let sql = format!(
+        "SELECT * FROM read_json('{}', unknown_opt => 'value')",
+        path.to_string_lossy()
+    );
 
-    assert!(!result.newline_delimited);
-    assert_eq!(result.file_extension, ".json");
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+
+    let message = format!("{:?}", result.unwrap_err());
+    assert!(
+        message.contains("Unknown argument 'unknown_opt' for read_json"),
+        "got: {}",
+        message
+    );
     Ok(())
 }
 
@@ -238,7 +306,7 @@ fn assert_users(columns: &[ColumnTypeInfo], batches: &[RecordBatch]) -> AppResul
     Ok(())
 }
 
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_read_json_parses_standard_array() -> AppResult<()> {
     let path = write_temp("array.json", USERS_JSON_ARRAY)?;
     let sql = format!("SELECT * FROM read_json('{}')", path.to_string_lossy());
@@ -250,7 +318,7 @@ async fn test_read_json_parses_standard_array() -> AppResult<()> {
     assert_users(&columns, &batches)
 }
 
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_read_ndjson_parses_newline_delimited() -> AppResult<()> {
     let path = write_temp("nd.ndjson", USERS_NDJSON)?;
     let sql = format!("SELECT * FROM read_ndjson('{}')", path.to_string_lossy());
@@ -265,7 +333,7 @@ async fn test_read_ndjson_parses_newline_delimited() -> AppResult<()> {
 /// Regression: the frontend maps dropped `.json` files to `read_ndjson(...)`.
 /// Because the extension filter is derived from the path, NDJSON content stored
 /// in a `.json` file must still load.
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_read_ndjson_supports_json_extension() -> AppResult<()> {
     let path = write_temp("ndjson_in.json", USERS_NDJSON)?;
     let sql = format!("SELECT * FROM read_ndjson('{}')", path.to_string_lossy());
@@ -280,7 +348,7 @@ async fn test_read_ndjson_supports_json_extension() -> AppResult<()> {
 
 /// `read_json` auto-detects newline-delimited content, so NDJSON stored in a
 /// `.json` file loads correctly through `read_json`.
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_read_json_handles_ndjson_content() -> AppResult<()> {
     let path = write_temp("json_with_ndjson.json", USERS_NDJSON)?;
     let sql = format!("SELECT * FROM read_json('{}')", path.to_string_lossy());
@@ -294,7 +362,7 @@ async fn test_read_json_handles_ndjson_content() -> AppResult<()> {
 
 /// `read_json` also accepts a real `.ndjson` file (frontend now routes
 /// `.ndjson` here too).
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_read_json_reads_ndjson_file() -> AppResult<()> {
     let path = write_temp("real.ndjson", USERS_NDJSON)?;
     let sql = format!("SELECT * FROM read_json('{}')", path.to_string_lossy());
@@ -308,7 +376,7 @@ async fn test_read_json_reads_ndjson_file() -> AppResult<()> {
 
 /// A WHERE clause over the parsed array confirms typed columns are queryable,
 /// not just present.
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread")]
 async fn test_read_json_array_is_filterable() -> AppResult<()> {
     let path = write_temp("filter.json", USERS_JSON_ARRAY)?;
     let sql = format!(
@@ -324,3 +392,582 @@ async fn test_read_json_array_is_filterable() -> AppResult<()> {
     assert_eq!(column_strings(&batches, 0)?, vec!["alice", "carol", "eve"]);
     Ok(())
 }
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests — read_* inside CTEs, set operations and sub-queries
+// ═══════════════════════════════════════════════════════════════════════
+
+const USERS_CSV: &str = "\
+id,name,age
+1,alice,30
+2,bob,25
+3,carol,41
+";
+
+const ORDERS_CSV: &str = "\
+order_id,user_id
+10,1
+11,3
+12,3
+";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_inside_cte() -> AppResult<()> {
+    let path = write_temp("cte_users.csv", USERS_CSV)?;
+    let sql = format!(
+        "WITH u AS (SELECT * FROM read_csv('{}')) SELECT name FROM u WHERE age > 26 ORDER BY id",
+        path.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "carol"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_on_both_sides_of_union_all() -> AppResult<()> {
+    let users = write_temp("union_a.csv", USERS_CSV)?;
+    let more = write_temp("union_b.csv", "id,name,age\n4,dan,38\n")?;
+    let sql = format!(
+        "SELECT name FROM read_csv('{}') UNION ALL SELECT name FROM read_csv('{}')",
+        users.to_string_lossy(),
+        more.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&users);
+    let _ = std::fs::remove_file(&more);
+    let (_columns, batches) = result?;
+
+    assert_eq!(total_rows(&batches), 4);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_inside_in_subquery() -> AppResult<()> {
+    let users = write_temp("in_users.csv", USERS_CSV)?;
+    let orders = write_temp("in_orders.csv", ORDERS_CSV)?;
+    let sql = format!(
+        "SELECT name FROM read_csv('{}') WHERE id IN (SELECT user_id FROM read_csv('{}')) ORDER BY id",
+        users.to_string_lossy(),
+        orders.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&users);
+    let _ = std::fs::remove_file(&orders);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "carol"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_inside_exists_subquery() -> AppResult<()> {
+    let users = write_temp("exists_users.csv", USERS_CSV)?;
+    let orders = write_temp("exists_orders.csv", ORDERS_CSV)?;
+    let sql = format!(
+        "SELECT u.name FROM read_csv('{}') u \
+         WHERE NOT EXISTS (SELECT 1 FROM read_csv('{}') o WHERE o.user_id = u.id)",
+        users.to_string_lossy(),
+        orders.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&users);
+    let _ = std::fs::remove_file(&orders);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["bob"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_inside_nested_join() -> AppResult<()> {
+    let users = write_temp("nested_users.csv", USERS_CSV)?;
+    let orders = write_temp("nested_orders.csv", ORDERS_CSV)?;
+    let sql = format!(
+        "SELECT u.name FROM (read_csv('{}') u JOIN read_csv('{}') o ON o.user_id = u.id) \
+         ORDER BY o.order_id",
+        users.to_string_lossy(),
+        orders.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&users);
+    let _ = std::fs::remove_file(&orders);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "carol", "carol"]);
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests — table function arguments
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_paginate_pages_within_query_limit() -> AppResult<()> {
+    let path = write_temp("paginate_users.csv", USERS_CSV)?;
+    let sql = format!(
+        "SELECT name FROM read_csv('{}') ORDER BY id",
+        path.to_string_lossy()
+    );
+    let limited_sql = format!("{} LIMIT 2", sql);
+
+    let paged = run_page(&sql, 1, 1).await;
+    let limited = run_page(&limited_sql, 1, 1).await;
+    let past_limit = run_page(&limited_sql, 1, 2).await;
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(column_strings(&paged?, 0)?, vec!["bob"]);
+    assert_eq!(column_strings(&limited?, 0)?, vec!["bob"]);
+    assert!(column_strings(&past_limit?, 0)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_reader_args_positional_values() -> AppResult<()> {
+    let args = reader_args(
+        "read_csv",
+        vec![lit("data.csv"), lit(false), lit(ScalarValue::Null), lit(";")],
+    )?;
+
+    assert_eq!(args.path(), "data.csv");
+    assert_eq!(args.get_bool("infer_schema")?, Some(false));
+    // NULL marks a skipped positional argument.
+    assert_eq!(args.get_bool("has_header")?, None);
+    assert_eq!(args.get_str("delimiter")?, Some(";"));
+    assert_eq!(args.get_str("file_extension")?, None);
+    Ok(())
+}
+
+#[test]
+fn test_reader_args_rejects_wrong_type() -> AppResult<()> {
+    let args = reader_args("read_csv", vec![lit("data.csv"), lit("yes")])?;
+    let err = args.get_bool("infer_schema").unwrap_err();
+    assert!(matches!(err, AppError::BadRequest { .. }));
+    Ok(())
+}
+
+#[test]
+fn test_reader_args_rejects_too_many_arguments() {
+    let err = reader_args("read_parquet", vec![lit("data.parquet"), lit(true)]).unwrap_err();
+    assert!(matches!(err, AppError::BadRequest { .. }));
+}
+
+#[test]
+fn test_reader_args_requires_path() {
+    let err = reader_args("read_csv", vec![]).unwrap_err();
+    assert!(matches!(err, AppError::BadRequest { .. }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_named_arguments() -> AppResult<()> {
+    let path = write_temp("named_args.csv", "id;name\n1;alice\n2;bob\n")?;
+    let sql = format!(
+        "SELECT name FROM read_csv('{}', delimiter => ';', infer_schema => false) ORDER BY id",
+        path.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "bob"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_path_by_name() -> AppResult<()> {
+    let path = write_temp("named_path.csv", "id;name\n1;alice\n2;bob\n")?;
+    let sql = format!(
+        "SELECT name FROM read_csv(delimiter => ';', path => '{}') ORDER BY id",
+        path.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "bob"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_argument_given_twice() {
+    for (sql, expected) in [
+        (
+            "SELECT * FROM read_csv('a.csv', true, infer_schema => false)",
+            "Argument 'infer_schema' of read_csv given twice",
+        ),
+        (
+            "SELECT * FROM read_csv('a.csv', path => 'b.csv')",
+            "Argument 'path' of read_csv given twice",
+        ),
+        (
+            "SELECT * FROM read_csv('a.csv', delimiter => ';', DELIMITER => ',')",
+            "Argument 'delimiter' of read_csv given twice",
+        ),
+    ] {
+        let message = format!("{:?}", run_query(sql).await.unwrap_err());
+        assert!(message.contains(expected), "got: {}", message);
+    }
+}
+
+#[tokio::test]
+async fn test_read_csv_on_current_thread_runtime_errors() {
+    let message = format!(
+        "{:?}",
+        run_query("SELECT * FROM read_csv('a.csv')").await.unwrap_err()
+    );
+    assert!(message.contains("multi-threaded"), "got: {}", message);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_missing_file_reports_file_not_found() {
+    let err = run_query("SELECT * FROM read_csv('/tmp/easydb_ctx_missing_12345.csv')")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::FileNotFound { .. }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_explain_over_read_csv() -> AppResult<()> {
+    let path = write_temp("explain.csv", USERS_CSV)?;
+    let sql = format!("EXPLAIN SELECT * FROM read_csv('{}')", path.to_string_lossy());
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (columns, _batches) = result?;
+
+    assert_eq!(columns[0].column_name, "plan_type");
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_create_view_over_read_csv() -> AppResult<()> {
+    let path = write_temp("view.csv", USERS_CSV)?;
+    let mut ctx = get_sql_context();
+    let create = format!(
+        "CREATE VIEW young AS SELECT * FROM read_csv('{}') WHERE age < 35",
+        path.to_string_lossy()
+    );
+
+    let result = async {
+        collect(get_data_frame(&mut ctx, &create).await?).await?;
+        let df = get_data_frame(&mut ctx, "SELECT name FROM young ORDER BY id").await?;
+        collect(df).await
+    }
+    .await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "bob"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collect_with_metrics_reports_rows_scanned() -> AppResult<()> {
+    let path = write_temp("metrics.csv", USERS_CSV)?;
+    let mut ctx = get_sql_context();
+    let sql = format!("SELECT name FROM read_csv('{}') WHERE age < 35", path.to_string_lossy());
+
+    let result = async {
+        let df = get_data_frame(&mut ctx, &sql).await?;
+        collect_with_metrics(df).await
+    }
+    .await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches, metrics) = result?;
+
+    let returned: usize = batches.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(returned, 2);
+    assert_eq!(metrics.rows_scanned, Some(USERS_CSV.lines().count() - 1));
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests — compressed input
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_detects_gzip_from_extension() -> AppResult<()> {
+    let path = write_temp_compressed("users.csv.gz", USERS_CSV)?;
+    let sql = format!("SELECT name FROM read_csv('{}') ORDER BY id", path.to_string_lossy());
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "bob", "carol"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_ndjson_and_json_detect_zstd() -> AppResult<()> {
+    let nd = write_temp_compressed("users.ndjson.zst", USERS_NDJSON)?;
+    let array = write_temp_compressed("users.json.zst", USERS_JSON_ARRAY)?;
+
+    let result = async {
+        let nd_sql = format!("SELECT * FROM read_ndjson('{}')", nd.to_string_lossy());
+        let array_sql = format!("SELECT * FROM read_json('{}')", array.to_string_lossy());
+        Ok::<_, AppError>((run_query(&nd_sql).await?, run_query(&array_sql).await?))
+    }
+    .await;
+    let _ = std::fs::remove_file(&nd);
+    let _ = std::fs::remove_file(&array);
+    let ((nd_columns, nd_batches), (array_columns, array_batches)) = result?;
+
+    assert_users(&nd_columns, &nd_batches)?;
+    assert_users(&array_columns, &array_batches)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_compressed_glob() -> AppResult<()> {
+    let mut dir = std::env::temp_dir();
+    dir.push(format!("easydb_ctx_{}_gz_glob", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let mut first = flate2::write::GzEncoder::new(
+        std::fs::File::create(dir.join("a.csv.gz"))?,
+        flate2::Compression::default(),
+    );
+    first.write_all(USERS_CSV.as_bytes())?;
+    first.finish()?;
+    std::fs::copy(dir.join("a.csv.gz"), dir.join("b.csv.gz"))?;
+    let sql = format!(
+        "SELECT count(*) AS n FROM read_csv('{}/*.csv.gz')",
+        dir.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_dir_all(&dir);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["6"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_compression_argument_overrides_extension() -> AppResult<()> {
+    // Gzipped content behind a plain `.csv` name.
+    let gzipped = write_temp_compressed("gz.csv.gz", USERS_CSV)?;
+    let path = gzipped.with_extension("");
+    std::fs::rename(&gzipped, &path)?;
+    let sql = format!(
+        "SELECT name FROM read_csv('{}', compression => 'gzip') ORDER BY id",
+        path.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["alice", "bob", "carol"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unknown_compression_is_rejected() -> AppResult<()> {
+    let path = write_temp("bad_compression.csv", USERS_CSV)?;
+    let sql = format!(
+        "SELECT * FROM read_csv('{}', compression => 'rar')",
+        path.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+
+    let message = format!("{:?}", result.unwrap_err());
+    assert!(
+        message.contains("Unsupported compression 'rar'"),
+        "got: {}",
+        message
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_inside_zip_archive() -> AppResult<()> {
+    let path = write_temp_zip(
+        "export.zip",
+        &[
+            ("2024/a.csv", USERS_CSV),
+            ("2024/b.csv", USERS_CSV),
+            ("2023/c.csv", USERS_CSV),
+        ],
+    )?;
+    let sql = format!(
+        "SELECT count(*) AS n FROM read_csv('{}!/2024/*.csv')",
+        path.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["6"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_zip_with_pattern_and_format() -> AppResult<()> {
+    let users_json = r#"[{"id": 1, "name": "alice"}, {"id": 2, "name": "bob"}]"#;
+    let path = write_temp_zip(
+        "read_zip.zip",
+        &[("users.csv", USERS_CSV), ("data/users.json", users_json)],
+    )?;
+    let archive = path.to_string_lossy();
+
+    let csv = run_query(&format!(
+        "SELECT name FROM read_zip('{}', format => 'csv') ORDER BY id",
+        archive
+    ))
+    .await;
+    let json = run_query(&format!(
+        "SELECT name FROM read_zip('{}', pattern => 'data/*.json') ORDER BY id",
+        archive
+    ))
+    .await;
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(column_strings(&csv?.1, 0)?, vec!["alice", "bob", "carol"]);
+    assert_eq!(column_strings(&json?.1, 0)?, vec!["alice", "bob"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_zip_tables_in_one_query_read_their_own_members() -> AppResult<()> {
+    let path = write_temp_zip(
+        "join.zip",
+        &[("users.csv", USERS_CSV), ("ids.csv", "id\n2\n3\n")],
+    )?;
+    let archive = path.to_string_lossy();
+    let sql = format!(
+        "SELECT u.name FROM read_csv('{0}!/users.csv') u \
+         JOIN read_zip('{0}', pattern => 'ids.csv') i ON u.id = i.id ORDER BY u.id",
+        archive
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["bob", "carol"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_detects_gbk() -> AppResult<()> {
+    let content = "编号,姓名,城市\n".to_string() + &"1,张三,北京\n2,李四,上海\n".repeat(20);
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_ctx_{}_gbk.csv", std::process::id()));
+    std::fs::write(&path, encoding_rs::GBK.encode(&content).0)?;
+    let sql = format!(
+        "SELECT DISTINCT \"城市\" FROM read_csv('{}') ORDER BY \"城市\"",
+        path.to_string_lossy()
+    );
+
+    let result = run_query(&sql).await;
+    let _ = std::fs::remove_file(&path);
+    let (_columns, batches) = result?;
+
+    assert_eq!(column_strings(&batches, 0)?, vec!["上海", "北京"]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_gbk_files_and_zip_members() -> AppResult<()> {
+    let content = "编号,城市\n".to_string() + &"1,北京\n2,上海\n".repeat(20);
+    let gbk = encoding_rs::GBK.encode(&content).0.into_owned();
+    let mut dir = std::env::temp_dir();
+    dir.push(format!("easydb_ctx_{}_gbk_glob", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("a.csv"), &gbk)?;
+    std::fs::write(dir.join("b.csv"), &gbk)?;
+    let archive = dir.join("gbk.zip");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive)?);
+    zip.start_file("cities.csv", zip::write::SimpleFileOptions::default())?;
+    zip.write_all(&gbk)?;
+    zip.finish()?;
+
+    let files = run_query(&format!(
+        "SELECT count(*), count(DISTINCT \"城市\") FROM read_csv('{}/*.csv')",
+        dir.to_string_lossy()
+    ))
+    .await;
+    let member = run_query(&format!(
+        "SELECT \"城市\" FROM read_zip('{}', format => 'csv') WHERE \"编号\" = 2 LIMIT 1",
+        archive.to_string_lossy()
+    ))
+    .await;
+    let json = run_query(&format!(
+        "SELECT * FROM read_zip('{}', pattern => '*.json', encoding => 'gbk')",
+        archive.to_string_lossy()
+    ))
+    .await;
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let (_columns, batches) = files?;
+    assert_eq!(column_strings(&batches, 0)?, vec!["80"]);
+    assert_eq!(column_strings(&batches, 1)?, vec!["2"]);
+    assert_eq!(column_strings(&member?.1, 0)?, vec!["上海"]);
+    let message = format!("{:?}", json.unwrap_err());
+    assert!(
+        message.contains("'encoding' is only supported for CSV"),
+        "got: {}",
+        message
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_csv_encoding_argument() -> AppResult<()> {
+    // Latin-1 "José" and UTF-16 with a BOM behind a gzip layer.
+    let mut latin1 = std::env::temp_dir();
+    latin1.push(format!("easydb_ctx_{}_latin1.csv", std::process::id()));
+    std::fs::write(&latin1, b"id,name\n1,Jos\xE9\n")?;
+    let utf16: Vec<u8> = "\u{FEFF}id,name\n1,王五\n"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let mut gzipped = std::env::temp_dir();
+    gzipped.push(format!("easydb_ctx_{}_utf16.csv.gz", std::process::id()));
+    let mut encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&gzipped)?,
+        flate2::Compression::default(),
+    );
+    encoder.write_all(&utf16)?;
+    encoder.finish()?;
+
+    let latin1_result = run_query(&format!(
+        "SELECT name FROM read_csv('{}', encoding => 'latin1')",
+        latin1.to_string_lossy()
+    ))
+    .await;
+    let utf16_result = run_query(&format!(
+        "SELECT name FROM read_csv('{}')",
+        gzipped.to_string_lossy()
+    ))
+    .await;
+    let unknown = run_query(&format!(
+        "SELECT name FROM read_csv('{}', encoding => 'klingon')",
+        latin1.to_string_lossy()
+    ))
+    .await;
+    let _ = std::fs::remove_file(&latin1);
+    let _ = std::fs::remove_file(&gzipped);
+
+    assert_eq!(column_strings(&latin1_result?.1, 0)?, vec!["José"]);
+    assert_eq!(column_strings(&utf16_result?.1, 0)?, vec!["王五"]);
+    let message = format!("{:?}", unknown.unwrap_err());
+    assert!(
+        message.contains("Unsupported encoding 'klingon'"),
+        "got: {}",
+        message
+    );
+    Ok(())
+}
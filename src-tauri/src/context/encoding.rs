@@ -0,0 +1,293 @@
+use crate::commands::query::format_rows;
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use datafusion::arrow::array::{Array, AsArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{
+    DataType, Float16Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    SchemaRef, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use datafusion::arrow::ipc::writer::StreamWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+use std::str::FromStr;
+
+/// Largest integer a JavaScript number represents exactly (2^53 - 1).
+const MAX_SAFE_INTEGER: i64 = 9_007_199_254_740_991;
+
+/// How result rows are sent to the frontend.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResultEncoding {
+    /// Display strings, with NULL rendered as `"NULL"`.
+    #[default]
+    Text,
+    /// JSON values that keep the Arrow type: numbers, booleans and real
+    /// nulls, ISO 8601 dates and timestamps, base64 binary, and nested
+    /// lists/structs/maps as JSON arrays and objects.
+    Json,
+    /// The record batches themselves, as a base64 encoded Arrow IPC stream.
+    ArrowIpc,
+}
+
+impl FromStr for ResultEncoding {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> AppResult<Self> {
+        match s {
+            "text" => Ok(ResultEncoding::Text),
+            "json" => Ok(ResultEncoding::Json),
+            "arrow_ipc" => Ok(ResultEncoding::ArrowIpc),
+            _ => Err(AppError::BadRequest {
+                message: format!(
+                    "Invalid encoding: '{}'. Please use 'text', 'json' or 'arrow_ipc'.",
+                    s
+                ),
+            }),
+        }
+    }
+}
+
+impl ResultEncoding {
+    /// Parse an optional command argument, defaulting to [`ResultEncoding::Text`].
+    pub fn parse(encoding: Option<&str>) -> AppResult<Self> {
+        encoding.map_or(Ok(ResultEncoding::Text), str::parse)
+    }
+}
+
+/// Rows in the requested encoding. Exactly one field is populated: `rows`
+/// for `text` (left empty otherwise), `typed_rows` for `json` and
+/// `arrow_ipc` for `arrow_ipc`.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct EncodedRows {
+    pub rows: Vec<Vec<String>>,
+    pub typed_rows: Option<Vec<Vec<Value>>>,
+    pub arrow_ipc: Option<String>,
+}
+
+impl EncodedRows {
+    pub fn encode(
+        encoding: ResultEncoding,
+        schema: &SchemaRef,
+        batches: &[RecordBatch],
+    ) -> AppResult<Self> {
+        Ok(match encoding {
+            ResultEncoding::Text => EncodedRows {
+                rows: format_rows(batches)?,
+                ..Default::default()
+            },
+            ResultEncoding::Json => EncodedRows {
+                typed_rows: Some(batches_to_json_rows(batches)?),
+                ..Default::default()
+            },
+            ResultEncoding::ArrowIpc => EncodedRows {
+                arrow_ipc: Some(batches_to_ipc_base64(schema, batches)?),
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// Convert record batches to rows of JSON values, see [`ResultEncoding::Json`].
+pub fn batches_to_json_rows(batches: &[RecordBatch]) -> AppResult<Vec<Vec<Value>>> {
+    let total_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+    let mut rows: Vec<Vec<Value>> = Vec::with_capacity(total_rows);
+
+    for batch in batches {
+        let mut columns = batch
+            .columns()
+            .iter()
+            .map(|c| column_to_json(c.as_ref()).map(Vec::into_iter))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        for _ in 0..batch.num_rows() {
+            rows.push(
+                columns
+                    .iter_mut()
+                    .map(|column| column.next().unwrap_or(Value::Null))
+                    .collect(),
+            );
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Serialize record batches as an Arrow IPC stream, base64 encoded so it can
+/// travel in the JSON command response.
+pub fn batches_to_ipc_base64(schema: &SchemaRef, batches: &[RecordBatch]) -> AppResult<String> {
+    let mut buffer = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut buffer, schema)?;
+    for batch in batches {
+        writer.write(batch)?;
+    }
+    writer.finish()?;
+    drop(writer);
+
+    Ok(BASE64_STANDARD.encode(buffer))
+}
+
+/// Every value of `array` as JSON, nulls included.
+fn column_to_json(array: &dyn Array) -> AppResult<Vec<Value>> {
+    let values = match array.data_type() {
+        DataType::Null => vec![Value::Null; array.len()],
+        DataType::Boolean => map_values(array.as_boolean().iter(), Value::Bool),
+        DataType::Int8 => map_values(array.as_primitive::<Int8Type>().iter(), Value::from),
+        DataType::Int16 => map_values(array.as_primitive::<Int16Type>().iter(), Value::from),
+        DataType::Int32 => map_values(array.as_primitive::<Int32Type>().iter(), Value::from),
+        DataType::Int64 => map_values(array.as_primitive::<Int64Type>().iter(), int_to_json),
+        DataType::UInt8 => map_values(array.as_primitive::<UInt8Type>().iter(), Value::from),
+        DataType::UInt16 => map_values(array.as_primitive::<UInt16Type>().iter(), Value::from),
+        DataType::UInt32 => map_values(array.as_primitive::<UInt32Type>().iter(), Value::from),
+        DataType::UInt64 => map_values(array.as_primitive::<UInt64Type>().iter(), uint_to_json),
+        DataType::Float16 => map_values(array.as_primitive::<Float16Type>().iter(), |v| {
+            float_to_json(v.to_f64())
+        }),
+        DataType::Float32 => map_values(array.as_primitive::<Float32Type>().iter(), |v| {
+            float_to_json(v as f64)
+        }),
+        DataType::Float64 => map_values(array.as_primitive::<Float64Type>().iter(), float_to_json),
+        DataType::Utf8 => map_values(array.as_string::<i32>().iter(), Value::from),
+        DataType::LargeUtf8 => map_values(array.as_string::<i64>().iter(), Value::from),
+        DataType::Utf8View => map_values(array.as_string_view().iter(), Value::from),
+        DataType::Binary => map_values(array.as_binary::<i32>().iter(), binary_to_json),
+        DataType::LargeBinary => map_values(array.as_binary::<i64>().iter(), binary_to_json),
+        DataType::BinaryView => map_values(array.as_binary_view().iter(), binary_to_json),
+        DataType::FixedSizeBinary(_) => {
+            map_values(array.as_fixed_size_binary().iter(), binary_to_json)
+        }
+        DataType::List(_) => {
+            let list = array.as_list::<i32>();
+            nested_values(array, |row| column_to_json(list.value(row).as_ref()).map(Value::Array))?
+        }
+        DataType::LargeList(_) => {
+            let list = array.as_list::<i64>();
+            nested_values(array, |row| column_to_json(list.value(row).as_ref()).map(Value::Array))?
+        }
+        DataType::FixedSizeList(_, _) => {
+            let list = array.as_fixed_size_list();
+            nested_values(array, |row| column_to_json(list.value(row).as_ref()).map(Value::Array))?
+        }
+        DataType::Struct(fields) => {
+            let strukt = array.as_struct();
+            let mut columns = strukt
+                .columns()
+                .iter()
+                .map(|c| column_to_json(c.as_ref()).map(Vec::into_iter))
+                .collect::<AppResult<Vec<_>>>()?;
+
+            (0..array.len())
+                .map(|row| {
+                    // Advance every child column, even for null structs, so
+                    // the children stay aligned with `row`.
+                    let object: Map<String, Value> = fields
+                        .iter()
+                        .zip(columns.iter_mut())
+                        .map(|(field, column)| {
+                            (field.name().clone(), column.next().unwrap_or(Value::Null))
+                        })
+                        .collect();
+                    if strukt.is_null(row) {
+                        Value::Null
+                    } else {
+                        Value::Object(object)
+                    }
+                })
+                .collect()
+        }
+        DataType::Map(_, _) => {
+            let map = array.as_map();
+            nested_values(array, |row| {
+                let entries = map.value(row);
+                let keys = column_to_json(entries.column(0).as_ref())?;
+                let values = column_to_json(entries.column(1).as_ref())?;
+                let object: Map<String, Value> = keys
+                    .into_iter()
+                    .map(|key| match key {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    })
+                    .zip(values)
+                    .collect();
+                Ok(Value::Object(object))
+            })?
+        }
+        DataType::Dictionary(_, value_type) => {
+            column_to_json(cast(array, value_type.as_ref())?.as_ref())?
+        }
+        // Decimals keep their exact digits as strings; dates, times and
+        // timestamps use Arrow's ISO 8601 display format.
+        _ => formatted_values(array)?,
+    };
+
+    Ok(values)
+}
+
+fn map_values<T>(values: impl Iterator<Item = Option<T>>, to_json: impl Fn(T) -> Value) -> Vec<Value> {
+    values.map(|v| v.map_or(Value::Null, &to_json)).collect()
+}
+
+fn nested_values(
+    array: &dyn Array,
+    to_json: impl Fn(usize) -> AppResult<Value>,
+) -> AppResult<Vec<Value>> {
+    (0..array.len())
+        .map(|row| {
+            if array.is_null(row) {
+                Ok(Value::Null)
+            } else {
+                to_json(row)
+            }
+        })
+        .collect()
+}
+
+fn formatted_values(array: &dyn Array) -> AppResult<Vec<Value>> {
+    let options = FormatOptions::default();
+    let formatter = ArrayFormatter::try_new(array, &options)?;
+
+    Ok((0..array.len())
+        .map(|row| {
+            if array.is_null(row) {
+                Value::Null
+            } else {
+                Value::String(formatter.value(row).to_string())
+            }
+        })
+        .collect())
+}
+
+/// 64-bit integers outside the JavaScript safe range are sent as strings so
+/// `JSON.parse` doesn't silently round them.
+fn int_to_json(value: i64) -> Value {
+    if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&value) {
+        Value::from(value)
+    } else {
+        Value::String(value.to_string())
+    }
+}
+
+fn uint_to_json(value: u64) -> Value {
+    if value <= MAX_SAFE_INTEGER as u64 {
+        Value::from(value)
+    } else {
+        Value::String(value.to_string())
+    }
+}
+
+/// JSON has no NaN or infinity; those are sent as the strings `"NaN"`,
+/// `"Infinity"` and `"-Infinity"`.
+fn float_to_json(value: f64) -> Value {
+    match Number::from_f64(value) {
+        Some(number) => Value::Number(number),
+        None if value.is_nan() => Value::from("NaN"),
+        None if value > 0.0 => Value::from("Infinity"),
+        None => Value::from("-Infinity"),
+    }
+}
+
+fn binary_to_json(value: &[u8]) -> Value {
+    Value::String(BASE64_STANDARD.encode(value))
+}
@@ -0,0 +1,181 @@
+use super::encoding::{batches_to_ipc_base64, batches_to_json_rows, EncodedRows, ResultEncoding};
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use datafusion::arrow::array::{
+    ArrayRef, BinaryArray, BooleanArray, Float64Array, Int32Array, Int64Array, ListArray,
+    StringArray, StructArray, TimestampMillisecondArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Int32Type, Schema, TimeUnit};
+use datafusion::arrow::ipc::reader::StreamReader;
+use datafusion::arrow::record_batch::RecordBatch;
+use serde_json::{json, Value};
+use std::io::Cursor;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn batch(columns: Vec<(&str, ArrayRef)>) -> RecordBatch {
+    RecordBatch::try_from_iter(columns).unwrap()
+}
+
+fn json_column(batch: &RecordBatch, index: usize) -> AppResult<Vec<Value>> {
+    Ok(batches_to_json_rows(std::slice::from_ref(batch))?
+        .into_iter()
+        .map(|mut row| row.swap_remove(index))
+        .collect())
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_parse_encoding() -> AppResult<()> {
+    assert_eq!(ResultEncoding::parse(None)?, ResultEncoding::Text);
+    assert_eq!(ResultEncoding::parse(Some("json"))?, ResultEncoding::Json);
+    assert_eq!(ResultEncoding::parse(Some("arrow_ipc"))?, ResultEncoding::ArrowIpc);
+
+    let err = ResultEncoding::parse(Some("xml")).unwrap_err();
+    assert!(matches!(err, AppError::BadRequest { .. }));
+    Ok(())
+}
+
+#[test]
+fn test_json_keeps_numbers_booleans_and_nulls() -> AppResult<()> {
+    let batch = batch(vec![
+        ("id", Arc::new(Int32Array::from(vec![Some(1), None])) as ArrayRef),
+        ("active", Arc::new(BooleanArray::from(vec![Some(true), Some(false)])) as ArrayRef),
+        ("score", Arc::new(Float64Array::from(vec![Some(1.5), None])) as ArrayRef),
+    ]);
+
+    let rows = batches_to_json_rows(&[batch])?;
+    assert_eq!(rows[0], vec![json!(1), json!(true), json!(1.5)]);
+    assert_eq!(rows[1], vec![Value::Null, json!(false), Value::Null]);
+    Ok(())
+}
+
+#[test]
+fn test_json_distinguishes_null_from_null_text() -> AppResult<()> {
+    let batch = batch(vec![(
+        "name",
+        Arc::new(StringArray::from(vec![None, Some("NULL")])) as ArrayRef,
+    )]);
+
+    assert_eq!(json_column(&batch, 0)?, vec![Value::Null, json!("NULL")]);
+    Ok(())
+}
+
+#[test]
+fn test_json_large_integers_are_strings() -> AppResult<()> {
+    let batch = batch(vec![(
+        "id",
+        Arc::new(Int64Array::from(vec![42, i64::MAX])) as ArrayRef,
+    )]);
+
+    assert_eq!(json_column(&batch, 0)?, vec![json!(42), json!("9223372036854775807")]);
+    Ok(())
+}
+
+#[test]
+fn test_json_non_finite_floats_are_strings() -> AppResult<()> {
+    let batch = batch(vec![(
+        "value",
+        Arc::new(Float64Array::from(vec![f64::NAN, f64::NEG_INFINITY])) as ArrayRef,
+    )]);
+
+    assert_eq!(json_column(&batch, 0)?, vec![json!("NaN"), json!("-Infinity")]);
+    Ok(())
+}
+
+#[test]
+fn test_json_binary_is_base64() -> AppResult<()> {
+    let batch = batch(vec![(
+        "payload",
+        Arc::new(BinaryArray::from(vec![&b"hello"[..]])) as ArrayRef,
+    )]);
+
+    assert_eq!(json_column(&batch, 0)?, vec![json!("aGVsbG8=")]);
+    Ok(())
+}
+
+#[test]
+fn test_json_timestamp_is_iso_8601() -> AppResult<()> {
+    let batch = batch(vec![(
+        "created_at",
+        Arc::new(TimestampMillisecondArray::from(vec![1_700_000_000_000])) as ArrayRef,
+    )]);
+
+    assert_eq!(json_column(&batch, 0)?, vec![json!("2023-11-14T22:13:20")]);
+    Ok(())
+}
+
+#[test]
+fn test_json_nested_list_and_struct() -> AppResult<()> {
+    let tags = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+        Some(vec![Some(1), None]),
+        None,
+    ]);
+    let user = StructArray::from(vec![
+        (
+            Arc::new(Field::new("name", DataType::Utf8, true)),
+            Arc::new(StringArray::from(vec!["alice", "bob"])) as ArrayRef,
+        ),
+        (
+            Arc::new(Field::new("age", DataType::Int32, true)),
+            Arc::new(Int32Array::from(vec![30, 25])) as ArrayRef,
+        ),
+    ]);
+    let batch = batch(vec![
+        ("tags", Arc::new(tags) as ArrayRef),
+        ("user", Arc::new(user) as ArrayRef),
+    ]);
+
+    let rows = batches_to_json_rows(&[batch])?;
+    assert_eq!(rows[0], vec![json!([1, null]), json!({"name": "alice", "age": 30})]);
+    assert_eq!(rows[1], vec![Value::Null, json!({"name": "bob", "age": 25})]);
+    Ok(())
+}
+
+#[test]
+fn test_arrow_ipc_round_trip() -> AppResult<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("at", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2])),
+            Arc::new(TimestampMillisecondArray::from(vec![Some(0), None])),
+        ],
+    )?;
+
+    let encoded = batches_to_ipc_base64(&schema, std::slice::from_ref(&batch))?;
+    let bytes = BASE64_STANDARD.decode(encoded).unwrap();
+    let decoded = StreamReader::try_new(Cursor::new(bytes), None)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(decoded, vec![batch]);
+    Ok(())
+}
+
+#[test]
+fn test_encoded_rows_populates_only_requested_encoding() -> AppResult<()> {
+    let batch = batch(vec![(
+        "id",
+        Arc::new(Int32Array::from(vec![Some(7), None])) as ArrayRef,
+    )]);
+    let schema = batch.schema();
+
+    let text = EncodedRows::encode(ResultEncoding::Text, &schema, std::slice::from_ref(&batch))?;
+    assert_eq!(text.rows, vec![vec!["7".to_string()], vec!["NULL".to_string()]]);
+    assert!(text.typed_rows.is_none() && text.arrow_ipc.is_none());
+
+    let json = EncodedRows::encode(ResultEncoding::Json, &schema, std::slice::from_ref(&batch))?;
+    assert!(json.rows.is_empty() && json.arrow_ipc.is_none());
+    assert_eq!(json.typed_rows, Some(vec![vec![json!(7)], vec![Value::Null]]));
+    Ok(())
+}
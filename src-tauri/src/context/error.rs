@@ -2,15 +2,17 @@ use crate::context::error::AppError::{BadRequest, InternalServer};
 use calamine::XlsxError;
 use datafusion::arrow::error::ArrowError;
 use datafusion::error::DataFusionError;
+use datafusion::parquet::errors::ParquetError;
 use datafusion_table_providers::mysql;
 use datafusion_table_providers::postgres;
 use datafusion_table_providers::sql::db_connection_pool::mysqlpool;
 use datafusion_table_providers::sql::db_connection_pool::postgrespool;
 use derive_more::with_trait::{Display, Error};
 use glob::{GlobError, PatternError};
-use sqlparser::parser::ParserError;
+use rust_xlsxwriter::XlsxError as XlsxWriteError;
 use tauri::ipc::InvokeError;
 use tokio::task::JoinError;
+use zip::result::ZipError;
 
 #[derive(Debug, Display, Error, Clone)]
 pub enum AppError {
@@ -31,15 +33,6 @@ impl AppError {
     fn log_backtrace() {}
 }
 
-impl From<ParserError> for AppError {
-    fn from(error: ParserError) -> Self {
-        AppError::log_backtrace();
-        BadRequest {
-            message: error.to_string(),
-        }
-    }
-}
-
 impl From<AppError> for InvokeError {
     fn from(err: AppError) -> Self {
         AppError::log_backtrace();
@@ -83,6 +76,15 @@ impl From<XlsxError> for AppError {
     }
 }
 
+impl From<XlsxWriteError> for AppError {
+    fn from(error: XlsxWriteError) -> Self {
+        AppError::log_backtrace();
+        InternalServer {
+            message: error.to_string(),
+        }
+    }
+}
+
 impl From<JoinError> for AppError {
     fn from(error: JoinError) -> Self {
         AppError::log_backtrace();
@@ -113,12 +115,25 @@ impl From<rusqlite::Error> for AppError {
 impl From<DataFusionError> for AppError {
     fn from(error: DataFusionError) -> Self {
         AppError::log_backtrace();
+        // Errors raised by our own table functions travel through DataFusion
+        // wrapped as `External`; unwrap them so e.g. `FileNotFound` survives.
+        if let DataFusionError::External(inner) = error.find_root() {
+            if let Some(app_error) = inner.downcast_ref::<AppError>() {
+                return app_error.clone();
+            }
+        }
         BadRequest {
             message: error.to_string(),
         }
     }
 }
 
+impl From<AppError> for DataFusionError {
+    fn from(error: AppError) -> Self {
+        DataFusionError::External(Box::new(error))
+    }
+}
+
 impl From<ArrowError> for AppError {
     fn from(error: ArrowError) -> Self {
         AppError::log_backtrace();
@@ -128,6 +143,24 @@ impl From<ArrowError> for AppError {
     }
 }
 
+impl From<ParquetError> for AppError {
+    fn from(error: ParquetError) -> Self {
+        AppError::log_backtrace();
+        InternalServer {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<ZipError> for AppError {
+    fn from(error: ZipError) -> Self {
+        AppError::log_backtrace();
+        InternalServer {
+            message: error.to_string(),
+        }
+    }
+}
+
 impl From<mysqlpool::Error> for AppError {
     fn from(error: mysqlpool::Error) -> Self {
         AppError::log_backtrace();
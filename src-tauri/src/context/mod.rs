@@ -1,6 +1,17 @@
+#[allow(clippy::module_inception)]
 pub mod context;
+pub mod encoding;
 pub mod error;
+pub mod query_registry;
+pub mod result_cache;
 pub mod schema;
+pub mod table_function;
 
 #[cfg(test)]
-mod context_test;
\ No newline at end of file
+mod context_test;
+#[cfg(test)]
+mod encoding_test;
+#[cfg(test)]
+mod query_registry_test;
+#[cfg(test)]
+mod result_cache_test;
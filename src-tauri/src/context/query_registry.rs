@@ -0,0 +1,185 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::utils::date_utils::time_difference_from_now;
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use tokio::task::AbortHandle;
+
+struct RunningQuery {
+    sql: String,
+    started_at: DateTime<Utc>,
+    abort_handle: AbortHandle,
+    cancel_flag: CancelFlag,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RunningQueryInfo {
+    pub query_id: String,
+    pub sql: String,
+    pub started_at: String,
+    pub elapsed: String,
+    /// Cancelled but still finishing synchronous work.
+    pub cancelled: bool,
+}
+
+/// Raised by `cancel_query` for work the abort handle cannot interrupt:
+/// synchronous loops such as reading Excel sheets, zip members or transcoding
+/// a CSV file only stop where they call [`check_cancelled`].
+#[derive(Clone, Debug, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+tokio::task_local! {
+    static CANCEL_FLAG: CancelFlag;
+}
+
+/// Run `future` with `flag` as the cancel flag seen by [`check_cancelled`].
+pub fn with_cancel_flag<F: Future>(flag: CancelFlag, future: F) -> impl Future<Output = F::Output> {
+    CANCEL_FLAG.scope(flag, future)
+}
+
+/// Fail when the query running on this task has been cancelled. Outside a
+/// cancellable query this always succeeds.
+pub fn check_cancelled() -> AppResult<()> {
+    if CANCEL_FLAG
+        .try_with(CancelFlag::is_cancelled)
+        .unwrap_or(false)
+    {
+        return Err(AppError::BadRequest {
+            message: "Query was cancelled".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// A reader that fails once the current query is cancelled, so copying a
+/// large file or archive member stops between chunks.
+pub struct CancellableRead<R> {
+    inner: R,
+}
+
+impl<R: Read> CancellableRead<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+impl<R: Read> Read for CancellableRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        check_cancelled().map_err(|_| io::Error::other("Query was cancelled"))?;
+        self.inner.read(buf)
+    }
+}
+
+/// In-flight queries keyed by query ID. Each entry holds the abort handle of
+/// the task executing the query, so cancelling drops the DataFusion stream
+/// (stopping execution and any MySQL/Postgres fetch) at its next await point.
+/// Entries stay listed until the task has actually finished.
+static RUNNING_QUERIES: LazyLock<Mutex<HashMap<String, RunningQuery>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_QUERY_ID: AtomicU64 = AtomicU64::new(1);
+
+fn running_queries() -> MutexGuard<'static, HashMap<String, RunningQuery>> {
+    // A panic while holding the lock cannot leave the map half-updated, so a
+    // poisoned lock is still safe to use.
+    RUNNING_QUERIES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Generate an ID for callers that don't supply their own.
+pub fn next_query_id() -> String {
+    format!("query-{}", NEXT_QUERY_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Removes its query from the registry when dropped.
+pub struct RegisteredQuery {
+    query_id: String,
+}
+
+impl Drop for RegisteredQuery {
+    fn drop(&mut self) {
+        running_queries().remove(&self.query_id);
+    }
+}
+
+/// Track a running query until the returned guard is dropped. `cancel_flag`
+/// is raised when the query is cancelled.
+pub fn register_query(
+    query_id: &str,
+    sql: &str,
+    abort_handle: AbortHandle,
+    cancel_flag: CancelFlag,
+) -> AppResult<RegisteredQuery> {
+    let mut queries = running_queries();
+    if queries.contains_key(query_id) {
+        return Err(AppError::BadRequest {
+            message: format!("Query '{}' is already running", query_id),
+        });
+    }
+
+    queries.insert(
+        query_id.to_string(),
+        RunningQuery {
+            sql: sql.to_string(),
+            started_at: Utc::now(),
+            abort_handle,
+            cancel_flag,
+        },
+    );
+
+    Ok(RegisteredQuery {
+        query_id: query_id.to_string(),
+    })
+}
+
+/// Abort the query registered under `query_id`. Returns `false` when no such
+/// query is running (e.g. it already finished). The query stays listed as
+/// cancelled until its task ends.
+pub fn cancel_query(query_id: &str) -> bool {
+    match running_queries().get(query_id) {
+        Some(query) => {
+            query.cancel_flag.cancel();
+            query.abort_handle.abort();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Snapshot of the running queries, oldest first.
+pub fn list_running_queries() -> Vec<RunningQueryInfo> {
+    let queries = running_queries();
+    let mut running: Vec<(&String, &RunningQuery)> = queries.iter().collect();
+    running.sort_by_key(|(_, query)| query.started_at);
+
+    running
+        .into_iter()
+        .map(|(query_id, query)| RunningQueryInfo {
+            query_id: query_id.clone(),
+            sql: query.sql.clone(),
+            started_at: query
+                .started_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            elapsed: time_difference_from_now(query.started_at),
+            cancelled: query.cancel_flag.is_cancelled(),
+        })
+        .collect()
+}
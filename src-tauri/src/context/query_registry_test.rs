@@ -0,0 +1,183 @@
+use super::query_registry::{
+    cancel_query, check_cancelled, list_running_queries, next_query_id, register_query, CancelFlag,
+};
+use crate::commands::run_cancellable;
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_next_query_id_is_unique() {
+    assert_ne!(next_query_id(), next_query_id());
+}
+
+#[tokio::test]
+async fn test_register_query_is_listed_until_guard_drops() -> AppResult<()> {
+    let query_id = next_query_id();
+    let handle = tokio::spawn(std::future::pending::<()>());
+
+    let guard = register_query(
+        &query_id,
+        "SELECT 1",
+        handle.abort_handle(),
+        CancelFlag::default(),
+    )?;
+    let listed = list_running_queries();
+    let info = listed
+        .iter()
+        .find(|q| q.query_id == query_id)
+        .expect("registered query should be listed");
+    assert_eq!(info.sql, "SELECT 1");
+
+    drop(guard);
+    assert!(list_running_queries().iter().all(|q| q.query_id != query_id));
+    handle.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_register_query_rejects_duplicate_id() -> AppResult<()> {
+    let query_id = next_query_id();
+    let handle = tokio::spawn(std::future::pending::<()>());
+
+    let _guard = register_query(
+        &query_id,
+        "SELECT 1",
+        handle.abort_handle(),
+        CancelFlag::default(),
+    )?;
+    let err = register_query(
+        &query_id,
+        "SELECT 2",
+        handle.abort_handle(),
+        CancelFlag::default(),
+    )
+    .err()
+        .expect("duplicate query id should be rejected");
+    assert!(matches!(err, AppError::BadRequest { .. }));
+
+    handle.abort();
+    Ok(())
+}
+
+#[test]
+fn test_cancel_unknown_query_returns_false() {
+    assert!(!cancel_query("query-does-not-exist"));
+}
+
+#[tokio::test]
+async fn test_run_cancellable_aborts_running_query() {
+    let query_id = next_query_id();
+    let task = tokio::spawn(run_cancellable(
+        Some(query_id.clone()),
+        "SELECT * FROM big".to_string(),
+        || async {
+            std::future::pending::<()>().await;
+            Ok(())
+        },
+    ));
+
+    // Wait until the query shows up in the registry before cancelling it.
+    while list_running_queries().iter().all(|q| q.query_id != query_id) {
+        tokio::task::yield_now().await;
+    }
+    assert!(cancel_query(&query_id));
+
+    let err = task.await.unwrap().unwrap_err();
+    assert!(matches!(err, AppError::BadRequest { .. }));
+    assert!(list_running_queries().iter().all(|q| q.query_id != query_id));
+}
+
+#[tokio::test]
+async fn test_run_cancellable_returns_result_and_unregisters() -> AppResult<()> {
+    let query_id = next_query_id();
+    let value = run_cancellable(Some(query_id.clone()), "SELECT 42".to_string(), || async {
+        Ok(42)
+    })
+    .await?;
+
+    assert_eq!(value, 42);
+    assert!(list_running_queries().iter().all(|q| q.query_id != query_id));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_cancellable_rejects_duplicate_id_without_running() {
+    let query_id = next_query_id();
+    let first = tokio::spawn(run_cancellable(
+        Some(query_id.clone()),
+        "SELECT * FROM big".to_string(),
+        || async {
+            std::future::pending::<()>().await;
+            Ok(())
+        },
+    ));
+    while list_running_queries().iter().all(|q| q.query_id != query_id) {
+        tokio::task::yield_now().await;
+    }
+
+    let ran = Arc::new(AtomicBool::new(false));
+    let ran_in_query = ran.clone();
+    let err = run_cancellable(Some(query_id.clone()), "SELECT 1".to_string(), move || async move {
+        ran_in_query.store(true, Ordering::SeqCst);
+        Ok(())
+    })
+    .await
+    .unwrap_err();
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+
+    assert!(format!("{:?}", err).contains("already running"));
+    assert!(!ran.load(Ordering::SeqCst));
+    assert!(cancel_query(&query_id));
+    let _ = first.await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cancelled_query_stays_listed_until_task_ends() {
+    let query_id = next_query_id();
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    let (cancelled_tx, cancelled_rx) = std::sync::mpsc::channel::<()>();
+    let task = tokio::spawn(run_cancellable(
+        Some(query_id.clone()),
+        "SELECT * FROM read_excel('big.xlsx')".to_string(),
+        move || async move {
+            // Synchronous work the abort handle cannot interrupt.
+            tokio::task::block_in_place(|| {
+                started_tx.send(()).unwrap();
+                cancelled_rx.recv().unwrap();
+                check_cancelled()
+            })
+        },
+    ));
+
+    tokio::task::spawn_blocking(move || started_rx.recv().unwrap())
+        .await
+        .unwrap();
+    while list_running_queries().iter().all(|q| q.query_id != query_id) {
+        tokio::task::yield_now().await;
+    }
+    assert!(cancel_query(&query_id));
+    let listed = list_running_queries();
+    let info = listed
+        .iter()
+        .find(|q| q.query_id == query_id)
+        .expect("cancelled query should stay listed while it runs");
+    assert!(info.cancelled);
+
+    cancelled_tx.send(()).unwrap();
+    let err = task.await.unwrap().unwrap_err();
+    assert!(format!("{:?}", err).contains("was cancelled"));
+    assert!(list_running_queries().iter().all(|q| q.query_id != query_id));
+}
+
+#[test]
+fn test_check_cancelled_outside_query_succeeds() {
+    assert!(check_cancelled().is_ok());
+}
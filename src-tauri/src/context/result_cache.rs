@@ -0,0 +1,549 @@
+use crate::commands::query::ColumnTypeInfo;
+use crate::context::context::ScanMetrics;
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::ipc::reader::FileReader;
+use datafusion::arrow::ipc::writer::FileWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Size and age budget of the result cache.
+#[derive(Clone, Copy, Debug)]
+pub struct ResultCacheLimits {
+    /// Results kept in memory; beyond this, the least recently used results
+    /// are spilled to Arrow IPC files.
+    pub max_memory_bytes: usize,
+    /// Memory plus spilled results; beyond this, the least recently used
+    /// results are dropped entirely.
+    pub max_total_bytes: usize,
+    /// Results not read for this long are dropped.
+    pub max_idle: Duration,
+}
+
+impl Default for ResultCacheLimits {
+    fn default() -> Self {
+        Self {
+            max_memory_bytes: 512 * 1024 * 1024,
+            max_total_bytes: 4 * 1024 * 1024 * 1024,
+            max_idle: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+enum Storage {
+    Memory(Arc<Vec<RecordBatch>>),
+    /// Still in memory while a [`PendingSpill`] writes it out.
+    Spilling(Arc<Vec<RecordBatch>>),
+    Spilled(PathBuf),
+}
+
+struct CachedResult {
+    columns: Vec<ColumnTypeInfo>,
+    metrics: ScanMetrics,
+    schema: SchemaRef,
+    storage: Storage,
+    /// Row count of every batch, in order, so a page can seek straight to the
+    /// first batch it needs (also for spilled results).
+    batch_rows: Vec<usize>,
+    size_bytes: usize,
+    last_access: Instant,
+}
+
+impl CachedResult {
+    fn total_rows(&self) -> usize {
+        self.batch_rows.iter().sum()
+    }
+
+    /// Memory this result still counts against the budget: a result being
+    /// spilled is already on its way out.
+    fn in_memory_bytes(&self) -> usize {
+        match self.storage {
+            Storage::Memory(_) => self.size_bytes,
+            Storage::Spilling(_) | Storage::Spilled(_) => 0,
+        }
+    }
+}
+
+/// A cached result to write to an Arrow IPC file. The write happens without
+/// holding the cache, see [`cache_result`].
+pub struct PendingSpill {
+    result_id: String,
+    schema: SchemaRef,
+    batches: Arc<Vec<RecordBatch>>,
+    path: PathBuf,
+}
+
+impl PendingSpill {
+    /// Write the result to its spill file.
+    pub fn write(&self) -> AppResult<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut writer = FileWriter::try_new(File::create(&self.path)?, &self.schema)?;
+        for batch in self.batches.iter() {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// A result being streamed into the cache, see [`ResultCache::start`].
+/// Batches are kept in memory until they cross the memory budget; from then
+/// on the result is written to its spill file as it arrives.
+pub struct ResultBuilder {
+    result_id: String,
+    schema: SchemaRef,
+    path: PathBuf,
+    max_memory_bytes: usize,
+    batches: Vec<RecordBatch>,
+    batch_rows: Vec<usize>,
+    size_bytes: usize,
+    spill: Option<FileWriter<BufWriter<File>>>,
+}
+
+impl ResultBuilder {
+    pub fn push(&mut self, batch: RecordBatch) -> AppResult<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        self.batch_rows.push(batch.num_rows());
+        self.size_bytes += batch.get_array_memory_size();
+        if let Some(spill) = &mut self.spill {
+            spill.write(&batch)?;
+            return Ok(());
+        }
+        self.batches.push(batch);
+        if self.size_bytes > self.max_memory_bytes {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let file = BufWriter::new(File::create(&self.path)?);
+            let spill = self.spill.insert(FileWriter::try_new(file, &self.schema)?);
+            for batch in self.batches.drain(..) {
+                spill.write(&batch)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Complete the result, flushing its spill file if it has one.
+    pub fn finish(mut self) -> AppResult<BuiltResult> {
+        let storage = match self.spill.take() {
+            Some(mut spill) => match spill.finish() {
+                Ok(()) => Storage::Spilled(self.path.clone()),
+                Err(err) => {
+                    let _ = std::fs::remove_file(&self.path);
+                    return Err(err.into());
+                }
+            },
+            None => Storage::Memory(Arc::new(std::mem::take(&mut self.batches))),
+        };
+        Ok(BuiltResult {
+            result_id: std::mem::take(&mut self.result_id),
+            schema: self.schema.clone(),
+            storage,
+            batch_rows: std::mem::take(&mut self.batch_rows),
+            size_bytes: self.size_bytes,
+        })
+    }
+}
+
+impl Drop for ResultBuilder {
+    /// A result abandoned mid-stream (failed or cancelled query) leaves no
+    /// spill file behind.
+    fn drop(&mut self) {
+        if self.spill.is_some() {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// A complete result, ready for [`ResultCache::insert`].
+pub struct BuiltResult {
+    result_id: String,
+    schema: SchemaRef,
+    storage: Storage,
+    batch_rows: Vec<usize>,
+    size_bytes: usize,
+}
+
+/// Where a page of a cached result is read from. Taken from the cache so a
+/// spill file can be read without holding the cache lock.
+pub struct PageSource {
+    schema: SchemaRef,
+    columns: Vec<ColumnTypeInfo>,
+    metrics: ScanMetrics,
+    total_rows: usize,
+    data: PageData,
+    first_batch: usize,
+    skip: usize,
+    limit: usize,
+}
+
+enum PageData {
+    Memory(Arc<Vec<RecordBatch>>),
+    Spilled(PathBuf),
+}
+
+impl PageSource {
+    /// Read the page. `None` when the spill file was removed in the meantime
+    /// because the result was released or evicted.
+    pub fn read(self) -> AppResult<Option<CachedPage>> {
+        let mut skip = self.skip;
+        let mut remaining = self.limit;
+        let mut batches = Vec::new();
+        match &self.data {
+            PageData::Memory(cached) => {
+                for batch in &cached[self.first_batch.min(cached.len())..] {
+                    if remaining == 0 {
+                        break;
+                    }
+                    take_rows(batch, &mut skip, &mut remaining, &mut batches);
+                }
+            }
+            PageData::Spilled(path) => {
+                let file = match File::open(path) {
+                    Ok(file) => file,
+                    Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+                    Err(err) => return Err(err.into()),
+                };
+                let mut reader = FileReader::try_new(file, None)?;
+                if self.first_batch < reader.num_batches() {
+                    reader.set_index(self.first_batch)?;
+                    for batch in reader {
+                        if remaining == 0 {
+                            break;
+                        }
+                        take_rows(&batch?, &mut skip, &mut remaining, &mut batches);
+                    }
+                }
+            }
+        }
+
+        Ok(Some(CachedPage {
+            schema: self.schema,
+            columns: self.columns,
+            batches,
+            total_rows: self.total_rows,
+            metrics: self.metrics,
+        }))
+    }
+}
+
+/// One page served from the cache.
+pub struct CachedPage {
+    pub schema: SchemaRef,
+    pub columns: Vec<ColumnTypeInfo>,
+    pub batches: Vec<RecordBatch>,
+    pub total_rows: usize,
+    /// Metrics of the execution that produced the cached result.
+    pub metrics: ScanMetrics,
+}
+
+/// Materialized query results keyed by result ID, so paging through a result
+/// doesn't re-run the query (re-parsing Excel, re-querying MySQL, ...).
+pub struct ResultCache {
+    limits: ResultCacheLimits,
+    entries: HashMap<String, CachedResult>,
+}
+
+static NEXT_RESULT_ID: AtomicU64 = AtomicU64::new(1);
+
+impl ResultCache {
+    pub fn new(limits: ResultCacheLimits) -> Self {
+        Self {
+            limits,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Start streaming a result into the cache. The builder works without
+    /// holding the cache; the result is added by [`ResultCache::insert`].
+    pub fn start(&self, spill_dir: &Path, schema: SchemaRef) -> ResultBuilder {
+        let result_id = format!("result-{}", NEXT_RESULT_ID.fetch_add(1, Ordering::Relaxed));
+        ResultBuilder {
+            path: spill_dir.join(format!("{}.arrow", result_id)),
+            result_id,
+            schema,
+            max_memory_bytes: self.limits.max_memory_bytes,
+            batches: Vec::new(),
+            batch_rows: Vec::new(),
+            size_bytes: 0,
+            spill: None,
+        }
+    }
+
+    /// Cache a complete result and return its ID. Older results are spilled
+    /// or evicted to stay within the limits; the new result itself is always
+    /// kept. The spills are returned to be written with
+    /// [`PendingSpill::write`] and reported back through
+    /// [`ResultCache::finish_spill`]; until then the results stay readable
+    /// from memory.
+    pub fn insert(
+        &mut self,
+        spill_dir: &Path,
+        result: BuiltResult,
+        columns: Vec<ColumnTypeInfo>,
+        metrics: ScanMetrics,
+    ) -> (String, Vec<PendingSpill>) {
+        self.evict_idle();
+
+        let result_id = result.result_id;
+        self.entries.insert(
+            result_id.clone(),
+            CachedResult {
+                columns,
+                metrics,
+                schema: result.schema,
+                storage: result.storage,
+                batch_rows: result.batch_rows,
+                size_bytes: result.size_bytes,
+                last_access: Instant::now(),
+            },
+        );
+        let spills = self.enforce_limits(spill_dir, &result_id);
+
+        (result_id, spills)
+    }
+
+    /// Record the outcome of a spill. On success the result is read from the
+    /// file from now on; if the result was removed in the meantime the file is
+    /// deleted. A result that failed to spill is evicted, as keeping it in
+    /// memory would break the memory budget.
+    pub fn finish_spill(&mut self, spill: PendingSpill, written: AppResult<()>) {
+        // The result may have been released or evicted meanwhile.
+        let entry = self
+            .entries
+            .get_mut(&spill.result_id)
+            .filter(|entry| matches!(entry.storage, Storage::Spilling(_)));
+        match (entry, written) {
+            (Some(entry), Ok(())) => entry.storage = Storage::Spilled(spill.path),
+            (Some(_), Err(err)) => {
+                log::warn!("Evicting cached result {}: spill failed: {:?}", spill.result_id, err);
+                self.entries.remove(&spill.result_id);
+                let _ = std::fs::remove_file(&spill.path);
+            }
+            (None, _) => {
+                let _ = std::fs::remove_file(&spill.path);
+            }
+        }
+    }
+
+    /// Locate rows `offset..offset + limit` of a cached result, or `None`
+    /// when the result is unknown or has been evicted.
+    pub fn page_source(
+        &mut self,
+        result_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Option<PageSource> {
+        self.evict_idle();
+
+        let entry = self.entries.get_mut(result_id)?;
+        entry.last_access = Instant::now();
+
+        // Locate the first batch that contains row `offset`.
+        let mut first_batch = 0;
+        let mut skip = offset;
+        while first_batch < entry.batch_rows.len() && skip >= entry.batch_rows[first_batch] {
+            skip -= entry.batch_rows[first_batch];
+            first_batch += 1;
+        }
+
+        let data = match &entry.storage {
+            Storage::Memory(cached) | Storage::Spilling(cached) => PageData::Memory(cached.clone()),
+            Storage::Spilled(path) => PageData::Spilled(path.clone()),
+        };
+        Some(PageSource {
+            schema: entry.schema.clone(),
+            columns: entry.columns.clone(),
+            metrics: entry.metrics,
+            total_rows: entry.total_rows(),
+            data,
+            first_batch,
+            skip,
+            limit,
+        })
+    }
+
+    /// Rows `offset..offset + limit` of a cached result, or `None` when the
+    /// result is unknown or has been evicted.
+    pub fn page(
+        &mut self,
+        result_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> AppResult<Option<CachedPage>> {
+        match self.page_source(result_id, offset, limit) {
+            Some(source) => source.read(),
+            None => Ok(None),
+        }
+    }
+
+    /// Total row count of a cached result.
+    pub fn total_rows(&self, result_id: &str) -> Option<usize> {
+        self.entries.get(result_id).map(CachedResult::total_rows)
+    }
+
+    /// Drop a cached result (and its spill file). Returns `false` if unknown.
+    pub fn remove(&mut self, result_id: &str) -> bool {
+        match self.entries.remove(result_id) {
+            Some(entry) => {
+                discard(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn evict_idle(&mut self) {
+        let max_idle = self.limits.max_idle;
+        let idle: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.last_access.elapsed() > max_idle)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for result_id in idle {
+            self.remove(&result_id);
+        }
+    }
+
+    /// Spill, then evict, least recently used results other than `keep`.
+    fn enforce_limits(&mut self, spill_dir: &Path, keep: &str) -> Vec<PendingSpill> {
+        let mut by_age: Vec<(Instant, String)> = self
+            .entries
+            .iter()
+            .filter(|(id, _)| id.as_str() != keep)
+            .map(|(id, entry)| (entry.last_access, id.clone()))
+            .collect();
+        by_age.sort();
+
+        let mut spills = Vec::new();
+        for (_, result_id) in &by_age {
+            if self.memory_bytes() <= self.limits.max_memory_bytes {
+                break;
+            }
+            if let Some(entry) = self.entries.get_mut(result_id) {
+                spills.extend(start_spill(entry, spill_dir, result_id));
+            }
+        }
+
+        for (_, result_id) in &by_age {
+            if self.total_bytes() <= self.limits.max_total_bytes {
+                break;
+            }
+            self.remove(result_id);
+        }
+
+        spills
+    }
+
+    fn memory_bytes(&self) -> usize {
+        self.entries.values().map(CachedResult::in_memory_bytes).sum()
+    }
+
+    fn total_bytes(&self) -> usize {
+        self.entries.values().map(|entry| entry.size_bytes).sum()
+    }
+}
+
+impl Drop for ResultCache {
+    fn drop(&mut self) {
+        for (_, entry) in self.entries.drain() {
+            discard(entry);
+        }
+    }
+}
+
+/// Append up to `remaining` rows of `batch`, starting at row `skip`, to `out`.
+fn take_rows(batch: &RecordBatch, skip: &mut usize, remaining: &mut usize, out: &mut Vec<RecordBatch>) {
+    let rows = batch.num_rows().saturating_sub(*skip).min(*remaining);
+    if rows > 0 {
+        out.push(batch.slice(*skip, rows));
+        *remaining -= rows;
+    }
+    *skip = 0;
+}
+
+/// Mark an in-memory result as being spilled to an Arrow IPC file in
+/// `spill_dir`, returning the write to perform.
+fn start_spill(
+    entry: &mut CachedResult,
+    spill_dir: &Path,
+    result_id: &str,
+) -> Option<PendingSpill> {
+    let Storage::Memory(batches) = &entry.storage else {
+        return None;
+    };
+    let batches = batches.clone();
+    entry.storage = Storage::Spilling(batches.clone());
+    Some(PendingSpill {
+        result_id: result_id.to_string(),
+        schema: entry.schema.clone(),
+        batches,
+        path: spill_dir.join(format!("{}.arrow", result_id)),
+    })
+}
+
+fn discard(entry: CachedResult) {
+    if let Storage::Spilled(path) = entry.storage {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+static RESULT_CACHE: LazyLock<Mutex<ResultCache>> =
+    LazyLock::new(|| Mutex::new(ResultCache::new(ResultCacheLimits::default())));
+
+/// The session-wide result cache shared by the query commands.
+pub fn result_cache() -> MutexGuard<'static, ResultCache> {
+    RESULT_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Add a streamed result to the session-wide cache and return its ID. Spill
+/// files are written without holding the cache lock, so other pages are
+/// served in the meantime. Every spill is finished even if one fails.
+pub fn cache_result(
+    spill_dir: &Path,
+    result: ResultBuilder,
+    columns: Vec<ColumnTypeInfo>,
+    metrics: ScanMetrics,
+) -> AppResult<String> {
+    let result = result.finish()?;
+    let (result_id, spills) = result_cache().insert(spill_dir, result, columns, metrics);
+    for spill in spills {
+        let written = spill.write();
+        result_cache().finish_spill(spill, written);
+    }
+    Ok(result_id)
+}
+
+/// A page of a result in the session-wide cache. A spilled result is read
+/// after releasing the cache lock.
+pub fn cached_page(result_id: &str, offset: usize, limit: usize) -> AppResult<Option<CachedPage>> {
+    let source = result_cache().page_source(result_id, offset, limit);
+    match source {
+        Some(source) => source.read(),
+        None => Ok(None),
+    }
+}
+
+/// Remove spill files left behind by a previous session.
+pub fn clear_spill_dir(spill_dir: &Path) -> AppResult<()> {
+    if spill_dir.exists() {
+        std::fs::remove_dir_all(spill_dir).map_err(|e| AppError::InternalServer {
+            message: format!("Failed to clear result cache: {}", e),
+        })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,303 @@
+use super::result_cache::{BuiltResult, ResultCache, ResultCacheLimits};
+use crate::commands::query::ColumnTypeInfo;
+use crate::context::context::ScanMetrics;
+use crate::context::schema::AppResult;
+use datafusion::arrow::array::{Array, Int32Array};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn spill_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("easydb_result_cache_{}_{}", std::process::id(), name))
+}
+
+fn schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]))
+}
+
+fn columns() -> Vec<ColumnTypeInfo> {
+    vec![ColumnTypeInfo {
+        column_name: "id".to_string(),
+        arrow_type: "Int32".to_string(),
+        default_sql_type: "INT".to_string(),
+    }]
+}
+
+/// Three batches holding ids `0..10`, `10..20` and `20..25`.
+fn batches() -> Vec<RecordBatch> {
+    [(0, 10), (10, 20), (20, 25)]
+        .iter()
+        .map(|&(from, to)| {
+            RecordBatch::try_new(schema(), vec![Arc::new(Int32Array::from_iter_values(from..to))])
+                .unwrap()
+        })
+        .collect()
+}
+
+fn ids(batches: &[RecordBatch]) -> Vec<i32> {
+    batches
+        .iter()
+        .flat_map(|b| {
+            let array = b.column(0).as_any().downcast_ref::<Int32Array>().unwrap();
+            (0..array.len()).map(|i| array.value(i)).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Stream `batches()` into a result for `cache`.
+fn build(cache: &ResultCache, dir: &Path) -> AppResult<BuiltResult> {
+    let mut builder = cache.start(dir, schema());
+    for batch in batches() {
+        builder.push(batch)?;
+    }
+    builder.finish()
+}
+
+/// Cache `batches()` and write any spills it asks for.
+fn insert(cache: &mut ResultCache, dir: &Path, metrics: ScanMetrics) -> AppResult<String> {
+    let result = build(cache, dir)?;
+    let (result_id, spills) = cache.insert(dir, result, columns(), metrics);
+    for spill in spills {
+        let written = spill.write();
+        cache.finish_spill(spill, written);
+    }
+    Ok(result_id)
+}
+
+fn batches_size() -> usize {
+    batches().iter().map(|b| b.get_array_memory_size()).sum()
+}
+
+fn limits(max_memory_bytes: usize, max_total_bytes: usize) -> ResultCacheLimits {
+    ResultCacheLimits {
+        max_memory_bytes,
+        max_total_bytes,
+        max_idle: Duration::from_secs(60),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_page_from_memory_spans_batches() -> AppResult<()> {
+    let dir = spill_dir("memory");
+    let mut cache = ResultCache::new(limits(usize::MAX, usize::MAX));
+    let result_id = insert(&mut cache, &dir, ScanMetrics::default())?;
+
+    let page = cache.page(&result_id, 8, 5)?.expect("cached");
+    assert_eq!(ids(&page.batches), vec![8, 9, 10, 11, 12]);
+    assert_eq!(page.total_rows, 25);
+    assert_eq!(page.columns[0].column_name, "id");
+    assert!(!dir.exists(), "nothing should be spilled");
+    Ok(())
+}
+
+#[test]
+fn test_page_past_end_is_empty() -> AppResult<()> {
+    let dir = spill_dir("past_end");
+    let mut cache = ResultCache::new(limits(usize::MAX, usize::MAX));
+    let result_id = insert(&mut cache, &dir, ScanMetrics::default())?;
+
+    let page = cache.page(&result_id, 100, 10)?.expect("cached");
+    assert!(ids(&page.batches).is_empty());
+    assert_eq!(page.total_rows, 25);
+    Ok(())
+}
+
+#[test]
+fn test_oversized_result_is_spilled_and_paged_from_disk() -> AppResult<()> {
+    let dir = spill_dir("spilled");
+    let mut cache = ResultCache::new(limits(0, usize::MAX));
+    let result_id = insert(&mut cache, &dir, ScanMetrics::default())?;
+
+    assert!(dir.join(format!("{}.arrow", result_id)).exists());
+    let page = cache.page(&result_id, 18, 4)?.expect("cached");
+    assert_eq!(ids(&page.batches), vec![18, 19, 20, 21]);
+
+    assert!(cache.remove(&result_id));
+    assert!(!dir.join(format!("{}.arrow", result_id)).exists());
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_total_budget_evicts_least_recently_used() -> AppResult<()> {
+    let dir = spill_dir("evict");
+    let size = batches_size();
+    let mut cache = ResultCache::new(limits(usize::MAX, size + size / 2));
+
+    let first = insert(&mut cache, &dir, ScanMetrics::default())?;
+    let second = insert(&mut cache, &dir, ScanMetrics::default())?;
+
+    assert!(cache.page(&first, 0, 1)?.is_none(), "older result is evicted");
+    assert!(cache.page(&second, 0, 1)?.is_some(), "newest result is kept");
+    Ok(())
+}
+
+#[test]
+fn test_unknown_result_id_returns_none() -> AppResult<()> {
+    let mut cache = ResultCache::new(ResultCacheLimits::default());
+    assert!(cache.page("result-unknown", 0, 10)?.is_none());
+    assert!(!cache.remove("result-unknown"));
+    Ok(())
+}
+
+#[test]
+fn test_idle_results_expire() -> AppResult<()> {
+    let dir = spill_dir("idle");
+    let mut cache = ResultCache::new(ResultCacheLimits {
+        max_memory_bytes: usize::MAX,
+        max_total_bytes: usize::MAX,
+        max_idle: Duration::ZERO,
+    });
+    let result_id = insert(&mut cache, &dir, ScanMetrics::default())?;
+
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(cache.page(&result_id, 0, 1)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_page_reports_metrics_of_original_execution() -> AppResult<()> {
+    let dir = spill_dir("metrics");
+    let mut cache = ResultCache::new(limits(usize::MAX, usize::MAX));
+    let metrics = ScanMetrics {
+        rows_scanned: Some(25),
+        bytes_scanned: Some(1024),
+    };
+    let result_id = insert(&mut cache, &dir, metrics)?;
+
+    let page = cache.page(&result_id, 0, 1)?.expect("cached");
+    assert_eq!(page.metrics, metrics);
+    Ok(())
+}
+
+#[test]
+fn test_result_is_paged_from_memory_until_spill_finishes() -> AppResult<()> {
+    let dir = spill_dir("pending");
+    let size = batches_size();
+    let mut cache = ResultCache::new(limits(size + size / 2, usize::MAX));
+    let first = insert(&mut cache, &dir, ScanMetrics::default())?;
+    let result = build(&cache, &dir)?;
+    let (_second, mut spills) = cache.insert(&dir, result, columns(), ScanMetrics::default());
+    assert_eq!(spills.len(), 1, "the older result is spilled");
+    assert!(!dir.exists(), "the spill is written by the caller");
+
+    let page = cache.page(&first, 8, 4)?.expect("cached");
+    assert_eq!(ids(&page.batches), vec![8, 9, 10, 11]);
+
+    let spill = spills.remove(0);
+    let written = spill.write();
+    cache.finish_spill(spill, written);
+    let page = cache.page(&first, 8, 4)?.expect("cached");
+    assert_eq!(ids(&page.batches), vec![8, 9, 10, 11]);
+    assert!(dir.join(format!("{}.arrow", first)).exists());
+
+    cache.remove(&first);
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_spill_of_released_result_is_discarded() -> AppResult<()> {
+    let dir = spill_dir("released");
+    let size = batches_size();
+    let mut cache = ResultCache::new(limits(size + size / 2, usize::MAX));
+    let first = insert(&mut cache, &dir, ScanMetrics::default())?;
+    let result = build(&cache, &dir)?;
+    let (_second, mut spills) = cache.insert(&dir, result, columns(), ScanMetrics::default());
+    assert!(cache.remove(&first));
+
+    let spill = spills.remove(0);
+    let written = spill.write();
+    cache.finish_spill(spill, written);
+    assert!(!dir.join(format!("{}.arrow", first)).exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_failed_spill_evicts_only_that_result() -> AppResult<()> {
+    // A file where the spill directory should be makes every spill fail.
+    let dir = spill_dir("failed");
+    std::fs::write(&dir, b"")?;
+    let size = batches_size();
+    let mut cache = ResultCache::new(limits(size + size / 2, usize::MAX));
+
+    let first = insert(&mut cache, &dir, ScanMetrics::default());
+    let second = insert(&mut cache, &dir, ScanMetrics::default());
+    let first_page = cache.page(first.as_ref().unwrap(), 0, 1);
+    let second_page = cache.page(second.as_ref().unwrap(), 0, 1);
+    let _ = std::fs::remove_file(&dir);
+
+    assert!(first_page?.is_none(), "the result that failed to spill is evicted");
+    assert!(second_page?.is_some(), "the newest result is kept");
+    Ok(())
+}
+
+#[test]
+fn test_builder_spills_once_memory_budget_is_crossed() -> AppResult<()> {
+    let dir = spill_dir("streamed");
+    let first_batch = batches()[0].get_array_memory_size();
+    let mut cache = ResultCache::new(limits(first_batch, usize::MAX));
+
+    let mut builder = cache.start(&dir, schema());
+    let mut batches = batches().into_iter();
+    builder.push(batches.next().unwrap())?;
+    assert!(!dir.exists(), "the first batch fits in memory");
+    builder.push(batches.next().unwrap())?;
+    assert!(dir.exists(), "the second batch crosses the budget");
+    for batch in batches {
+        builder.push(batch)?;
+    }
+    let (result_id, spills) =
+        cache.insert(&dir, builder.finish()?, columns(), ScanMetrics::default());
+    assert!(spills.is_empty());
+
+    let page = cache.page(&result_id, 8, 14)?.expect("cached");
+    assert_eq!(ids(&page.batches), (8..22).collect::<Vec<_>>());
+    assert_eq!(page.total_rows, 25);
+
+    cache.remove(&result_id);
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_abandoned_builder_removes_its_spill_file() -> AppResult<()> {
+    let dir = spill_dir("abandoned");
+    let cache = ResultCache::new(limits(0, usize::MAX));
+
+    let mut builder = cache.start(&dir, schema());
+    builder.push(batches().remove(0))?;
+    let files = std::fs::read_dir(&dir)?.count();
+    drop(builder);
+
+    assert_eq!(files, 1);
+    assert_eq!(std::fs::read_dir(&dir)?.count(), 0);
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_page_of_result_removed_while_reading_is_none() -> AppResult<()> {
+    let dir = spill_dir("removed");
+    let mut cache = ResultCache::new(limits(0, usize::MAX));
+    let result_id = insert(&mut cache, &dir, ScanMetrics::default())?;
+
+    let source = cache.page_source(&result_id, 0, 5).expect("cached");
+    assert!(cache.remove(&result_id));
+
+    assert!(source.read()?.is_none());
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -0,0 +1,874 @@
+use crate::context::context::{
+    csv_encoding, detect_json_newline_delimited, file_compression_type, get_csv_read_options,
+    get_json_read_options, listing_extension, mysql_table_provider, path_file_extension,
+    postgres_table_provider, read_excel, sniff_json_newline_delimited,
+};
+use crate::context::error::AppError;
+use crate::context::query_registry::CancellableRead;
+use crate::context::schema::AppResult;
+use crate::reader::encoding::{parse_encoding, Utf8Reader};
+use crate::reader::excel::ExcelReader;
+use crate::reader::zip::{read_zip_member, ZipMemberStore};
+use crate::utils::file_utils::{ensure_path_exists, find_files, find_zip_members, split_zip_path};
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion::arrow::csv::reader::Format;
+use datafusion::arrow::csv::ReaderBuilder;
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
+use datafusion::catalog::streaming::StreamingTable;
+use datafusion::catalog::{Session, TableFunctionImpl, TableProvider};
+use datafusion::common::ScalarValue;
+use datafusion::dataframe::DataFrame;
+use datafusion::datasource::file_format::csv::CsvFormat;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
+use datafusion::datasource::MemTable;
+use datafusion::error::DataFusionError;
+use datafusion::execution::object_store::ObjectStoreUrl;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
+use datafusion::physical_plan::stream::RecordBatchReceiverStream;
+use datafusion::physical_plan::streaming::PartitionStream;
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::prelude::{CsvReadOptions, JsonReadOptions, ParquetReadOptions, SessionContext};
+use datafusion::sql::parser::{CopyToSource, Statement as DFStatement};
+use datafusion::sql::sqlparser::ast::{
+    self as sql_ast, FunctionArg, FunctionArgExpr, TableFactor, Value, VisitMut, VisitorMut,
+};
+use encoding_rs::{Encoding, UTF_8};
+use futures::{Stream, StreamExt};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs::File;
+use std::future::Future;
+use std::io::{Cursor, Read};
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::RuntimeFlavor;
+
+/// A `read_*` table function: its SQL name and the names of the arguments it
+/// accepts after the leading path, in positional order.
+struct ReaderSpec {
+    name: &'static str,
+    parameters: &'static [&'static str],
+}
+
+const CSV_PARAMETERS: &[&str] = &[
+    "infer_schema",
+    "has_header",
+    "delimiter",
+    "file_extension",
+    "compression",
+    "encoding",
+];
+const JSON_PARAMETERS: &[&str] = &["file_extension", "compression"];
+const EXCEL_PARAMETERS: &[&str] = &["sheet_name", "infer_schema"];
+const ZIP_PARAMETERS: &[&str] = &[
+    "pattern",
+    "format",
+    "infer_schema",
+    "has_header",
+    "delimiter",
+    "file_extension",
+    "compression",
+    "encoding",
+    "sheet_name",
+];
+
+const READERS: &[ReaderSpec] = &[
+    ReaderSpec { name: "read_csv", parameters: CSV_PARAMETERS },
+    ReaderSpec { name: "read_tsv", parameters: CSV_PARAMETERS },
+    ReaderSpec { name: "read_text", parameters: CSV_PARAMETERS },
+    ReaderSpec { name: "read_json", parameters: JSON_PARAMETERS },
+    ReaderSpec { name: "read_ndjson", parameters: JSON_PARAMETERS },
+    ReaderSpec { name: "read_parquet", parameters: &[] },
+    ReaderSpec { name: "read_excel", parameters: EXCEL_PARAMETERS },
+    ReaderSpec { name: "read_xlsx", parameters: EXCEL_PARAMETERS },
+    ReaderSpec { name: "read_zip", parameters: ZIP_PARAMETERS },
+    ReaderSpec { name: "read_mysql", parameters: &["conn"] },
+    ReaderSpec {
+        name: "read_postgres",
+        parameters: &["host", "username", "db", "pass", "port", "sslmode"],
+    },
+];
+
+/// Positional parameter names of the reader called `name`, or `None` when
+/// `name` is not one of the `read_*` table functions.
+pub(crate) fn reader_parameters(name: &str) -> Option<&'static [&'static str]> {
+    READERS
+        .iter()
+        .find(|reader| reader.name.eq_ignore_ascii_case(name))
+        .map(|reader| reader.parameters)
+}
+
+pub fn register_table_functions(ctx: &SessionContext) {
+    for reader in READERS {
+        ctx.register_udtf(
+            reader.name,
+            Arc::new(ReaderFunction {
+                name: reader.name,
+                parameters: reader.parameters,
+            }),
+        );
+    }
+}
+
+/// Arguments of a single `read_*` call: the leading path plus every optional
+/// argument that was supplied (by position or by name).
+#[derive(Debug, Clone)]
+pub struct ReaderArgs {
+    function: String,
+    path: String,
+    values: HashMap<&'static str, ScalarValue>,
+}
+
+impl ReaderArgs {
+    /// Build the arguments of `function` from the planned call expressions.
+    /// The first expression is the path; the rest fill `parameters` in order,
+    /// with `NULL` meaning "not supplied".
+    pub fn try_new(function: &str, parameters: &[&'static str], exprs: &[Expr]) -> AppResult<Self> {
+        let Some(first) = exprs.first() else {
+            return Err(AppError::BadRequest {
+                message: "The file path is missing.".to_string(),
+            });
+        };
+        let path = literal(first)
+            .and_then(scalar_as_str)
+            .ok_or_else(|| AppError::BadRequest {
+                message: format!("The first argument of {} must be a string path.", function),
+            })?
+            .to_string();
+
+        let optional = &exprs[1..];
+        if optional.len() > parameters.len() {
+            return Err(AppError::BadRequest {
+                message: format!(
+                    "{} accepts at most {} argument(s) after the path, got {}",
+                    function,
+                    parameters.len(),
+                    optional.len()
+                ),
+            });
+        }
+
+        let mut values = HashMap::new();
+        for (name, expr) in parameters.iter().zip(optional) {
+            let value = literal(expr).ok_or_else(|| AppError::BadRequest {
+                message: format!("'{}' of {} must be a literal value", name, function),
+            })?;
+            if !value.is_null() {
+                values.insert(*name, value.clone());
+            }
+        }
+
+        Ok(Self {
+            function: function.to_string(),
+            path,
+            values,
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get_str(&self, name: &str) -> AppResult<Option<&str>> {
+        match self.values.get(name) {
+            None => Ok(None),
+            Some(value) => scalar_as_str(value)
+                .map(Some)
+                .ok_or_else(|| self.type_error(name, "a string")),
+        }
+    }
+
+    pub fn get_bool(&self, name: &str) -> AppResult<Option<bool>> {
+        match self.values.get(name) {
+            None => Ok(None),
+            Some(ScalarValue::Boolean(value)) => Ok(*value),
+            Some(_) => Err(self.type_error(name, "a boolean")),
+        }
+    }
+
+    fn type_error(&self, name: &str, expected: &str) -> AppError {
+        AppError::BadRequest {
+            message: format!("'{}' of {} must be {}", name, self.function, expected),
+        }
+    }
+}
+
+fn literal(expr: &Expr) -> Option<&ScalarValue> {
+    match expr {
+        Expr::Literal(value, _) => Some(value),
+        _ => None,
+    }
+}
+
+fn scalar_as_str(value: &ScalarValue) -> Option<&str> {
+    match value {
+        ScalarValue::Utf8(Some(value))
+        | ScalarValue::LargeUtf8(Some(value))
+        | ScalarValue::Utf8View(Some(value)) => Some(value.as_str()),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct ReaderFunction {
+    name: &'static str,
+    parameters: &'static [&'static str],
+}
+
+impl TableFunctionImpl for ReaderFunction {
+    fn call(&self, exprs: &[Expr]) -> datafusion::error::Result<Arc<dyn TableProvider>> {
+        let args = ReaderArgs::try_new(self.name, self.parameters, exprs)?;
+        block_on(create_table_provider(self.name, &args))
+            .and_then(|result| result)
+            .map_err(DataFusionError::from)
+    }
+}
+
+/// `TableFunctionImpl::call` is synchronous, while listing files, inferring
+/// schemas and opening connection pools are async. Block the current worker
+/// on them; this needs the multi-threaded runtime Tauri commands run on, since
+/// `block_in_place` panics on a current-thread runtime. Anywhere else this
+/// returns an error instead.
+fn block_on<F: Future>(future: F) -> AppResult<F::Output> {
+    let handle = tokio::runtime::Handle::try_current()
+        .ok()
+        .filter(|handle| handle.runtime_flavor() == RuntimeFlavor::MultiThread)
+        .ok_or_else(|| AppError::InternalServer {
+            message: "read_* table functions must be planned on a multi-threaded Tokio runtime"
+                .to_string(),
+        })?;
+    Ok(tokio::task::block_in_place(|| handle.block_on(future)))
+}
+
+async fn create_table_provider(
+    reader_name: &str,
+    args: &ReaderArgs,
+) -> AppResult<Arc<dyn TableProvider>> {
+    let table_path = args.path();
+
+    if reader_name == "read_zip" {
+        return read_zip(args).await;
+    }
+    if let Some((archive, pattern)) = split_zip_path(table_path) {
+        return zip_table_provider(reader_name, archive, pattern, args).await;
+    }
+
+    if !matches!(reader_name, "read_mysql" | "read_postgres") {
+        ensure_path_exists(table_path)?;
+    }
+    if is_csv_reader(reader_name) {
+        if let Some(provider) = transcoded_csv_provider(reader_name, table_path, args).await? {
+            return Ok(provider);
+        }
+    }
+
+    // File readers go through a scratch context so DataFusion's listing and
+    // schema inference can be reused as-is; the resulting view is planned and
+    // executed by the caller's session.
+    let ctx = SessionContext::new();
+    let df = match reader_name {
+        "read_excel" | "read_xlsx" => {
+            let batch = read_excel(ExcelReader::new(table_path.to_string()), args)?;
+            return Ok(Arc::new(MemTable::try_new(batch.schema(), vec![vec![batch]])?));
+        }
+        "read_mysql" => return mysql_table_provider(args).await,
+        "read_postgres" => return postgres_table_provider(args).await,
+        _ => {
+            // `read_json` accepts both a standard JSON array `[{...}, {...}]`
+            // (parsed natively since DataFusion 53) and newline-delimited
+            // JSON. The format is auto-detected from the file content, so a
+            // `.ndjson` file works through `read_json` too.
+            let newline_delimited = reader_name == "read_json"
+                && detect_json_newline_delimited(table_path, &file_compression_type(args)?);
+            read_files(
+                &ctx,
+                reader_name,
+                vec![table_path.to_string()],
+                args,
+                newline_delimited,
+            )
+            .await?
+        }
+    };
+
+    Ok(df.into_view())
+}
+
+/// Read `paths` with the DataFusion reader behind `reader_name`.
+/// `newline_delimited` is the detected shape of `read_json` input.
+///
+/// Compressed files (`orders.csv.gz`) are listed by their full extension,
+/// see `listing_extension`.
+async fn read_files(
+    ctx: &SessionContext,
+    reader_name: &str,
+    paths: Vec<String>,
+    args: &ReaderArgs,
+    newline_delimited: bool,
+) -> AppResult<DataFrame> {
+    let first_path = paths.first().cloned().unwrap_or_default();
+    let df = match reader_name {
+        "read_csv" | "read_tsv" | "read_text" => {
+            let options = csv_read_options(reader_name, args)?;
+            let extension = listing_extension(
+                options.file_extension,
+                &options.file_compression_type,
+                &first_path,
+            );
+            ctx.read_csv(paths, options.file_extension(&extension))
+                .await?
+        }
+        "read_json" => {
+            let json_ext = path_file_extension(&first_path);
+            let mut options = JsonReadOptions::default().newline_delimited(newline_delimited);
+            if let Some(ext) = json_ext.as_deref() {
+                options.file_extension = ext;
+            }
+            let options = get_json_read_options(args, options)?;
+            let extension = listing_extension(
+                options.file_extension,
+                &options.file_compression_type,
+                &first_path,
+            );
+            ctx.read_json(paths, options.file_extension(&extension))
+                .await?
+        }
+        "read_ndjson" => {
+            // Newline-delimited JSON: one JSON object per line.
+            let json_ext = path_file_extension(&first_path);
+            let mut options = JsonReadOptions::default()
+                .file_extension(".ndjson")
+                .newline_delimited(true);
+            if let Some(ext) = json_ext.as_deref() {
+                options.file_extension = ext;
+            }
+            let options = get_json_read_options(args, options)?;
+            let extension = listing_extension(
+                options.file_extension,
+                &options.file_compression_type,
+                &first_path,
+            );
+            ctx.read_json(paths, options.file_extension(&extension))
+                .await?
+        }
+        "read_parquet" => {
+            ctx.read_parquet(paths, ParquetReadOptions::default())
+                .await?
+        }
+        _ => {
+            return Err(AppError::BadRequest {
+                message: format!("'{}' is not a supported table function", reader_name),
+            })
+        }
+    };
+    Ok(df)
+}
+
+/// CSV options of `reader_name`: tab-separated for `read_tsv` and
+/// `read_text`, adjusted by the call's arguments.
+fn csv_read_options<'a>(reader_name: &str, args: &'a ReaderArgs) -> AppResult<CsvReadOptions<'a>> {
+    let mut options = CsvReadOptions::default();
+    if reader_name != "read_csv" {
+        options.delimiter = b'\t';
+        options.file_extension = if reader_name == "read_tsv" {
+            ".tsv"
+        } else {
+            ".txt"
+        };
+    }
+    get_csv_read_options(args, options)
+}
+
+/// `read_zip(path, pattern => '*.csv', format => 'csv')`: read the archive
+/// members matching `pattern` with the reader for `format`. Either one may be
+/// left out; the format then comes from the pattern's extension, and the
+/// pattern matches every member of that format.
+async fn read_zip(args: &ReaderArgs) -> AppResult<Arc<dyn TableProvider>> {
+    let pattern = args.get_str("pattern")?;
+    let format = match args.get_str("format")? {
+        Some(format) => format.trim().to_lowercase(),
+        None => pattern
+            .and_then(|pattern| std::path::Path::new(pattern).extension())
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .ok_or_else(|| AppError::BadRequest {
+                message: "read_zip needs a 'format' when the pattern has no extension".to_string(),
+            })?,
+    };
+    let (reader_name, extension) = match format.as_str() {
+        "csv" => ("read_csv", "csv"),
+        "tsv" => ("read_tsv", "tsv"),
+        "txt" | "text" => ("read_text", "txt"),
+        "json" => ("read_json", "json"),
+        "ndjson" | "jsonl" => ("read_ndjson", "ndjson"),
+        "parquet" => ("read_parquet", "parquet"),
+        "xlsx" | "excel" => ("read_excel", "xlsx"),
+        _ => {
+            return Err(AppError::BadRequest {
+                message: format!(
+                    "Unsupported read_zip format '{}'. Supported: csv, tsv, text, json, ndjson, parquet, excel",
+                    format
+                ),
+            })
+        }
+    };
+    let pattern = match pattern {
+        Some(pattern) => pattern.to_string(),
+        None => format!("**/*.{}", extension),
+    };
+    zip_table_provider(reader_name, args.path(), &pattern, args).await
+}
+
+/// Object stores of zip archive members are registered under
+/// `memory://files-<n>/`, one per table.
+static MEMORY_STORES: AtomicUsize = AtomicUsize::new(0);
+
+fn is_csv_reader(reader_name: &str) -> bool {
+    matches!(reader_name, "read_csv" | "read_tsv" | "read_text")
+}
+
+/// Read the members of `archive` matching `pattern` with `reader_name`. Each
+/// member is decompressed into memory while it is read rather than
+/// extracted.
+async fn zip_table_provider(
+    reader_name: &str,
+    archive: &str,
+    pattern: &str,
+    args: &ReaderArgs,
+) -> AppResult<Arc<dyn TableProvider>> {
+    if matches!(reader_name, "read_mysql" | "read_postgres" | "read_zip") {
+        return Err(AppError::BadRequest {
+            message: format!("{} can't read from a zip archive", reader_name),
+        });
+    }
+    if !is_csv_reader(reader_name) && args.get_str("encoding")?.is_some() {
+        return Err(AppError::BadRequest {
+            message: "'encoding' is only supported for CSV, TSV and text files".to_string(),
+        });
+    }
+    let members = find_zip_members(archive, pattern)?;
+    if matches!(reader_name, "read_excel" | "read_xlsx") {
+        let reader = ExcelReader::from_zip(archive.to_string(), members);
+        let batch = read_excel(reader, args)?;
+        return Ok(Arc::new(MemTable::try_new(batch.schema(), vec![vec![batch]])?));
+    }
+
+    let compression = file_compression_type(args)?;
+    if is_csv_reader(reader_name) {
+        let first = read_zip_member(archive, &members[0])?;
+        let encoding = csv_encoding(args, compression.convert_read(Cursor::new(first))?)?;
+        if encoding != UTF_8 {
+            let inputs = members
+                .into_iter()
+                .map(|name| CsvInput::ZipMember {
+                    archive: archive.to_string(),
+                    name,
+                })
+                .collect();
+            return transcoded_csv_table(reader_name, inputs, compression, encoding, args).await;
+        }
+    }
+    let newline_delimited = if reader_name == "read_json" {
+        let first = read_zip_member(archive, &members[0])?;
+        let sniffed = compression
+            .convert_read(Cursor::new(first))
+            .ok()
+            .and_then(sniff_json_newline_delimited);
+        sniffed.unwrap_or_else(|| members[0].to_ascii_lowercase().ends_with(".ndjson"))
+    } else {
+        false
+    };
+    zip_members_table(reader_name, archive, &members, args, newline_delimited).await
+}
+
+/// Read CSV files that aren't UTF-8, which DataFusion's CSV reader requires,
+/// by converting them while they are read. The encoding is the `encoding`
+/// argument or detected from the first file. Returns `None` for UTF-8 input,
+/// which is read from disk as-is.
+async fn transcoded_csv_provider(
+    reader_name: &str,
+    table_path: &str,
+    args: &ReaderArgs,
+) -> AppResult<Option<Arc<dyn TableProvider>>> {
+    if table_path.contains("://") || std::path::Path::new(table_path).is_dir() {
+        // DataFusion lists these itself, so there is no file to convert.
+        return match args.get_str("encoding")? {
+            Some(label) if parse_encoding(label)? != UTF_8 => Err(AppError::BadRequest {
+                message: format!(
+                    "Encoding '{}' is only supported for local files and glob patterns",
+                    label
+                ),
+            }),
+            _ => Ok(None),
+        };
+    }
+    let compression = file_compression_type(args)?;
+    let files = find_files(table_path)?;
+    let encoding = csv_encoding(args, compression.convert_read(File::open(&files[0])?)?)?;
+    if encoding == UTF_8 {
+        return Ok(None);
+    }
+    let inputs = files.into_iter().map(CsvInput::File).collect();
+    transcoded_csv_table(reader_name, inputs, compression, encoding, args)
+        .await
+        .map(Some)
+}
+
+/// A CSV file read by [`transcoded_csv_table`].
+#[derive(Clone, Debug)]
+enum CsvInput {
+    File(String),
+    /// A zip archive member, read into memory when it is opened.
+    ZipMember {
+        archive: String,
+        name: String,
+    },
+}
+
+impl CsvInput {
+    /// The content, decompressed and converted from `encoding` to UTF-8.
+    fn open(
+        &self,
+        compression: FileCompressionType,
+        encoding: &'static Encoding,
+    ) -> AppResult<Utf8Reader<Box<dyn Read + Send>>> {
+        let content = match self {
+            CsvInput::File(path) => compression.convert_read(File::open(path)?)?,
+            CsvInput::ZipMember { archive, name } => {
+                let bytes = read_zip_member(archive, name)?;
+                compression.convert_read(Cursor::new(bytes))?
+            }
+        };
+        Ok(Utf8Reader::new(content, encoding))
+    }
+}
+
+/// Read `inputs` in `encoding` as a table streamed through a [`Utf8Reader`],
+/// one partition per file. The schema is inferred like DataFusion's CSV
+/// reader does, from the first records of the converted files.
+async fn transcoded_csv_table(
+    reader_name: &str,
+    inputs: Vec<CsvInput>,
+    compression: FileCompressionType,
+    encoding: &'static Encoding,
+    args: &ReaderArgs,
+) -> AppResult<Arc<dyn TableProvider>> {
+    let options = csv_read_options(reader_name, args)?;
+    let csv_format = CsvFormat::default()
+        .with_has_header(options.has_header)
+        .with_delimiter(options.delimiter)
+        .with_quote(options.quote)
+        .with_escape(options.escape)
+        .with_terminator(options.terminator)
+        .with_comment(options.comment)
+        .with_schema_infer_max_rec(options.schema_infer_max_records);
+    let mut format = Format::default()
+        .with_header(options.has_header)
+        .with_delimiter(options.delimiter)
+        .with_quote(options.quote);
+    if let Some(escape) = options.escape {
+        format = format.with_escape(escape);
+    }
+    if let Some(terminator) = options.terminator {
+        format = format.with_terminator(terminator);
+    }
+    if let Some(comment) = options.comment {
+        format = format.with_comment(comment);
+    }
+
+    let state = SessionContext::new().state();
+    let mut schemas = Vec::new();
+    let mut records_to_read = options.schema_infer_max_records;
+    for input in &inputs {
+        let content = CancellableRead::new(input.open(compression, encoding)?);
+        let chunks = csv_format
+            .read_to_delimited_chunks_from_stream(read_chunks(content).boxed())
+            .await;
+        let (schema, records_read) = csv_format
+            .infer_schema_from_stream(&state, records_to_read, chunks)
+            .await?;
+        records_to_read -= records_read;
+        schemas.push(schema);
+        if records_to_read == 0 {
+            break;
+        }
+    }
+    let schema = Arc::new(Schema::try_merge(schemas)?);
+
+    let partitions = inputs
+        .into_iter()
+        .map(|input| {
+            Arc::new(TranscodedCsvPartition {
+                schema: schema.clone(),
+                input,
+                compression,
+                encoding,
+                format: format.clone(),
+            }) as Arc<dyn PartitionStream>
+        })
+        .collect();
+    Ok(Arc::new(StreamingTable::try_new(schema, partitions)?))
+}
+
+/// `content` as a stream of chunks, read as they are polled.
+fn read_chunks(
+    mut content: impl Read + Send + 'static,
+) -> impl Stream<Item = datafusion::error::Result<Bytes>> + Send {
+    futures::stream::iter(std::iter::from_fn(move || {
+        let mut chunk = vec![0u8; 64 * 1024];
+        match content.read(&mut chunk) {
+            Ok(0) => None,
+            Ok(read) => {
+                chunk.truncate(read);
+                Some(Ok(Bytes::from(chunk)))
+            }
+            Err(err) => Some(Err(DataFusionError::from(err))),
+        }
+    }))
+}
+
+/// One file of a [`transcoded_csv_table`]. It is converted and parsed on a
+/// blocking thread, which stops once the query drops the stream.
+#[derive(Debug)]
+struct TranscodedCsvPartition {
+    schema: SchemaRef,
+    input: CsvInput,
+    compression: FileCompressionType,
+    encoding: &'static Encoding,
+    format: Format,
+}
+
+impl PartitionStream for TranscodedCsvPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let mut builder = RecordBatchReceiverStream::builder(self.schema.clone(), 2);
+        let tx = builder.tx();
+        let input = self.input.clone();
+        let (compression, encoding) = (self.compression, self.encoding);
+        let reader = ReaderBuilder::new(self.schema.clone())
+            .with_format(self.format.clone())
+            .with_batch_size(ctx.session_config().batch_size());
+        builder.spawn_blocking(move || {
+            let content = input.open(compression, encoding)?;
+            for batch in reader.build(content)? {
+                if tx.blocking_send(batch.map_err(DataFusionError::from)).is_err() {
+                    // The query was cancelled or has all the rows it needs.
+                    break;
+                }
+            }
+            Ok(())
+        });
+        builder.build()
+    }
+}
+
+/// Read the members `names` of `archive` with `reader_name`. The members are
+/// streamed from a [`ZipMemberStore`] that is registered with the session
+/// running the query, see [`ZipMembersTable`].
+async fn zip_members_table(
+    reader_name: &str,
+    archive: &str,
+    names: &[String],
+    args: &ReaderArgs,
+    newline_delimited: bool,
+) -> AppResult<Arc<dyn TableProvider>> {
+    let store = Arc::new(ZipMemberStore::new(archive, names)?);
+    let url = format!(
+        "memory://files-{}/",
+        MEMORY_STORES.fetch_add(1, Ordering::Relaxed)
+    );
+    let store_url = ObjectStoreUrl::parse(&url)?;
+    let ctx = SessionContext::new();
+    ctx.register_object_store(store_url.as_ref(), store.clone());
+
+    let paths = names.iter().map(|name| format!("{}{}", url, name)).collect();
+    let df = read_files(&ctx, reader_name, paths, args, newline_delimited).await?;
+    Ok(Arc::new(ZipMembersTable {
+        view: df.into_view(),
+        store_url,
+        store,
+    }))
+}
+
+/// A table read from zip archive members. Planning a scan registers their
+/// object store with the session running the query, which then streams the
+/// members like any other files. Sessions are created per query, so the store
+/// is dropped with it.
+#[derive(Debug)]
+struct ZipMembersTable {
+    view: Arc<dyn TableProvider>,
+    store_url: ObjectStoreUrl,
+    store: Arc<ZipMemberStore>,
+}
+
+#[async_trait]
+impl TableProvider for ZipMembersTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.view.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.view.table_type()
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> datafusion::error::Result<Arc<dyn ExecutionPlan>> {
+        state
+            .runtime_env()
+            .register_object_store(self.store_url.as_ref(), self.store.clone());
+        self.view.scan(state, projection, filters, limit).await
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> datafusion::error::Result<Vec<TableProviderFilterPushDown>> {
+        self.view.supports_filters_pushdown(filters)
+    }
+}
+
+/// Rewrite `name => value` arguments of `read_*` calls into positional
+/// arguments, padding skipped parameters with `NULL`.
+///
+/// DataFusion's planner only passes unnamed table function arguments through
+/// to [`TableFunctionImpl::call`], so named arguments are mapped onto each
+/// reader's declared parameter order before planning. The path can be given
+/// as `path => '...'`. An unknown name, or an argument given both by name and
+/// by position, is an error.
+pub fn normalize_named_args(statement: &mut DFStatement) -> AppResult<()> {
+    let flow = match statement {
+        DFStatement::Statement(statement) => statement.visit(&mut NamedArgNormalizer),
+        DFStatement::CopyTo(copy) => match &mut copy.source {
+            CopyToSource::Query(query) => query.visit(&mut NamedArgNormalizer),
+            _ => ControlFlow::Continue(()),
+        },
+        DFStatement::Explain(explain) => return normalize_named_args(&mut explain.statement),
+        _ => ControlFlow::Continue(()),
+    };
+
+    match flow {
+        ControlFlow::Break(err) => Err(err),
+        ControlFlow::Continue(()) => Ok(()),
+    }
+}
+
+struct NamedArgNormalizer;
+
+impl VisitorMut for NamedArgNormalizer {
+    type Break = AppError;
+
+    fn pre_visit_table_factor(
+        &mut self,
+        table_factor: &mut TableFactor,
+    ) -> ControlFlow<Self::Break> {
+        if let TableFactor::Table {
+            name,
+            args: Some(args),
+            ..
+        } = table_factor
+        {
+            if let Some(parameters) = reader_parameters(&name.to_string()) {
+                if let Err(err) = positional_args(&name.to_string(), &mut args.args, parameters) {
+                    return ControlFlow::Break(err);
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn positional_args(
+    function: &str,
+    args: &mut Vec<FunctionArg>,
+    parameters: &[&str],
+) -> AppResult<()> {
+    let is_named = |arg: &FunctionArg| {
+        matches!(arg, FunctionArg::Named { .. } | FunctionArg::ExprNamed { .. })
+    };
+    if !args.iter().any(is_named) {
+        return Ok(());
+    }
+
+    let given_twice = |name: &str| AppError::BadRequest {
+        message: format!("Argument '{}' of {} given twice", name, function),
+    };
+    let mut unnamed = Vec::new();
+    let mut path = None;
+    let mut slots: Vec<Option<FunctionArgExpr>> = vec![None; parameters.len()];
+    for arg in args.drain(..) {
+        let (name, value) = match arg {
+            FunctionArg::Unnamed(value) => {
+                unnamed.push(value);
+                continue;
+            }
+            FunctionArg::Named { name, arg, .. } => (name.value, arg),
+            FunctionArg::ExprNamed {
+                name: sql_ast::Expr::Identifier(name),
+                arg,
+                ..
+            } => (name.value, arg),
+            FunctionArg::ExprNamed { name, .. } => {
+                return Err(AppError::BadRequest {
+                    message: format!("Invalid argument name '{}' for {}", name, function),
+                })
+            }
+        };
+        let slot = if name.eq_ignore_ascii_case("path") {
+            &mut path
+        } else {
+            let index = parameters
+                .iter()
+                .position(|parameter| parameter.eq_ignore_ascii_case(&name))
+                .ok_or_else(|| AppError::BadRequest {
+                    message: format!("Unknown argument '{}' for {}", name, function),
+                })?;
+            &mut slots[index]
+        };
+        if slot.replace(value).is_some() {
+            return Err(given_twice(&name.to_lowercase()));
+        }
+    }
+
+    // The first unnamed argument is the path unless it was given by name; any
+    // further unnamed arguments fill the leading parameter slots, exactly like
+    // a positional call.
+    if path.is_some() && !unnamed.is_empty() {
+        return Err(given_twice("path"));
+    }
+    let mut unnamed = unnamed.into_iter();
+    if path.is_none() {
+        path = unnamed.next();
+    }
+    args.extend(path.map(FunctionArg::Unnamed));
+    for (index, value) in unnamed.enumerate() {
+        match slots.get_mut(index) {
+            Some(slot) if slot.is_none() => *slot = Some(value),
+            Some(_) => return Err(given_twice(parameters[index])),
+            // Left for `ReaderArgs::try_new` to report.
+            None => args.push(FunctionArg::Unnamed(value)),
+        }
+    }
+
+    let used = slots.iter().rposition(Option::is_some).map_or(0, |i| i + 1);
+    for slot in slots.into_iter().take(used) {
+        args.push(FunctionArg::Unnamed(slot.unwrap_or_else(|| {
+            FunctionArgExpr::Expr(sql_ast::Expr::value(Value::Null))
+        })));
+    }
+
+    Ok(())
+}
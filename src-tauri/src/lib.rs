@@ -1,9 +1,12 @@
 use crate::commands::app::restart_app;
 use crate::commands::query::{
-    delete_saved_query, delete_sql_history_before, fetch, fetch_column_types, fetch_page,
-    generate_sql_content, list_saved_queries, save_query, sql_history, writer,
+    cancel_query, delete_saved_query, delete_sql_history_before, fetch, fetch_column_types,
+    fetch_page, fetch_stream, generate_sql_content, list_saved_queries, release_result,
+    running_queries, save_query, sql_history, writer,
 };
 use crate::commands::utils::open_url;
+use crate::context::result_cache;
+use crate::utils::app_data_utils::get_result_cache_dir;
 use crate::utils::db_utils;
 use tauri::Listener;
 
@@ -14,6 +17,7 @@ pub mod context;
 pub mod reader;
 pub mod sql;
 pub mod utils;
+pub mod writer;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -28,18 +32,23 @@ pub fn run() {
             }
             app.handle().plugin(tauri_plugin_dialog::init())?;
             db_utils::init(&app.handle());
+            result_cache::clear_spill_dir(&get_result_cache_dir(app.handle())?)?;
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
+            cancel_query,
             delete_saved_query,
             delete_sql_history_before,
             fetch,
             fetch_column_types,
             fetch_page,
+            fetch_stream,
             generate_sql_content,
             list_saved_queries,
             open_url,
+            release_result,
             restart_app,
+            running_queries,
             save_query,
             sql_history,
             writer
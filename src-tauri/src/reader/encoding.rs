@@ -0,0 +1,114 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use chardetng::EncodingDetector;
+use encoding_rs::{CoderResult, Decoder, Encoding, UTF_8};
+use std::io::{self, Read};
+
+/// Bytes from the start of a file checked for a byte order mark and UTF-8,
+/// and otherwise fed to the charset detector.
+const SAMPLE_BYTES: usize = 64 * 1024;
+/// Bytes decoded at a time when converting to UTF-8.
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// Look up an encoding by its label: `utf-8`, `gbk`, `gb18030`, `utf-16le`,
+/// `utf-16be`, `latin1`, `shift_jis`, ... (any WHATWG Encoding Standard
+/// label). `latin1` reads as windows-1252, its superset.
+pub fn parse_encoding(label: &str) -> AppResult<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| AppError::BadRequest {
+        message: format!(
+            "Unsupported encoding '{}'. Use a label such as utf-8, gbk, gb18030, utf-16le, utf-16be or latin1",
+            label
+        ),
+    })
+}
+
+/// Detect the encoding of `content` from its first bytes: a byte order mark
+/// if there is one, UTF-8 if the bytes are valid UTF-8, otherwise the best
+/// guess of a charset detector. UTF-16 is only recognized by its BOM.
+pub fn detect_encoding(content: impl Read) -> AppResult<&'static Encoding> {
+    let mut sample = Vec::with_capacity(SAMPLE_BYTES);
+    content
+        .take(SAMPLE_BYTES as u64)
+        .read_to_end(&mut sample)?;
+
+    if let Some((encoding, _)) = Encoding::for_bom(&sample) {
+        return Ok(encoding);
+    }
+    match std::str::from_utf8(&sample) {
+        Ok(_) => return Ok(UTF_8),
+        // Only the last character is cut off by the end of the sample.
+        Err(e) if e.error_len().is_none() => return Ok(UTF_8),
+        Err(_) => {}
+    }
+    let mut detector = EncodingDetector::new();
+    detector.feed(&sample, sample.len() < SAMPLE_BYTES);
+    Ok(detector.guess(None, false))
+}
+
+/// Reads `inner` as `encoding` and yields it as UTF-8, dropping a BOM, so
+/// files can be converted as they are read. Malformed sequences become
+/// U+FFFD.
+pub struct Utf8Reader<R> {
+    inner: R,
+    decoder: Decoder,
+    input: Vec<u8>,
+    /// Decoded bytes, read from `position` on.
+    output: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read> Utf8Reader<R> {
+    pub fn new(inner: R, encoding: &'static Encoding) -> Self {
+        Self {
+            inner,
+            decoder: encoding.new_decoder_with_bom_removal(),
+            input: vec![0u8; CHUNK_BYTES],
+            output: Vec::new(),
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Decode the next chunk of `inner` into `output`.
+    fn fill(&mut self) -> io::Result<()> {
+        let read = self.inner.read(&mut self.input)?;
+        let last = read == 0;
+        let mut input = &self.input[..read];
+        self.output.clear();
+        self.position = 0;
+        loop {
+            let start = self.output.len();
+            let needed = self
+                .decoder
+                .max_utf8_buffer_length(input.len())
+                .unwrap_or(CHUNK_BYTES);
+            self.output.resize(start + needed, 0);
+            let (result, consumed, written, _) =
+                self.decoder
+                    .decode_to_utf8(input, &mut self.output[start..], last);
+            self.output.truncate(start + written);
+            input = &input[consumed..];
+            if result == CoderResult::InputEmpty {
+                break;
+            }
+        }
+        self.finished = last;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Utf8Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.output.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let len = buf.len().min(self.output.len() - self.position);
+        buf[..len].copy_from_slice(&self.output[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
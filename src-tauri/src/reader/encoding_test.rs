@@ -0,0 +1,83 @@
+use super::encoding::{detect_encoding, parse_encoding, Utf8Reader};
+use encoding_rs::{Encoding, GBK, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use std::io::Read;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// A GBK export the size of a small bank statement.
+fn gbk_statement() -> Vec<u8> {
+    let text = "日期,摘要,金额\n".to_string() + &"2024-01-02,工资收入,12000.00\n".repeat(50);
+    GBK.encode(&text).0.into_owned()
+}
+
+/// Read all of `content` as `encoding` through a [`Utf8Reader`], a few
+/// bytes at a time so characters are split across reads.
+fn read_utf8(content: &[u8], encoding: &'static Encoding) -> Vec<u8> {
+    let mut reader = Utf8Reader::new(content, encoding);
+    let mut text = Vec::new();
+    let mut buffer = [0u8; 5];
+    loop {
+        let read = reader.read(&mut buffer).unwrap();
+        if read == 0 {
+            return text;
+        }
+        text.extend_from_slice(&buffer[..read]);
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_parse_encoding_labels() {
+    assert_eq!(parse_encoding("GBK").unwrap(), GBK);
+    assert_eq!(parse_encoding(" utf-8 ").unwrap(), UTF_8);
+    assert_eq!(parse_encoding("utf-16le").unwrap(), UTF_16LE);
+    assert_eq!(parse_encoding("latin1").unwrap(), WINDOWS_1252);
+    assert_eq!(parse_encoding("gb18030").unwrap().name(), "gb18030");
+    assert!(parse_encoding("klingon").is_err());
+}
+
+#[test]
+fn test_detects_bom() {
+    assert_eq!(detect_encoding(&b"\xFF\xFEa\0"[..]).unwrap(), UTF_16LE);
+    assert_eq!(detect_encoding(&b"\xFE\xFF\0a"[..]).unwrap(), UTF_16BE);
+    assert_eq!(detect_encoding(&b"\xEF\xBB\xBFid"[..]).unwrap(), UTF_8);
+}
+
+#[test]
+fn test_detects_utf8_and_gbk() {
+    assert_eq!(detect_encoding("名称\n中文\n".as_bytes()).unwrap(), UTF_8);
+    assert_eq!(detect_encoding(&b"id,name\n1,a\n"[..]).unwrap(), UTF_8);
+    assert_eq!(detect_encoding(gbk_statement().as_slice()).unwrap(), GBK);
+}
+
+#[test]
+fn test_utf8_cut_off_by_sample_is_utf8() {
+    // The sample ends inside the three bytes of the last '中'.
+    let mut content = "中".repeat(64 * 1024 / 3).into_bytes();
+    content.extend_from_slice("中".as_bytes());
+    assert_eq!(detect_encoding(content.as_slice()).unwrap(), UTF_8);
+}
+
+#[test]
+fn test_gbk_after_an_ascii_prefix_is_gbk() {
+    // The first 16 KiB are plain ASCII, which is also valid UTF-8.
+    let mut content = "id,name\n".repeat(2 * 1024).into_bytes();
+    content.extend(gbk_statement());
+    assert_eq!(detect_encoding(content.as_slice()).unwrap(), GBK);
+}
+
+#[test]
+fn test_utf8_reader() {
+    let statement = gbk_statement();
+    let text = read_utf8(&statement, GBK);
+    assert!(text.starts_with("日期,摘要,金额\n".as_bytes()));
+    assert_eq!(text, GBK.decode(&statement).0.as_bytes());
+
+    let utf16 = b"\xFF\xFEi\0d\0\n\0\x2D\x4E\n\0";
+    assert_eq!(read_utf8(&utf16[..], UTF_16LE), "id\n中\n".as_bytes());
+}
@@ -1,5 +1,7 @@
 use crate::context::error::AppError;
+use crate::context::query_registry::check_cancelled;
 use crate::context::schema::AppResult;
+use crate::reader::zip::read_zip_member;
 use crate::utils::file_utils::find_files;
 use calamine::{open_workbook, Data, HeaderRow, Range, Reader, Xlsx};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
@@ -9,26 +11,47 @@ use datafusion::arrow::array::{
 use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use datafusion::arrow::record_batch::RecordBatch;
 use std::collections::HashSet;
+use std::io::{Cursor, Read, Seek};
 use std::sync::Arc;
 
 pub struct ExcelReadOptions {}
 
+#[derive(Default)]
 pub struct ExcelParseOptions {}
 
+/// Where the workbooks come from.
+enum ExcelSource {
+    /// A file path or glob pattern.
+    Path(String),
+    /// Members of a zip archive.
+    Zip {
+        archive: String,
+        members: Vec<String>,
+    },
+}
+
 pub struct ExcelReader {
-    path: String,
+    source: ExcelSource,
     sheet_name: Option<String>,
     infer_schema_length: usize,
-    try_parse_dates: bool,
 }
 
 impl ExcelReader {
     pub fn new(path: String) -> Self {
+        Self::with_source(ExcelSource::Path(path))
+    }
+
+    /// Read the workbooks `members` of the zip archive `archive`, appending
+    /// their rows in order.
+    pub fn from_zip(archive: String, members: Vec<String>) -> Self {
+        Self::with_source(ExcelSource::Zip { archive, members })
+    }
+
+    fn with_source(source: ExcelSource) -> Self {
         Self {
-            path,
+            source,
             sheet_name: None,
             infer_schema_length: 1000,
-            try_parse_dates: false,
         }
     }
 
@@ -49,18 +72,21 @@ impl ExcelReader {
         let mut float64_data: Vec<Vec<Option<f64>>> = Vec::new();
         let mut timestamp_data: Vec<Vec<Option<i64>>> = Vec::new();
 
-        let default_sheet = "Sheet1".to_string();
-        let files = find_files(&self.path)?;
-        for file in files {
-            let mut xlsx: Xlsx<_> = open_workbook(file)?;
-            let sheet_names = xlsx.sheet_names();
-            let range =
-                xlsx.with_header_row(HeaderRow::Row(0))
-                    .worksheet_range(match self.sheet_name {
-                        Some(ref sheet_name) => sheet_name,
-                        None => sheet_names.get(0).unwrap_or(&default_sheet)
-                    })?;
-
+        // One workbook is open at a time, and its sheet is dropped once its
+        // rows are copied.
+        let ranges: Box<dyn Iterator<Item = AppResult<Range<Data>>>> = match &self.source {
+            ExcelSource::Path(path) => Box::new(
+                find_files(path)?
+                    .into_iter()
+                    .map(|file| self.worksheet(open_workbook(file)?)),
+            ),
+            ExcelSource::Zip { archive, members } => Box::new(members.iter().map(|member| {
+                let bytes = read_zip_member(archive, member)?;
+                self.worksheet(Xlsx::new(Cursor::new(bytes))?)
+            })),
+        };
+        for range in ranges {
+            let range = range?;
             if schema.is_none() {
                 let inferred = infer_field_schema(&range, self.infer_schema_length)?;
                 let num_columns = inferred.fields().len();
@@ -73,12 +99,13 @@ impl ExcelReader {
 
             if let Some(schema) = &schema {
                 for row in range.rows().skip(1) {
+                    check_cancelled()?;
                     for (i, cell) in row.iter().enumerate() {
                         if let Some(field) = schema.fields().get(i) {
                             match field.data_type() {
                                 DataType::Int64 => {
                                     let value = match cell {
-                                        Data::Int(v) => Some(*v as i64),
+                                        Data::Int(v) => Some(*v),
                                         Data::Float(v) => Some(*v as i64),
                                         _ => None,
                                     };
@@ -113,14 +140,6 @@ impl ExcelReader {
 
         match schema {
             Some(schema) => {
-                let num_rows = string_data
-                    .first()
-                    .map(|v| v.len())
-                    .or_else(|| int64_data.first().map(|v| v.len()))
-                    .or_else(|| float64_data.first().map(|v| v.len()))
-                    .or_else(|| timestamp_data.first().map(|v| v.len()))
-                    .unwrap_or(0);
-
                 let mut arrays = Vec::new();
                 for (i, field) in schema.fields().iter().enumerate() {
                     let array: Arc<dyn Array> = match field.data_type() {
@@ -142,11 +161,18 @@ impl ExcelReader {
             }),
         }
     }
-}
 
-impl Default for ExcelParseOptions {
-    fn default() -> Self {
-        Self {}
+    /// The configured sheet of `xlsx`, or its first sheet.
+    fn worksheet<RS: Read + Seek>(&self, mut xlsx: Xlsx<RS>) -> AppResult<Range<Data>> {
+        let default_sheet = "Sheet1".to_string();
+        let sheet_names = xlsx.sheet_names();
+        let range = xlsx
+            .with_header_row(HeaderRow::Row(0))
+            .worksheet_range(match self.sheet_name {
+                Some(ref sheet_name) => sheet_name,
+                None => sheet_names.first().unwrap_or(&default_sheet),
+            })?;
+        Ok(range)
     }
 }
 
@@ -268,13 +294,3 @@ pub(crate) fn infer_cell_data_type(cell: &Data) -> DataType {
         _ => DataType::Utf8,
     }
 }
-
-fn convert_arrow_data_type(cell: &Data) -> DataType {
-    match cell {
-        Data::String(_) => DataType::Utf8,
-        Data::Float(_) => DataType::Float64,
-        Data::Int(_) => DataType::Int64,
-        Data::DateTime(_) => DataType::Timestamp(TimeUnit::Nanosecond, None),
-        _ => DataType::Utf8,
-    }
-}
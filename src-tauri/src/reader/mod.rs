@@ -1,4 +1,10 @@
+pub mod encoding;
 pub mod excel;
+pub mod zip;
 
+#[cfg(test)]
+mod encoding_test;
 #[cfg(test)]
 mod excel_test;
+#[cfg(test)]
+mod zip_test;
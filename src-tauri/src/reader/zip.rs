@@ -0,0 +1,262 @@
+use crate::context::error::AppError;
+use crate::context::query_registry::CancellableRead;
+use crate::context::schema::AppResult;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use object_store::path::Path as ObjectPath;
+use object_store::{
+    CopyOptions, GetOptions, GetResult, GetResultPayload, ListResult, MultipartUpload, ObjectMeta,
+    ObjectStore, PutMultipartOptions, PutOptions, PutPayload, PutResult,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::sync::Mutex;
+use zip::ZipArchive;
+
+/// Most bytes, uncompressed, read from one zip archive member. A member is
+/// held in memory while it is read.
+pub const MAX_ZIP_MEMBER_BYTES: u64 = 1024 * 1024 * 1024;
+
+pub(crate) fn open_archive(archive: &str) -> AppResult<ZipArchive<BufReader<File>>> {
+    ZipArchive::new(BufReader::new(File::open(archive)?)).map_err(|e| AppError::BadRequest {
+        message: format!("'{}' is not a readable zip archive: {}", archive, e),
+    })
+}
+
+/// Read the member `name` of the zip archive `archive` into memory, without
+/// extracting it to disk.
+pub fn read_zip_member(archive: &str, name: &str) -> AppResult<Vec<u8>> {
+    read_member(archive, name, MAX_ZIP_MEMBER_BYTES)
+}
+
+/// [`read_zip_member`], failing once more than `max_bytes` are read.
+pub(crate) fn read_member(archive: &str, name: &str, max_bytes: u64) -> AppResult<Vec<u8>> {
+    let mut zip = open_archive(archive)?;
+    let member = zip.by_name(name)?;
+    let too_large = || AppError::BadRequest {
+        message: format!(
+            "The zip member '{}!/{}' is larger than {} MiB uncompressed. \
+             Extract it and read the file instead",
+            archive,
+            name,
+            max_bytes / (1024 * 1024)
+        ),
+    };
+    if member.size() > max_bytes {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::with_capacity(member.size() as usize);
+    // The declared size may be wrong, so the read is capped as well.
+    CancellableRead::new(member)
+        .take(max_bytes + 1)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(too_large());
+    }
+    Ok(bytes)
+}
+
+/// The members of a zip archive as a read-only object store, so DataFusion
+/// scans them like any other files. A member is decompressed when a scan
+/// reads it rather than up front; the last one read is kept for the ranged
+/// reads of Parquet files.
+#[derive(Debug)]
+pub struct ZipMemberStore {
+    archive: String,
+    /// Member name and declared size of each object.
+    members: BTreeMap<ObjectPath, (String, u64)>,
+    last_modified: DateTime<Utc>,
+    last_read: Mutex<Option<(ObjectPath, Bytes)>>,
+}
+
+impl ZipMemberStore {
+    /// A store of the members `names` of `archive`, each under the object
+    /// path of its name.
+    pub fn new(archive: &str, names: &[String]) -> AppResult<Self> {
+        let last_modified = std::fs::metadata(archive)?.modified()?.into();
+        let mut zip = open_archive(archive)?;
+        let members = names
+            .iter()
+            .map(|name| {
+                let size = zip.by_name(name)?.size();
+                Ok((ObjectPath::from(name.as_str()), (name.clone(), size)))
+            })
+            .collect::<AppResult<_>>()?;
+        Ok(Self {
+            archive: archive.to_string(),
+            members,
+            last_modified,
+            last_read: Mutex::new(None),
+        })
+    }
+
+    fn meta(&self, location: &ObjectPath, size: u64) -> ObjectMeta {
+        ObjectMeta {
+            location: location.clone(),
+            last_modified: self.last_modified,
+            size,
+            e_tag: None,
+            version: None,
+        }
+    }
+
+    async fn read(&self, location: &ObjectPath, name: &str) -> object_store::Result<Bytes> {
+        if let Some((path, bytes)) = &*self.last_read.lock().unwrap_or_else(|e| e.into_inner()) {
+            if path == location {
+                return Ok(bytes.clone());
+            }
+        }
+        let (archive, name) = (self.archive.clone(), name.to_string());
+        let read = tokio::task::spawn_blocking(move || read_zip_member(&archive, &name)).await;
+        let bytes = read
+            .map_err(AppError::from)
+            .and_then(|read| read)
+            .map_err(store_error)?;
+        let bytes = Bytes::from(bytes);
+        *self.last_read.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((location.clone(), bytes.clone()));
+        Ok(bytes)
+    }
+}
+
+impl fmt::Display for ZipMemberStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ZipMemberStore({})", self.archive)
+    }
+}
+
+fn store_error(error: impl std::error::Error + Send + Sync + 'static) -> object_store::Error {
+    object_store::Error::Generic {
+        store: "zip",
+        source: Box::new(error),
+    }
+}
+
+fn read_only() -> object_store::Error {
+    object_store::Error::NotSupported {
+        source: "zip archive members are read-only".into(),
+    }
+}
+
+#[async_trait]
+impl ObjectStore for ZipMemberStore {
+    async fn put_opts(
+        &self,
+        _location: &ObjectPath,
+        _payload: PutPayload,
+        _opts: PutOptions,
+    ) -> object_store::Result<PutResult> {
+        Err(read_only())
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        _location: &ObjectPath,
+        _opts: PutMultipartOptions,
+    ) -> object_store::Result<Box<dyn MultipartUpload>> {
+        Err(read_only())
+    }
+
+    async fn get_opts(
+        &self,
+        location: &ObjectPath,
+        options: GetOptions,
+    ) -> object_store::Result<GetResult> {
+        let Some((name, size)) = self.members.get(location) else {
+            return Err(object_store::Error::NotFound {
+                path: location.to_string(),
+                source: format!("'{}' has no member '{}'", self.archive, location).into(),
+            });
+        };
+        options.check_preconditions(&self.meta(location, *size))?;
+        if options.head {
+            return Ok(GetResult {
+                payload: GetResultPayload::Stream(futures::stream::empty().boxed()),
+                meta: self.meta(location, *size),
+                range: 0..*size,
+                attributes: Default::default(),
+            });
+        }
+
+        let data = self.read(location, name).await?;
+        let len = data.len() as u64;
+        let range = match options.range {
+            Some(range) => range.as_range(len).map_err(store_error)?,
+            None => 0..len,
+        };
+        let data = data.slice(range.start as usize..range.end as usize);
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(futures::stream::once(async { Ok(data) }).boxed()),
+            meta: self.meta(location, len),
+            range,
+            attributes: Default::default(),
+        })
+    }
+
+    fn delete_stream(
+        &self,
+        locations: BoxStream<'static, object_store::Result<ObjectPath>>,
+    ) -> BoxStream<'static, object_store::Result<ObjectPath>> {
+        locations.map(|_| Err(read_only())).boxed()
+    }
+
+    fn list(
+        &self,
+        prefix: Option<&ObjectPath>,
+    ) -> BoxStream<'static, object_store::Result<ObjectMeta>> {
+        let root = ObjectPath::default();
+        let prefix = prefix.unwrap_or(&root);
+        let objects: Vec<_> = self
+            .members
+            .iter()
+            .filter(|(location, _)| {
+                location
+                    .prefix_match(prefix)
+                    .is_some_and(|mut parts| parts.next().is_some())
+            })
+            .map(|(location, (_, size))| Ok(self.meta(location, *size)))
+            .collect();
+        futures::stream::iter(objects).boxed()
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&ObjectPath>,
+    ) -> object_store::Result<ListResult> {
+        let root = ObjectPath::default();
+        let prefix = prefix.unwrap_or(&root);
+        let mut common_prefixes = BTreeSet::new();
+        let mut objects = Vec::new();
+        for (location, (_, size)) in &self.members {
+            let Some(mut parts) = location.prefix_match(prefix) else {
+                continue;
+            };
+            let Some(first) = parts.next() else {
+                continue;
+            };
+            if parts.next().is_some() {
+                common_prefixes.insert(prefix.clone().join(first));
+            } else {
+                objects.push(self.meta(location, *size));
+            }
+        }
+        Ok(ListResult {
+            common_prefixes: common_prefixes.into_iter().collect(),
+            objects,
+        })
+    }
+
+    async fn copy_opts(
+        &self,
+        _from: &ObjectPath,
+        _to: &ObjectPath,
+        _options: CopyOptions,
+    ) -> object_store::Result<()> {
+        Err(read_only())
+    }
+}
@@ -0,0 +1,92 @@
+use super::zip::{read_member, ZipMemberStore};
+use crate::context::schema::AppResult;
+use datafusion::error::DataFusionError;
+use futures::StreamExt;
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, ObjectStoreExt};
+use std::io::Write;
+use std::path::PathBuf;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Write a zip archive holding `members` as `(name, content)` pairs.
+fn write_zip(name: &str, members: &[(&str, &str)]) -> AppResult<PathBuf> {
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_zip_{}_{}", std::process::id(), name));
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&path)?);
+    for (name, content) in members {
+        zip.start_file(*name, zip::write::SimpleFileOptions::default())?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish()?;
+    Ok(path)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_read_member_by_name() -> AppResult<()> {
+    let path = write_zip("member.zip", &[("a.csv", "id\n1\n"), ("b.csv", "id\n2\n")])?;
+    let archive = path.to_string_lossy().to_string();
+
+    let result = read_member(&archive, "b.csv", 10);
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(result?, b"id\n2\n");
+    Ok(())
+}
+
+#[test]
+fn test_read_member_over_the_limit_errors() -> AppResult<()> {
+    let path = write_zip("limit.zip", &[("a.csv", "id\n1\n2\n")])?;
+    let archive = path.to_string_lossy().to_string();
+
+    let result = read_member(&archive, "a.csv", 5);
+    let _ = std::fs::remove_file(&path);
+
+    let message = format!("{:?}", result.unwrap_err());
+    assert!(message.contains("Extract it"), "got: {}", message);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_member_store_reads_ranges_and_lists_members() -> AppResult<()> {
+    let path = write_zip(
+        "store.zip",
+        &[("a.csv", "id\n1\n"), ("dir/b.csv", "id\n22\n")],
+    )?;
+    let archive = path.to_string_lossy().to_string();
+    let names = ["a.csv".to_string(), "dir/b.csv".to_string()];
+
+    let store = ZipMemberStore::new(&archive, &names)?;
+    let location = ObjectPath::from("dir/b.csv");
+    let whole = store.get(&location).await.map_err(DataFusionError::from)?;
+    let whole = whole.bytes().await.map_err(DataFusionError::from)?;
+    let range = store
+        .get_range(&location, 3..5)
+        .await
+        .map_err(DataFusionError::from)?;
+    let listed: Vec<_> = store
+        .list(None)
+        .map(|meta| meta.map(|m| m.size))
+        .collect()
+        .await;
+    let missing = store.get(&ObjectPath::from("c.csv")).await;
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(&whole[..], b"id\n22\n");
+    assert_eq!(&range[..], b"22");
+    assert_eq!(
+        listed
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(DataFusionError::from)?,
+        [5, 6]
+    );
+    assert!(matches!(missing, Err(object_store::Error::NotFound { .. })));
+    Ok(())
+}
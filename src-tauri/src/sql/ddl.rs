@@ -0,0 +1,253 @@
+use crate::commands::query::Dialect;
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::sql::dialect::{decimal_parts, ColumnDefinition};
+use crate::sql::escape::quote_identifier;
+use crate::sql::generator::{resolve_export_specs, ExportColumnConfig, SqlType};
+use datafusion::arrow::array::{Array, AsArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+use serde::Deserialize;
+
+/// Options for the `CREATE TABLE` statement of a SQL export.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CreateTableOptions {
+    /// Source column names forming the primary key, in key order.
+    pub primary_key: Vec<String>,
+    pub if_not_exists: bool,
+    /// Prepend `DROP TABLE IF EXISTS`.
+    pub drop_if_exists: bool,
+}
+
+/// Generate a `CREATE TABLE` statement for the exported columns.
+///
+/// Column types are mapped from the Arrow schema per dialect; string and
+/// binary columns are sized from the longest value in `batches`. An
+/// `ExportColumnConfig.sql_type` override picks the type family (e.g. a
+/// number exported as TEXT becomes a VARCHAR), and is used verbatim when it
+/// spells out its own parameters such as `VARCHAR(64)` or `DECIMAL(10,2)`.
+pub fn generate_create_table(
+    schema: &Schema,
+    batches: &[RecordBatch],
+    table_name: &str,
+    db_dialect: &Dialect,
+    export_columns: Option<&[ExportColumnConfig]>,
+    options: &CreateTableOptions,
+) -> AppResult<String> {
+    let mut widths = ColumnWidths::default();
+    for batch in batches {
+        widths.update(batch);
+    }
+    generate_create_table_with_widths(
+        schema,
+        &widths,
+        table_name,
+        db_dialect,
+        export_columns,
+        options,
+    )
+}
+
+/// Like [`generate_create_table`], sizing string and binary columns from
+/// widths measured beforehand, e.g. while streaming the result.
+pub fn generate_create_table_with_widths(
+    schema: &Schema,
+    widths: &ColumnWidths,
+    table_name: &str,
+    db_dialect: &Dialect,
+    export_columns: Option<&[ExportColumnConfig]>,
+    options: &CreateTableOptions,
+) -> AppResult<String> {
+    let headers: Vec<String> = schema.fields().iter().map(|f| f.name().to_string()).collect();
+    if headers.is_empty() {
+        return Ok(String::new());
+    }
+    let export_specs = resolve_export_specs(&headers, export_columns)?;
+
+    let mut primary_key = Vec::with_capacity(options.primary_key.len());
+    for key_column in &options.primary_key {
+        let source_index = headers
+            .iter()
+            .position(|h| h == key_column)
+            .ok_or_else(|| AppError::BadRequest {
+                message: format!("Primary key column '{}' not found in data", key_column),
+            })?;
+        let spec = export_specs
+            .iter()
+            .find(|spec| spec.source_index == source_index)
+            .ok_or_else(|| AppError::BadRequest {
+                message: format!("Primary key column '{}' is not exported", key_column),
+            })?;
+        primary_key.push(spec.export_name.as_str());
+    }
+
+    let mut columns = Vec::with_capacity(export_specs.len());
+    for spec in &export_specs {
+        let field = schema.field(spec.source_index);
+        let column_type = match configured_type(export_columns, &spec.export_name) {
+            Some(sql_type) if sql_type.contains('(') => sql_type.to_string(),
+            _ => {
+                let max_len = widths.get(spec.source_index);
+                column_sql_type(
+                    field,
+                    spec.sql_type,
+                    spec.timezone.is_some(),
+                    max_len,
+                    db_dialect,
+                )
+            }
+        };
+
+        columns.push(ColumnDefinition {
+            name: quote_identifier(&spec.export_name, db_dialect),
+            sql_type: column_type,
+            not_null: !field.is_nullable() || primary_key.contains(&spec.export_name.as_str()),
+        });
+    }
+
+    let primary_key: Vec<String> = primary_key
+        .iter()
+        .map(|name| quote_identifier(name, db_dialect))
+        .collect();
+
+    let strategy = db_dialect.strategy();
+    let table = quote_identifier(table_name, db_dialect);
+    let mut ddl = String::new();
+    if options.drop_if_exists {
+        ddl.push_str(&strategy.drop_table_if_exists(&table));
+        ddl.push('\n');
+    }
+    ddl.push_str(&strategy.create_table(&table, options.if_not_exists, &columns, &primary_key));
+
+    Ok(ddl)
+}
+
+/// Dialect column type for `field`, within the family chosen by `sql_type`.
+/// `max_len` is the longest observed value in characters (bytes for binary).
+/// Zoned timestamps converted to a configured timezone are exported as
+/// wall-clock time and get a type without time zone.
+pub(crate) fn column_sql_type(
+    field: &Field,
+    sql_type: SqlType,
+    converts_timezone: bool,
+    max_len: usize,
+    db_dialect: &Dialect,
+) -> String {
+    let strategy = db_dialect.strategy();
+    let data_type = &match field.data_type() {
+        DataType::Timestamp(unit, Some(_)) if converts_timezone => DataType::Timestamp(*unit, None),
+        data_type => data_type.clone(),
+    };
+    match sql_type {
+        SqlType::Bool => strategy.bool_type(),
+        SqlType::Int if data_type.is_integer() => strategy.column_type(data_type, max_len),
+        SqlType::Int => strategy.integer_type(8, true),
+        SqlType::Float if decimal_parts(data_type).is_some() || data_type.is_floating() => {
+            strategy.column_type(data_type, max_len)
+        }
+        SqlType::Float => strategy.double_type(),
+        SqlType::Decimal if decimal_parts(data_type).is_some() => {
+            strategy.column_type(data_type, max_len)
+        }
+        SqlType::Decimal => strategy.decimal_type(38, 10),
+        SqlType::Date => strategy.date_type(),
+        SqlType::Timestamp if matches!(data_type, DataType::Timestamp(_, _)) => {
+            strategy.column_type(data_type, max_len)
+        }
+        SqlType::Timestamp => strategy.timestamp_type(&TimeUnit::Microsecond, false),
+        SqlType::Binary => strategy.binary_type(max_len),
+        SqlType::Text => strategy.varchar_type(max_len),
+        SqlType::Unknown => strategy.column_type(data_type, max_len),
+    }
+}
+
+/// Longest value of every column, gathered batch by batch so a `CREATE
+/// TABLE` can be sized without keeping the result in memory.
+#[derive(Clone, Debug, Default)]
+pub struct ColumnWidths {
+    max_lens: Vec<usize>,
+}
+
+impl ColumnWidths {
+    /// Whether any exported column is sized from its values, i.e. is a string
+    /// or binary column without a configured type such as `VARCHAR(64)`.
+    pub fn needed(schema: &Schema, export_columns: Option<&[ExportColumnConfig]>) -> bool {
+        let headers: Vec<String> = schema.fields().iter().map(|f| f.name().to_string()).collect();
+        let Ok(export_specs) = resolve_export_specs(&headers, export_columns) else {
+            return false;
+        };
+        export_specs.iter().any(|spec| {
+            let measured = matches!(
+                schema.field(spec.source_index).data_type(),
+                DataType::Utf8
+                    | DataType::LargeUtf8
+                    | DataType::Utf8View
+                    | DataType::Binary
+                    | DataType::LargeBinary
+                    | DataType::BinaryView
+            );
+            measured
+                && !configured_type(export_columns, &spec.export_name)
+                    .is_some_and(|sql_type| sql_type.contains('('))
+        })
+    }
+
+    pub fn update(&mut self, batch: &RecordBatch) {
+        if self.max_lens.len() < batch.num_columns() {
+            self.max_lens.resize(batch.num_columns(), 0);
+        }
+        for (max_len, array) in self.max_lens.iter_mut().zip(batch.columns()) {
+            *max_len = (*max_len).max(max_value_len(array.as_ref()));
+        }
+    }
+
+    /// Longest value of column `index`: characters for strings, bytes for
+    /// binary. Other columns are not measured and give 0, so a number
+    /// exported as TEXT gets the dialect's default VARCHAR length.
+    fn get(&self, index: usize) -> usize {
+        self.max_lens.get(index).copied().unwrap_or(0)
+    }
+}
+
+/// `ExportColumnConfig.sql_type` of the column exported as `export_name`.
+fn configured_type<'a>(
+    export_columns: Option<&'a [ExportColumnConfig]>,
+    export_name: &str,
+) -> Option<&'a str> {
+    export_columns.and_then(|cols| {
+        cols.iter()
+            .find(|c| c.export_column_name.trim() == export_name)
+            .map(|c| c.sql_type.trim())
+    })
+}
+
+/// Longest non-null value of `array`, see [`ColumnWidths::get`].
+fn max_value_len(array: &dyn Array) -> usize {
+    match array.data_type() {
+        DataType::Utf8 => max_chars(array.as_string::<i32>().iter().flatten()),
+        DataType::LargeUtf8 => max_chars(array.as_string::<i64>().iter().flatten()),
+        DataType::Utf8View => max_chars(array.as_string_view().iter().flatten()),
+        DataType::Binary => max_of(array.as_binary::<i32>().iter().flatten().map(<[u8]>::len)),
+        DataType::LargeBinary => max_of(array.as_binary::<i64>().iter().flatten().map(<[u8]>::len)),
+        DataType::BinaryView => max_of(array.as_binary_view().iter().flatten().map(<[u8]>::len)),
+        DataType::FixedSizeBinary(size) => (*size).max(0) as usize,
+        _ => 0,
+    }
+}
+
+/// Longest value in characters. A string has at most as many characters as
+/// bytes, so only values longer in bytes than the current maximum are counted.
+fn max_chars<'a>(values: impl Iterator<Item = &'a str>) -> usize {
+    values.fold(0, |max_len, value| {
+        if value.len() > max_len {
+            max_len.max(value.chars().count())
+        } else {
+            max_len
+        }
+    })
+}
+
+fn max_of(lengths: impl Iterator<Item = usize>) -> usize {
+    lengths.max().unwrap_or(0)
+}
@@ -0,0 +1,298 @@
+use super::ddl::{
+    generate_create_table, generate_create_table_with_widths, ColumnWidths, CreateTableOptions,
+};
+use super::generator::ExportColumnConfig;
+use crate::commands::query::Dialect;
+use datafusion::arrow::array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float64Array, Int64Array,
+    StringArray, TimestampMicrosecondArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// One row per Arrow type the DDL mapping cares about:
+///   id  name   price   active  born        seen_at               amount  raw
+///   1   alice  9.5     true    2022-01-08  <ts micros>           123.45  0x0102
+///   2   bo     NULL    false   NULL        NULL                  NULL    NULL
+fn make_typed_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("price", DataType::Float64, true),
+        Field::new("active", DataType::Boolean, true),
+        Field::new("born", DataType::Date32, true),
+        Field::new("seen_at", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+        Field::new("amount", DataType::Decimal128(10, 2), true),
+        Field::new("raw", DataType::Binary, true),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from(vec![1_i64, 2])),
+        Arc::new(StringArray::from(vec!["alice", "bo"])),
+        Arc::new(Float64Array::from(vec![Some(9.5), None])),
+        Arc::new(BooleanArray::from(vec![Some(true), Some(false)])),
+        Arc::new(Date32Array::from(vec![Some(19_000), None])),
+        Arc::new(TimestampMicrosecondArray::from(vec![Some(1_700_000_000_000_000), None])),
+        Arc::new(
+            Decimal128Array::from(vec![Some(12345_i128), None])
+                .with_precision_and_scale(10, 2)
+                .expect("decimal"),
+        ),
+        Arc::new(BinaryArray::from(vec![Some(&[1_u8, 2][..]), None])),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).expect("batch")
+}
+
+fn column(source: &str, export: &str, sql_type: &str) -> ExportColumnConfig {
+    ExportColumnConfig {
+        source_column_name: source.to_string(),
+        export_column_name: export.to_string(),
+        sql_type: sql_type.to_string(),
+        timezone: None,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_create_table_mysql_type_mapping() {
+    let batch = make_typed_batch();
+    let ddl = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "t",
+        &Dialect::MySQL,
+        None,
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+
+    assert_eq!(
+        ddl,
+        "CREATE TABLE `t` (\n\
+         \x20 `id` BIGINT NOT NULL,\n\
+         \x20 `name` VARCHAR(5),\n\
+         \x20 `price` DOUBLE,\n\
+         \x20 `active` TINYINT(1),\n\
+         \x20 `born` DATE,\n\
+         \x20 `seen_at` DATETIME(6),\n\
+         \x20 `amount` DECIMAL(10, 2),\n\
+         \x20 `raw` VARBINARY(2)\n\
+         );"
+    );
+}
+
+#[test]
+fn test_create_table_postgres_type_mapping() {
+    let batch = make_typed_batch();
+    let ddl = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "t",
+        &Dialect::PostgreSQL,
+        None,
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+
+    assert_eq!(
+        ddl,
+        "CREATE TABLE \"t\" (\n\
+         \x20 \"id\" BIGINT NOT NULL,\n\
+         \x20 \"name\" VARCHAR(5),\n\
+         \x20 \"price\" DOUBLE PRECISION,\n\
+         \x20 \"active\" BOOLEAN,\n\
+         \x20 \"born\" DATE,\n\
+         \x20 \"seen_at\" TIMESTAMP(6),\n\
+         \x20 \"amount\" NUMERIC(10, 2),\n\
+         \x20 \"raw\" BYTEA\n\
+         );"
+    );
+}
+
+#[test]
+fn test_create_table_honors_renames_and_type_overrides() {
+    let batch = make_typed_batch();
+    let config = vec![
+        column("id", "user_id", "TEXT"),
+        column("name", "full_name", "VARCHAR(64)"),
+        column("active", "is_active", "INT"),
+    ];
+    let ddl = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "t",
+        &Dialect::MySQL,
+        Some(&config),
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+
+    // TEXT on a number gets the default VARCHAR length; an explicit
+    // VARCHAR(64) is kept verbatim; INT on a boolean falls back to BIGINT.
+    assert_eq!(
+        ddl,
+        "CREATE TABLE `t` (\n\
+         \x20 `user_id` VARCHAR(255) NOT NULL,\n\
+         \x20 `full_name` VARCHAR(64),\n\
+         \x20 `is_active` BIGINT\n\
+         );"
+    );
+}
+
+#[test]
+fn test_create_table_primary_key_and_existence_clauses() {
+    let batch = make_typed_batch();
+    let config = vec![column("id", "user_id", "BIGINT"), column("name", "name", "TEXT")];
+    let options = CreateTableOptions {
+        primary_key: vec!["id".to_string(), "name".to_string()],
+        if_not_exists: true,
+        drop_if_exists: true,
+    };
+    let ddl = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "users",
+        &Dialect::PostgreSQL,
+        Some(&config),
+        &options,
+    )
+    .expect("ok");
+
+    assert_eq!(
+        ddl,
+        "DROP TABLE IF EXISTS \"users\";\n\
+         CREATE TABLE IF NOT EXISTS \"users\" (\n\
+         \x20 \"user_id\" BIGINT NOT NULL,\n\
+         \x20 \"name\" VARCHAR(5) NOT NULL,\n\
+         \x20 PRIMARY KEY (\"user_id\", \"name\")\n\
+         );"
+    );
+}
+
+#[test]
+fn test_create_table_primary_key_must_be_exported() {
+    let batch = make_typed_batch();
+    let config = vec![column("name", "name", "TEXT")];
+    let options = CreateTableOptions {
+        primary_key: vec!["id".to_string()],
+        ..Default::default()
+    };
+    let result = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "t",
+        &Dialect::MySQL,
+        Some(&config),
+        &options,
+    );
+    let msg = format!("{:?}", result.unwrap_err());
+    assert!(msg.contains("not exported"), "got: {}", msg);
+}
+
+#[test]
+fn test_create_table_without_rows_uses_default_varchar() {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int32, true),
+        Field::new("name", DataType::Utf8, true),
+    ]);
+    let ddl = generate_create_table(
+        &schema,
+        &[],
+        "t",
+        &Dialect::MySQL,
+        None,
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+
+    assert_eq!(ddl, "CREATE TABLE `t` (\n  `id` INT,\n  `name` VARCHAR(255)\n);");
+}
+
+#[test]
+fn test_create_table_long_text_becomes_longtext() {
+    let long = "x".repeat(20_000);
+    let batch = RecordBatch::try_from_iter_with_nullable(vec![(
+        "body",
+        Arc::new(StringArray::from(vec![long.as_str()])) as ArrayRef,
+        true,
+    )])
+    .expect("batch");
+    let ddl = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "t",
+        &Dialect::MySQL,
+        None,
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+
+    assert_eq!(ddl, "CREATE TABLE `t` (\n  `body` LONGTEXT\n);");
+}
+
+#[test]
+fn test_create_table_counts_characters_not_bytes() {
+    let batch = RecordBatch::try_from_iter_with_nullable(vec![(
+        "name",
+        Arc::new(StringArray::from(vec!["日本語"])) as ArrayRef,
+        true,
+    )])
+    .expect("batch");
+    let ddl = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "t",
+        &Dialect::PostgreSQL,
+        None,
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+
+    assert_eq!(ddl, "CREATE TABLE \"t\" (\n  \"name\" VARCHAR(3)\n);");
+}
+
+#[test]
+fn test_create_table_from_streamed_widths_matches_collected() {
+    let batch = make_typed_batch();
+    let mut widths = ColumnWidths::default();
+    widths.update(&batch.slice(0, 1));
+    widths.update(&batch.slice(1, 1));
+    let streamed = generate_create_table_with_widths(
+        &batch.schema(),
+        &widths,
+        "t",
+        &Dialect::MySQL,
+        None,
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+    let collected = generate_create_table(
+        &batch.schema(),
+        &[batch],
+        "t",
+        &Dialect::MySQL,
+        None,
+        &CreateTableOptions::default(),
+    )
+    .expect("ok");
+    assert_eq!(streamed, collected);
+}
+
+#[test]
+fn test_column_widths_needed_only_for_unsized_text() {
+    let schema = make_typed_batch().schema();
+    assert!(ColumnWidths::needed(&schema, None));
+
+    let sized = vec![column("id", "id", "BIGINT"), column("name", "name", "VARCHAR(64)")];
+    assert!(!ColumnWidths::needed(&schema, Some(&sized)));
+
+    let unsized_text = vec![column("id", "id", "BIGINT"), column("name", "name", "TEXT")];
+    assert!(ColumnWidths::needed(&schema, Some(&unsized_text)));
+}
@@ -0,0 +1,1011 @@
+use crate::commands::query::Dialect;
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::sql::escape::{
+    quote_clickhouse_string, quote_concat_string, quote_mysql_string, quote_postgres_string, to_hex,
+};
+use datafusion::arrow::datatypes::{DataType, TimeUnit};
+
+/// Longest VARCHAR emitted for MySQL: 65,535 bytes per row / 4 bytes per
+/// utf8mb4 character. Longer columns become LONGTEXT.
+const MYSQL_MAX_VARCHAR: usize = 16_383;
+/// Longest VARBINARY emitted for MySQL; longer columns become LONGBLOB.
+const MYSQL_MAX_VARBINARY: usize = 65_535;
+/// Longest VARCHAR PostgreSQL accepts; longer columns become TEXT.
+const POSTGRES_MAX_VARCHAR: usize = 10_485_760;
+/// Longest sized NVARCHAR in SQL Server; longer columns become NVARCHAR(MAX).
+const SQLSERVER_MAX_NVARCHAR: usize = 4_000;
+/// Longest sized VARBINARY in SQL Server; longer columns become VARBINARY(MAX).
+const SQLSERVER_MAX_VARBINARY: usize = 8_000;
+/// Most rows SQL Server accepts in one `VALUES` list.
+const SQLSERVER_MAX_ROWS_PER_INSERT: usize = 1_000;
+/// Longest VARCHAR2 with the default `MAX_STRING_SIZE = STANDARD`; longer
+/// columns become CLOB.
+const ORACLE_MAX_VARCHAR2: usize = 4_000;
+/// Longest RAW with `MAX_STRING_SIZE = STANDARD`; longer columns become BLOB.
+const ORACLE_MAX_RAW: usize = 2_000;
+/// VARCHAR length used when no rows were observed.
+const DEFAULT_VARCHAR: usize = 255;
+
+/// One column of a `CREATE TABLE` statement, with the name already quoted.
+pub struct ColumnDefinition {
+    pub name: String,
+    pub sql_type: String,
+    pub not_null: bool,
+}
+
+/// Per-database rules for the SQL export: literal and identifier syntax,
+/// statement shapes and the Arrow to column type mapping.
+///
+/// Identifiers and literals passed to the statement builders are already
+/// quoted with the same dialect.
+pub trait SqlDialect: Sync {
+    /// Display name used in error messages.
+    fn name(&self) -> &'static str;
+
+    fn quote_identifier(&self, name: &str) -> String;
+
+    /// A string literal the database reads back as exactly `value`.
+    fn quote_string(&self, value: &str) -> String;
+
+    fn quote_binary(&self, value: &[u8]) -> String;
+
+    fn bool_literal(&self, value: bool) -> &'static str {
+        if value {
+            "true"
+        } else {
+            "false"
+        }
+    }
+
+    /// Literal for a `YYYY-MM-DD` date.
+    fn date_literal(&self, date: &str) -> String {
+        self.quote_string(date)
+    }
+
+    /// Literal for a `YYYY-MM-DD HH:MM:SS[.fffffffff]` wall-clock time and,
+    /// for zoned values, its `+hh:mm` UTC offset.
+    fn timestamp_literal(&self, timestamp: &str, offset: Option<&str>) -> String {
+        self.quote_string(&format!("{}{}", timestamp, offset.unwrap_or("")))
+    }
+
+    /// Upper bound on rows per INSERT, applied on top of the requested size.
+    fn max_rows_per_insert(&self) -> Option<usize> {
+        None
+    }
+
+    /// One INSERT of `rows` (each a list of literals), without the trailing
+    /// semicolon.
+    fn insert_statement(&self, table: &str, columns: &str, rows: &[Vec<String>]) -> String {
+        multi_row_insert(table, columns, rows)
+    }
+
+    /// Bytes of an [`insert_statement`](Self::insert_statement) besides its
+    /// rows.
+    fn insert_statement_overhead(&self, table: &str, columns: &str) -> usize {
+        "INSERT INTO  () VALUES\n".len() + table.len() + columns.len()
+    }
+
+    /// Bytes each row adds to an [`insert_statement`](Self::insert_statement)
+    /// besides its literals and the `, ` between them.
+    fn insert_row_overhead(&self, _table: &str, _columns: &str) -> usize {
+        "(),\n".len()
+    }
+
+    /// Clause appended to each INSERT of an UPSERT export. `key_names` is
+    /// never empty; `update_names` may be.
+    fn upsert_clause(&self, _key_names: &[String], _update_names: &[String]) -> AppResult<String> {
+        Err(AppError::BadRequest {
+            message: format!("UPSERT statements are not supported for {}", self.name()),
+        })
+    }
+
+    /// Whether `(a, b) IN ((1, 2), (3, 4))` is accepted.
+    fn supports_row_value_in(&self) -> bool {
+        true
+    }
+
+    /// Statement opening a transaction, `None` where one starts implicitly.
+    fn begin_transaction(&self) -> AppResult<Option<&'static str>> {
+        Ok(Some("BEGIN;"))
+    }
+
+    /// Statements turning foreign key checks off and back on for the session.
+    fn foreign_key_checks(&self) -> AppResult<(&'static str, &'static str)> {
+        Err(AppError::BadRequest {
+            message: format!(
+                "Disabling foreign key checks is not supported for {}",
+                self.name()
+            ),
+        })
+    }
+
+    fn bool_type(&self) -> String;
+
+    /// Integer column of `bytes` width (1, 2, 4 or 8).
+    fn integer_type(&self, bytes: u8, signed: bool) -> String;
+
+    fn float_type(&self) -> String;
+
+    fn double_type(&self) -> String;
+
+    fn decimal_type(&self, precision: u8, scale: u8) -> String;
+
+    fn date_type(&self) -> String {
+        "DATE".to_string()
+    }
+
+    fn time_type(&self) -> String {
+        "TIME".to_string()
+    }
+
+    fn timestamp_type(&self, unit: &TimeUnit, with_time_zone: bool) -> String;
+
+    /// `max_len` is the longest observed value in bytes.
+    fn binary_type(&self, max_len: usize) -> String;
+
+    /// `max_len` is the longest observed value in characters, 0 if unknown.
+    fn varchar_type(&self, max_len: usize) -> String;
+
+    /// Unbounded text, used for nested and other types exported as their
+    /// display text.
+    fn text_type(&self) -> String {
+        "TEXT".to_string()
+    }
+
+    /// Column type for an Arrow type.
+    fn column_type(&self, data_type: &DataType, max_len: usize) -> String {
+        if let Some((precision, scale)) = decimal_parts(data_type) {
+            return self.decimal_type(precision, scale);
+        }
+        match data_type {
+            DataType::Boolean => self.bool_type(),
+            DataType::Int8 => self.integer_type(1, true),
+            DataType::Int16 => self.integer_type(2, true),
+            DataType::Int32 => self.integer_type(4, true),
+            DataType::Int64 => self.integer_type(8, true),
+            DataType::UInt8 => self.integer_type(1, false),
+            DataType::UInt16 => self.integer_type(2, false),
+            DataType::UInt32 => self.integer_type(4, false),
+            DataType::UInt64 => self.integer_type(8, false),
+            DataType::Float16 | DataType::Float32 => self.float_type(),
+            DataType::Float64 => self.double_type(),
+            DataType::Date32 | DataType::Date64 => self.date_type(),
+            DataType::Time32(_) | DataType::Time64(_) => self.time_type(),
+            DataType::Timestamp(unit, timezone) => self.timestamp_type(unit, timezone.is_some()),
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => self.binary_type(max_len),
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+                self.varchar_type(max_len)
+            }
+            _ => self.text_type(),
+        }
+    }
+
+    /// The full `CREATE TABLE` statement.
+    fn create_table(
+        &self,
+        table: &str,
+        if_not_exists: bool,
+        columns: &[ColumnDefinition],
+        primary_key: &[String],
+    ) -> String {
+        let prefix = if if_not_exists { "IF NOT EXISTS " } else { "" };
+        standard_create_table(prefix, table, columns, primary_key)
+    }
+
+    fn drop_table_if_exists(&self, table: &str) -> String {
+        format!("DROP TABLE IF EXISTS {};", table)
+    }
+}
+
+impl Dialect {
+    /// The SQL generation rules for this dialect.
+    pub fn strategy(&self) -> &'static dyn SqlDialect {
+        match self {
+            Dialect::MySQL => &MySqlDialect {
+                backslash_escapes: true,
+            },
+            Dialect::MySQLNoBackslashEscapes => &MySqlDialect {
+                backslash_escapes: false,
+            },
+            Dialect::PostgreSQL => &PostgresDialect,
+            Dialect::SQLite => &SqliteDialect,
+            Dialect::SqlServer => &SqlServerDialect,
+            Dialect::Oracle => &OracleDialect,
+            Dialect::ClickHouse => &ClickHouseDialect,
+        }
+    }
+}
+
+/// `(precision, scale)` of a decimal type, with negative scales clamped to 0.
+pub(crate) fn decimal_parts(data_type: &DataType) -> Option<(u8, u8)> {
+    match data_type {
+        DataType::Decimal32(precision, scale)
+        | DataType::Decimal64(precision, scale)
+        | DataType::Decimal128(precision, scale)
+        | DataType::Decimal256(precision, scale) => Some((*precision, (*scale).max(0) as u8)),
+        _ => None,
+    }
+}
+
+fn fractional_digits(unit: &TimeUnit) -> u8 {
+    match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 3,
+        TimeUnit::Microsecond => 6,
+        TimeUnit::Nanosecond => 9,
+    }
+}
+
+fn varchar_len(max_len: usize) -> usize {
+    if max_len == 0 {
+        DEFAULT_VARCHAR
+    } else {
+        max_len
+    }
+}
+
+fn multi_row_insert(table: &str, columns: &str, rows: &[Vec<String>]) -> String {
+    let values = rows
+        .iter()
+        .map(|row| format!("({})", row.join(", ")))
+        .collect::<Vec<String>>()
+        .join(",\n");
+    format!("INSERT INTO {} ({}) VALUES\n{}", table, columns, values)
+}
+
+fn standard_create_table(
+    prefix: &str,
+    table: &str,
+    columns: &[ColumnDefinition],
+    primary_key: &[String],
+) -> String {
+    let mut definitions: Vec<String> = columns
+        .iter()
+        .map(|column| {
+            format!(
+                "  {} {}{}",
+                column.name,
+                column.sql_type,
+                if column.not_null { " NOT NULL" } else { "" }
+            )
+        })
+        .collect();
+    if !primary_key.is_empty() {
+        definitions.push(format!("  PRIMARY KEY ({})", primary_key.join(", ")));
+    }
+    format!(
+        "CREATE TABLE {}{} (\n{}\n);",
+        prefix,
+        table,
+        definitions.join(",\n")
+    )
+}
+
+// ─── MySQL ────────────────────────────────────────────────────────────
+
+pub struct MySqlDialect {
+    /// False under the `NO_BACKSLASH_ESCAPES` SQL mode.
+    pub backslash_escapes: bool,
+}
+
+impl SqlDialect for MySqlDialect {
+    fn name(&self) -> &'static str {
+        "MySQL"
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        quote_mysql_string(value, self.backslash_escapes)
+    }
+
+    fn quote_binary(&self, value: &[u8]) -> String {
+        format!("X'{}'", to_hex(value))
+    }
+
+    fn date_literal(&self, date: &str) -> String {
+        format!("DATE '{}'", date)
+    }
+
+    /// Offsets in datetime literals need MySQL 8.0.19+.
+    fn timestamp_literal(&self, timestamp: &str, offset: Option<&str>) -> String {
+        format!("TIMESTAMP '{}{}'", timestamp, offset.unwrap_or(""))
+    }
+
+    /// `ON DUPLICATE KEY UPDATE` fires on any unique key of the table. A
+    /// no-op assignment keeps existing rows untouched when there is nothing
+    /// to update.
+    fn upsert_clause(&self, key_names: &[String], update_names: &[String]) -> AppResult<String> {
+        let assignments = if update_names.is_empty() {
+            vec![format!("{} = {}", key_names[0], key_names[0])]
+        } else {
+            update_names
+                .iter()
+                .map(|name| format!("{} = VALUES({})", name, name))
+                .collect()
+        };
+        Ok(format!("\nON DUPLICATE KEY UPDATE {}", assignments.join(", ")))
+    }
+
+    fn begin_transaction(&self) -> AppResult<Option<&'static str>> {
+        Ok(Some("START TRANSACTION;"))
+    }
+
+    fn foreign_key_checks(&self) -> AppResult<(&'static str, &'static str)> {
+        Ok(("SET FOREIGN_KEY_CHECKS=0;", "SET FOREIGN_KEY_CHECKS=1;"))
+    }
+
+    fn bool_type(&self) -> String {
+        "TINYINT(1)".to_string()
+    }
+
+    fn integer_type(&self, bytes: u8, signed: bool) -> String {
+        let name = match bytes {
+            1 => "TINYINT",
+            2 => "SMALLINT",
+            4 => "INT",
+            _ => "BIGINT",
+        };
+        if signed {
+            name.to_string()
+        } else {
+            format!("{} UNSIGNED", name)
+        }
+    }
+
+    fn float_type(&self) -> String {
+        "FLOAT".to_string()
+    }
+
+    fn double_type(&self) -> String {
+        "DOUBLE".to_string()
+    }
+
+    /// MySQL caps DECIMAL at 65 digits with a scale of at most 30.
+    fn decimal_type(&self, precision: u8, scale: u8) -> String {
+        let precision = precision.min(65);
+        format!("DECIMAL({}, {})", precision, scale.min(30).min(precision))
+    }
+
+    fn timestamp_type(&self, unit: &TimeUnit, _with_time_zone: bool) -> String {
+        match fractional_digits(unit).min(6) {
+            0 => "DATETIME".to_string(),
+            precision => format!("DATETIME({})", precision),
+        }
+    }
+
+    fn binary_type(&self, max_len: usize) -> String {
+        if max_len > MYSQL_MAX_VARBINARY {
+            "LONGBLOB".to_string()
+        } else {
+            format!("VARBINARY({})", max_len.max(1))
+        }
+    }
+
+    fn varchar_type(&self, max_len: usize) -> String {
+        match varchar_len(max_len) {
+            len if len > MYSQL_MAX_VARCHAR => "LONGTEXT".to_string(),
+            len => format!("VARCHAR({})", len),
+        }
+    }
+}
+
+// ─── PostgreSQL ───────────────────────────────────────────────────────
+
+pub struct PostgresDialect;
+
+impl SqlDialect for PostgresDialect {
+    fn name(&self) -> &'static str {
+        "PostgreSQL"
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        quote_postgres_string(value)
+    }
+
+    /// `decode` reads the hex the same way whatever
+    /// `standard_conforming_strings` is set to.
+    fn quote_binary(&self, value: &[u8]) -> String {
+        format!("decode('{}', 'hex')", to_hex(value))
+    }
+
+    fn date_literal(&self, date: &str) -> String {
+        format!("DATE '{}'", date)
+    }
+
+    fn timestamp_literal(&self, timestamp: &str, offset: Option<&str>) -> String {
+        match offset {
+            Some(offset) => format!("TIMESTAMP WITH TIME ZONE '{}{}'", timestamp, offset),
+            None => format!("TIMESTAMP '{}'", timestamp),
+        }
+    }
+
+    fn upsert_clause(&self, key_names: &[String], update_names: &[String]) -> AppResult<String> {
+        Ok(on_conflict_clause(key_names, update_names))
+    }
+
+    /// Needs superuser (or, from PostgreSQL 15, a granted SET privilege);
+    /// replica mode skips the triggers that enforce foreign keys.
+    fn foreign_key_checks(&self) -> AppResult<(&'static str, &'static str)> {
+        Ok((
+            "SET session_replication_role = replica;",
+            "SET session_replication_role = DEFAULT;",
+        ))
+    }
+
+    fn bool_type(&self) -> String {
+        "BOOLEAN".to_string()
+    }
+
+    fn integer_type(&self, bytes: u8, signed: bool) -> String {
+        match (bytes, signed) {
+            (1 | 2, true) | (1, false) => "SMALLINT",
+            (4, true) | (2, false) => "INTEGER",
+            (_, true) | (4, false) => "BIGINT",
+            _ => "NUMERIC(20, 0)",
+        }
+        .to_string()
+    }
+
+    fn float_type(&self) -> String {
+        "REAL".to_string()
+    }
+
+    fn double_type(&self) -> String {
+        "DOUBLE PRECISION".to_string()
+    }
+
+    fn decimal_type(&self, precision: u8, scale: u8) -> String {
+        format!("NUMERIC({}, {})", precision, scale)
+    }
+
+    fn timestamp_type(&self, unit: &TimeUnit, with_time_zone: bool) -> String {
+        let precision = fractional_digits(unit).min(6);
+        if with_time_zone {
+            format!("TIMESTAMP({}) WITH TIME ZONE", precision)
+        } else {
+            format!("TIMESTAMP({})", precision)
+        }
+    }
+
+    fn binary_type(&self, _max_len: usize) -> String {
+        "BYTEA".to_string()
+    }
+
+    fn varchar_type(&self, max_len: usize) -> String {
+        match varchar_len(max_len) {
+            len if len > POSTGRES_MAX_VARCHAR => "TEXT".to_string(),
+            len => format!("VARCHAR({})", len),
+        }
+    }
+}
+
+/// `ON CONFLICT (...) DO UPDATE` on exactly the key columns, or `DO NOTHING`
+/// when every exported column is part of the key.
+fn on_conflict_clause(key_names: &[String], update_names: &[String]) -> String {
+    let action = if update_names.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        let assignments = update_names
+            .iter()
+            .map(|name| format!("{} = EXCLUDED.{}", name, name))
+            .collect::<Vec<String>>();
+        format!("DO UPDATE SET {}", assignments.join(", "))
+    };
+    format!("\nON CONFLICT ({}) {}", key_names.join(", "), action)
+}
+
+// ─── SQLite ───────────────────────────────────────────────────────────
+
+/// SQLite stores values by affinity, so column types only pick one of
+/// INTEGER, REAL, NUMERIC, TEXT and BLOB.
+pub struct SqliteDialect;
+
+impl SqlDialect for SqliteDialect {
+    fn name(&self) -> &'static str {
+        "SQLite"
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        quote_concat_string(value, "", |c| c != '\0', |code| format!("char({})", code), " || ")
+    }
+
+    fn quote_binary(&self, value: &[u8]) -> String {
+        format!("X'{}'", to_hex(value))
+    }
+
+    fn bool_literal(&self, value: bool) -> &'static str {
+        if value {
+            "1"
+        } else {
+            "0"
+        }
+    }
+
+    /// SQLite 3.24+ shares PostgreSQL's upsert syntax.
+    fn upsert_clause(&self, key_names: &[String], update_names: &[String]) -> AppResult<String> {
+        Ok(on_conflict_clause(key_names, update_names))
+    }
+
+    /// Row values only compare against `IN (VALUES ...)` or subqueries.
+    fn supports_row_value_in(&self) -> bool {
+        false
+    }
+
+    /// The pragma is a no-op inside a transaction, so the script issues it
+    /// before `BEGIN`.
+    fn foreign_key_checks(&self) -> AppResult<(&'static str, &'static str)> {
+        Ok(("PRAGMA foreign_keys = OFF;", "PRAGMA foreign_keys = ON;"))
+    }
+
+    fn bool_type(&self) -> String {
+        "INTEGER".to_string()
+    }
+
+    fn integer_type(&self, _bytes: u8, _signed: bool) -> String {
+        "INTEGER".to_string()
+    }
+
+    fn float_type(&self) -> String {
+        "REAL".to_string()
+    }
+
+    fn double_type(&self) -> String {
+        "REAL".to_string()
+    }
+
+    fn decimal_type(&self, _precision: u8, _scale: u8) -> String {
+        "NUMERIC".to_string()
+    }
+
+    /// Dates and times are stored as ISO text, which SQLite's date functions read.
+    fn date_type(&self) -> String {
+        "TEXT".to_string()
+    }
+
+    fn time_type(&self) -> String {
+        "TEXT".to_string()
+    }
+
+    fn timestamp_type(&self, _unit: &TimeUnit, _with_time_zone: bool) -> String {
+        "TEXT".to_string()
+    }
+
+    fn binary_type(&self, _max_len: usize) -> String {
+        "BLOB".to_string()
+    }
+
+    fn varchar_type(&self, _max_len: usize) -> String {
+        "TEXT".to_string()
+    }
+}
+
+// ─── SQL Server ───────────────────────────────────────────────────────
+
+pub struct SqlServerDialect;
+
+impl SqlDialect for SqlServerDialect {
+    fn name(&self) -> &'static str {
+        "SQL Server"
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("[{}]", name.replace(']', "]]"))
+    }
+
+    /// `N'...'` keeps non-Latin text intact in NVARCHAR columns.
+    fn quote_string(&self, value: &str) -> String {
+        quote_concat_string(
+            value,
+            "N",
+            |c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'),
+            |code| format!("NCHAR({})", code),
+            " + ",
+        )
+    }
+
+    fn quote_binary(&self, value: &[u8]) -> String {
+        format!("0x{}", to_hex(value))
+    }
+
+    fn bool_literal(&self, value: bool) -> &'static str {
+        if value {
+            "1"
+        } else {
+            "0"
+        }
+    }
+
+    fn date_literal(&self, date: &str) -> String {
+        format!("'{}'", date)
+    }
+
+    /// ISO 8601 with a `T` reads the same under every DATEFORMAT setting;
+    /// DATETIME2 keeps at most 7 fractional digits.
+    fn timestamp_literal(&self, timestamp: &str, offset: Option<&str>) -> String {
+        let timestamp = timestamp.replacen(' ', "T", 1);
+        let timestamp = match timestamp.split_once('.') {
+            Some((seconds, fraction)) if fraction.len() > 7 => {
+                format!("{}.{}", seconds, &fraction[..7])
+            }
+            _ => timestamp,
+        };
+        format!("'{}{}'", timestamp, offset.unwrap_or(""))
+    }
+
+    fn max_rows_per_insert(&self) -> Option<usize> {
+        Some(SQLSERVER_MAX_ROWS_PER_INSERT)
+    }
+
+    fn supports_row_value_in(&self) -> bool {
+        false
+    }
+
+    fn begin_transaction(&self) -> AppResult<Option<&'static str>> {
+        Ok(Some("BEGIN TRANSACTION;"))
+    }
+
+    fn bool_type(&self) -> String {
+        "BIT".to_string()
+    }
+
+    /// TINYINT is unsigned in SQL Server, so signed bytes widen to SMALLINT.
+    fn integer_type(&self, bytes: u8, signed: bool) -> String {
+        match (bytes, signed) {
+            (1, false) => "TINYINT",
+            (1 | 2, true) => "SMALLINT",
+            (4, true) | (2, false) => "INT",
+            (_, true) | (4, false) => "BIGINT",
+            _ => "DECIMAL(20, 0)",
+        }
+        .to_string()
+    }
+
+    fn float_type(&self) -> String {
+        "REAL".to_string()
+    }
+
+    fn double_type(&self) -> String {
+        "FLOAT".to_string()
+    }
+
+    fn decimal_type(&self, precision: u8, scale: u8) -> String {
+        let precision = precision.min(38);
+        format!("DECIMAL({}, {})", precision, scale.min(precision))
+    }
+
+    fn timestamp_type(&self, unit: &TimeUnit, with_time_zone: bool) -> String {
+        let precision = fractional_digits(unit).min(7);
+        if with_time_zone {
+            format!("DATETIMEOFFSET({})", precision)
+        } else {
+            format!("DATETIME2({})", precision)
+        }
+    }
+
+    fn binary_type(&self, max_len: usize) -> String {
+        if max_len > SQLSERVER_MAX_VARBINARY {
+            "VARBINARY(MAX)".to_string()
+        } else {
+            format!("VARBINARY({})", max_len.max(1))
+        }
+    }
+
+    fn varchar_type(&self, max_len: usize) -> String {
+        match varchar_len(max_len) {
+            len if len > SQLSERVER_MAX_NVARCHAR => "NVARCHAR(MAX)".to_string(),
+            len => format!("NVARCHAR({})", len),
+        }
+    }
+
+    fn text_type(&self) -> String {
+        "NVARCHAR(MAX)".to_string()
+    }
+
+    /// SQL Server has no `CREATE TABLE IF NOT EXISTS`; the statement is
+    /// guarded with `OBJECT_ID` instead.
+    fn create_table(
+        &self,
+        table: &str,
+        if_not_exists: bool,
+        columns: &[ColumnDefinition],
+        primary_key: &[String],
+    ) -> String {
+        let create = standard_create_table("", table, columns, primary_key);
+        if if_not_exists {
+            format!(
+                "IF OBJECT_ID({}, N'U') IS NULL\n{}",
+                self.quote_string(table),
+                create
+            )
+        } else {
+            create
+        }
+    }
+}
+
+// ─── Oracle ───────────────────────────────────────────────────────────
+
+/// Oracle Database 23ai; older releases lack `IF [NOT] EXISTS` on tables.
+pub struct OracleDialect;
+
+impl SqlDialect for OracleDialect {
+    fn name(&self) -> &'static str {
+        "Oracle"
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        quote_concat_string(
+            value,
+            "",
+            |c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'),
+            |code| format!("CHR({})", code),
+            " || ",
+        )
+    }
+
+    fn quote_binary(&self, value: &[u8]) -> String {
+        format!("HEXTORAW('{}')", to_hex(value))
+    }
+
+    fn bool_literal(&self, value: bool) -> &'static str {
+        if value {
+            "1"
+        } else {
+            "0"
+        }
+    }
+
+    fn date_literal(&self, date: &str) -> String {
+        format!("DATE '{}'", date)
+    }
+
+    /// `TIMESTAMP 'YYYY-MM-DD HH24:MI:SS.FF [+TZH:TZM]'`.
+    fn timestamp_literal(&self, timestamp: &str, offset: Option<&str>) -> String {
+        match offset {
+            Some(offset) => format!("TIMESTAMP '{} {}'", timestamp, offset),
+            None => format!("TIMESTAMP '{}'", timestamp),
+        }
+    }
+
+    /// Oracle has no multi-row `VALUES`; several rows go through `INSERT ALL`.
+    fn insert_statement(&self, table: &str, columns: &str, rows: &[Vec<String>]) -> String {
+        if rows.len() <= 1 {
+            return multi_row_insert(table, columns, rows);
+        }
+        let mut statement = String::from("INSERT ALL\n");
+        for row in rows {
+            statement.push_str(&format!(
+                "  INTO {} ({}) VALUES ({})\n",
+                table,
+                columns,
+                row.join(", ")
+            ));
+        }
+        statement.push_str("SELECT 1 FROM DUAL");
+        statement
+    }
+
+    /// Sized for `INSERT ALL`, the larger of the two forms.
+    fn insert_statement_overhead(&self, _table: &str, _columns: &str) -> usize {
+        "INSERT ALL\nSELECT 1 FROM DUAL".len()
+    }
+
+    /// Every row of an `INSERT ALL` repeats the table and column list.
+    fn insert_row_overhead(&self, table: &str, columns: &str) -> usize {
+        "  INTO  () VALUES ()\n".len() + table.len() + columns.len()
+    }
+
+    fn begin_transaction(&self) -> AppResult<Option<&'static str>> {
+        Ok(None)
+    }
+
+    fn bool_type(&self) -> String {
+        "NUMBER(1)".to_string()
+    }
+
+    fn integer_type(&self, bytes: u8, signed: bool) -> String {
+        let digits = match (bytes, signed) {
+            (1, _) => 3,
+            (2, _) => 5,
+            (4, _) => 10,
+            (_, true) => 19,
+            (_, false) => 20,
+        };
+        format!("NUMBER({})", digits)
+    }
+
+    fn float_type(&self) -> String {
+        "BINARY_FLOAT".to_string()
+    }
+
+    fn double_type(&self) -> String {
+        "BINARY_DOUBLE".to_string()
+    }
+
+    fn decimal_type(&self, precision: u8, scale: u8) -> String {
+        let precision = precision.min(38);
+        format!("NUMBER({}, {})", precision, scale.min(precision))
+    }
+
+    /// Oracle has no time-of-day type; times are kept as their text.
+    fn time_type(&self) -> String {
+        "VARCHAR2(18 CHAR)".to_string()
+    }
+
+    fn timestamp_type(&self, unit: &TimeUnit, with_time_zone: bool) -> String {
+        let precision = fractional_digits(unit);
+        if with_time_zone {
+            format!("TIMESTAMP({}) WITH TIME ZONE", precision)
+        } else {
+            format!("TIMESTAMP({})", precision)
+        }
+    }
+
+    fn binary_type(&self, max_len: usize) -> String {
+        if max_len > ORACLE_MAX_RAW {
+            "BLOB".to_string()
+        } else {
+            format!("RAW({})", max_len.max(1))
+        }
+    }
+
+    fn varchar_type(&self, max_len: usize) -> String {
+        match varchar_len(max_len) {
+            len if len > ORACLE_MAX_VARCHAR2 => "CLOB".to_string(),
+            len => format!("VARCHAR2({} CHAR)", len),
+        }
+    }
+
+    fn text_type(&self) -> String {
+        "CLOB".to_string()
+    }
+}
+
+// ─── ClickHouse ───────────────────────────────────────────────────────
+
+pub struct ClickHouseDialect;
+
+impl SqlDialect for ClickHouseDialect {
+    fn name(&self) -> &'static str {
+        "ClickHouse"
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("`{}`", name.replace('\\', "\\\\").replace('`', "\\`"))
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        quote_clickhouse_string(value)
+    }
+
+    fn quote_binary(&self, value: &[u8]) -> String {
+        format!("unhex('{}')", to_hex(value))
+    }
+
+    /// DateTime64 only parses the basic `YYYY-MM-DD hh:mm:ss[.fff]` form
+    /// from a plain literal; zoned values go through best-effort parsing
+    /// with their fractional precision.
+    fn timestamp_literal(&self, timestamp: &str, offset: Option<&str>) -> String {
+        let Some(offset) = offset else {
+            return format!("'{}'", timestamp);
+        };
+        let precision = timestamp
+            .split_once('.')
+            .map(|(_, fraction)| fraction.len())
+            .unwrap_or(0);
+        format!(
+            "parseDateTime64BestEffort('{}{}', {})",
+            timestamp, offset, precision
+        )
+    }
+
+    fn begin_transaction(&self) -> AppResult<Option<&'static str>> {
+        Err(AppError::BadRequest {
+            message: "Transactions are not supported for ClickHouse".to_string(),
+        })
+    }
+
+    fn bool_type(&self) -> String {
+        "Bool".to_string()
+    }
+
+    fn integer_type(&self, bytes: u8, signed: bool) -> String {
+        format!("{}Int{}", if signed { "" } else { "U" }, u16::from(bytes) * 8)
+    }
+
+    fn float_type(&self) -> String {
+        "Float32".to_string()
+    }
+
+    fn double_type(&self) -> String {
+        "Float64".to_string()
+    }
+
+    fn decimal_type(&self, precision: u8, scale: u8) -> String {
+        let precision = precision.min(76);
+        format!("Decimal({}, {})", precision, scale.min(precision))
+    }
+
+    fn date_type(&self) -> String {
+        "Date32".to_string()
+    }
+
+    fn time_type(&self) -> String {
+        "String".to_string()
+    }
+
+    /// Zoned timestamps are absolute instants and are stored as UTC.
+    fn timestamp_type(&self, unit: &TimeUnit, with_time_zone: bool) -> String {
+        let precision = fractional_digits(unit);
+        if with_time_zone {
+            format!("DateTime64({}, 'UTC')", precision)
+        } else {
+            format!("DateTime64({})", precision)
+        }
+    }
+
+    fn binary_type(&self, _max_len: usize) -> String {
+        "String".to_string()
+    }
+
+    fn varchar_type(&self, _max_len: usize) -> String {
+        "String".to_string()
+    }
+
+    fn text_type(&self) -> String {
+        "String".to_string()
+    }
+
+    /// Nullable columns are spelled `Nullable(T)`, and the primary key
+    /// becomes the MergeTree sorting key.
+    fn create_table(
+        &self,
+        table: &str,
+        if_not_exists: bool,
+        columns: &[ColumnDefinition],
+        primary_key: &[String],
+    ) -> String {
+        let definitions = columns
+            .iter()
+            .map(|column| {
+                if column.not_null {
+                    format!("  {} {}", column.name, column.sql_type)
+                } else {
+                    format!("  {} Nullable({})", column.name, column.sql_type)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(",\n");
+        let order_by = if primary_key.is_empty() {
+            "tuple()".to_string()
+        } else {
+            format!("({})", primary_key.join(", "))
+        };
+        format!(
+            "CREATE TABLE {}{} (\n{}\n)\nENGINE = MergeTree\nORDER BY {};",
+            if if_not_exists { "IF NOT EXISTS " } else { "" },
+            table,
+            definitions,
+            order_by
+        )
+    }
+}
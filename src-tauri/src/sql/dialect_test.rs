@@ -0,0 +1,272 @@
+use super::ddl::{generate_create_table, CreateTableOptions};
+use super::escape::{quote_binary, quote_identifier, quote_string};
+use super::generator::{
+    generate_sql_delete_from_batches, generate_sql_inserts_from_batches,
+    generate_sql_upsert_from_batches,
+};
+use crate::commands::query::Dialect;
+use datafusion::arrow::array::{
+    ArrayRef, BooleanArray, Date32Array, Int64Array, StringArray, TimestampMicrosecondArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// id  active  born        seen_at
+/// 1   true    2022-01-08  2023-11-14T22:13:20.123456
+fn typed_row() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("active", DataType::Boolean, true),
+        Field::new("born", DataType::Date32, true),
+        Field::new("seen_at", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from(vec![1_i64])),
+        Arc::new(BooleanArray::from(vec![true])),
+        Arc::new(Date32Array::from(vec![19_000])),
+        Arc::new(TimestampMicrosecondArray::from(vec![1_700_000_000_123_456])),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).expect("batch")
+}
+
+fn ids(count: i64) -> RecordBatch {
+    RecordBatch::try_from_iter(vec![(
+        "id",
+        Arc::new(Int64Array::from((1..=count).collect::<Vec<i64>>())) as ArrayRef,
+    )])
+    .expect("batch")
+}
+
+fn tenant_id_batch() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![
+        ("tenant", Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef),
+        ("id", Arc::new(Int64Array::from(vec![1_i64, 2])) as ArrayRef),
+    ])
+    .expect("batch")
+}
+
+fn inserts(batch: RecordBatch, max_values: usize, dialect: &Dialect) -> String {
+    generate_sql_inserts_from_batches(vec![batch], "t", max_values, dialect, None, false)
+        .expect("ok")
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+// ─── literals ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_quote_identifier_new_dialects() {
+    assert_eq!(quote_identifier("a\"b", &Dialect::SQLite), "\"a\"\"b\"");
+    assert_eq!(quote_identifier("a]b", &Dialect::SqlServer), "[a]]b]");
+    assert_eq!(quote_identifier("a\"b", &Dialect::Oracle), "\"a\"\"b\"");
+    assert_eq!(quote_identifier("a`b", &Dialect::ClickHouse), "`a\\`b`");
+}
+
+#[test]
+fn test_quote_string_new_dialects() {
+    assert_eq!(quote_string("it's C:\\", &Dialect::SQLite), "'it''s C:\\'");
+    assert_eq!(quote_string("it's", &Dialect::SqlServer), "N'it''s'");
+    assert_eq!(quote_string("it's", &Dialect::Oracle), "'it''s'");
+    assert_eq!(
+        quote_string("it's C:\\\n", &Dialect::ClickHouse),
+        "'it\\'s C:\\\\\\n'"
+    );
+}
+
+#[test]
+fn test_quote_string_splices_control_characters() {
+    assert_eq!(quote_string("a\0b", &Dialect::SQLite), "'a' || char(0) || 'b'");
+    assert_eq!(quote_string("\x01", &Dialect::SqlServer), "NCHAR(1)");
+    assert_eq!(quote_string("a\x01", &Dialect::Oracle), "'a' || CHR(1)");
+    // Tabs and newlines can be written raw.
+    assert_eq!(quote_string("a\tb\n", &Dialect::Oracle), "'a\tb\n'");
+    assert_eq!(quote_string("", &Dialect::SqlServer), "N''");
+}
+
+#[test]
+fn test_quote_binary_new_dialects() {
+    let bytes = [0x00, 0xff];
+    assert_eq!(quote_binary(&bytes, &Dialect::SQLite), "X'00ff'");
+    assert_eq!(quote_binary(&bytes, &Dialect::SqlServer), "0x00ff");
+    assert_eq!(quote_binary(&bytes, &Dialect::Oracle), "HEXTORAW('00ff')");
+    assert_eq!(quote_binary(&bytes, &Dialect::ClickHouse), "unhex('00ff')");
+}
+
+#[test]
+fn test_bool_and_date_literals_per_dialect() {
+    let cases = [
+        (
+            Dialect::SQLite,
+            "(1, 1, '2022-01-08', '2023-11-14 22:13:20.123456')",
+        ),
+        (
+            Dialect::SqlServer,
+            "(1, 1, '2022-01-08', '2023-11-14T22:13:20.123456')",
+        ),
+        (
+            Dialect::Oracle,
+            "(1, 1, DATE '2022-01-08', TIMESTAMP '2023-11-14 22:13:20.123456')",
+        ),
+        (
+            Dialect::ClickHouse,
+            "(1, true, '2022-01-08', '2023-11-14 22:13:20.123456')",
+        ),
+    ];
+    for (dialect, values) in cases {
+        let sql = inserts(typed_row(), 1000, &dialect);
+        assert!(sql.ends_with(&format!("VALUES\n{};", values)), "got: {}", sql);
+    }
+}
+
+// ─── statements ───────────────────────────────────────────────────────
+
+#[test]
+fn test_sql_server_caps_rows_per_insert() {
+    let sql = inserts(ids(1001), 5000, &Dialect::SqlServer);
+    assert_eq!(sql.matches("INSERT INTO [t] ([id]) VALUES").count(), 2);
+    assert!(sql.ends_with("VALUES\n(1001);"));
+}
+
+#[test]
+fn test_oracle_multi_row_insert_uses_insert_all() {
+    let sql = inserts(ids(3), 2, &Dialect::Oracle);
+    assert_eq!(
+        sql,
+        "INSERT ALL\n\
+         \x20 INTO \"t\" (\"id\") VALUES (1)\n\
+         \x20 INTO \"t\" (\"id\") VALUES (2)\n\
+         SELECT 1 FROM DUAL;\n\
+         INSERT INTO \"t\" (\"id\") VALUES\n(3);"
+    );
+}
+
+#[test]
+fn test_sqlite_upsert_uses_on_conflict() {
+    let sql = generate_sql_upsert_from_batches(
+        vec![tenant_id_batch()],
+        "t",
+        1000,
+        &["id"],
+        &Dialect::SQLite,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert!(sql.ends_with("ON CONFLICT (\"id\") DO UPDATE SET \"tenant\" = EXCLUDED.\"tenant\";"));
+}
+
+#[test]
+fn test_upsert_unsupported_dialects_error() {
+    for dialect in [Dialect::SqlServer, Dialect::Oracle, Dialect::ClickHouse] {
+        let result = generate_sql_upsert_from_batches(
+            vec![tenant_id_batch()],
+            "t",
+            1000,
+            &["id"],
+            &dialect,
+            None,
+            false,
+        );
+        let msg = format!("{:?}", result.unwrap_err());
+        assert!(msg.contains("not supported"), "got: {}", msg);
+    }
+}
+
+#[test]
+fn test_batched_delete_without_row_values() {
+    let sql = generate_sql_delete_from_batches(
+        vec![tenant_id_batch()],
+        "t",
+        &["tenant", "id"],
+        Some(1000),
+        &Dialect::SqlServer,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "DELETE FROM [t] WHERE ([tenant] = N'a' AND [id] = 1) OR ([tenant] = N'b' AND [id] = 2);\n"
+    );
+}
+
+// ─── CREATE TABLE ─────────────────────────────────────────────────────
+
+fn create_table(dialect: &Dialect, options: &CreateTableOptions) -> String {
+    let batch = typed_row();
+    generate_create_table(&batch.schema(), &[batch], "t", dialect, None, options).expect("ok")
+}
+
+fn keyed() -> CreateTableOptions {
+    CreateTableOptions {
+        primary_key: vec!["id".to_string()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_create_table_sqlite() {
+    assert_eq!(
+        create_table(&Dialect::SQLite, &keyed()),
+        "CREATE TABLE \"t\" (\n\
+         \x20 \"id\" INTEGER NOT NULL,\n\
+         \x20 \"active\" INTEGER,\n\
+         \x20 \"born\" TEXT,\n\
+         \x20 \"seen_at\" TEXT,\n\
+         \x20 PRIMARY KEY (\"id\")\n\
+         );"
+    );
+}
+
+#[test]
+fn test_create_table_sql_server_if_not_exists() {
+    let options = CreateTableOptions {
+        if_not_exists: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        create_table(&Dialect::SqlServer, &options),
+        "IF OBJECT_ID(N'[t]', N'U') IS NULL\n\
+         CREATE TABLE [t] (\n\
+         \x20 [id] BIGINT NOT NULL,\n\
+         \x20 [active] BIT,\n\
+         \x20 [born] DATE,\n\
+         \x20 [seen_at] DATETIME2(6)\n\
+         );"
+    );
+}
+
+#[test]
+fn test_create_table_oracle() {
+    assert_eq!(
+        create_table(&Dialect::Oracle, &CreateTableOptions::default()),
+        "CREATE TABLE \"t\" (\n\
+         \x20 \"id\" NUMBER(19) NOT NULL,\n\
+         \x20 \"active\" NUMBER(1),\n\
+         \x20 \"born\" DATE,\n\
+         \x20 \"seen_at\" TIMESTAMP(6)\n\
+         );"
+    );
+}
+
+#[test]
+fn test_create_table_clickhouse() {
+    assert_eq!(
+        create_table(&Dialect::ClickHouse, &keyed()),
+        "CREATE TABLE `t` (\n\
+         \x20 `id` Int64,\n\
+         \x20 `active` Nullable(Bool),\n\
+         \x20 `born` Nullable(Date32),\n\
+         \x20 `seen_at` Nullable(DateTime64(6))\n\
+         )\n\
+         ENGINE = MergeTree\n\
+         ORDER BY (`id`);"
+    );
+}
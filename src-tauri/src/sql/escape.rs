@@ -0,0 +1,150 @@
+use crate::commands::query::Dialect;
+use std::fmt::Write;
+
+/// Quote a table or column name for the dialect, e.g. backticks for MySQL,
+/// double quotes for PostgreSQL, with embedded quote characters escaped.
+pub fn quote_identifier(name: &str, db_dialect: &Dialect) -> String {
+    db_dialect.strategy().quote_identifier(name)
+}
+
+/// Quote a string literal so the target database reads back exactly `value`.
+pub fn quote_string(value: &str, db_dialect: &Dialect) -> String {
+    db_dialect.strategy().quote_string(value)
+}
+
+/// Binary data as a hex literal.
+pub fn quote_binary(value: &[u8], db_dialect: &Dialect) -> String {
+    db_dialect.strategy().quote_binary(value)
+}
+
+pub(crate) fn to_hex(value: &[u8]) -> String {
+    let mut hex = String::with_capacity(value.len() * 2);
+    for byte in value {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+/// MySQL reads backslash escapes in string literals unless the server runs
+/// with `NO_BACKSLASH_ESCAPES`, in which case a backslash is a plain
+/// character. Control characters that can't be written in the literal for
+/// the given mode turn the whole value into a hex literal, which MySQL
+/// converts back to the column's character set.
+pub(crate) fn quote_mysql_string(value: &str, backslash_escapes: bool) -> String {
+    let expressible = |c: char| match c {
+        '\t' | '\n' | '\r' => true,
+        '\0' | '\x08' | '\x1a' => backslash_escapes,
+        c => !c.is_control(),
+    };
+    if !value.chars().all(expressible) {
+        return format!("X'{}'", to_hex(value.as_bytes()));
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("''"),
+            '\\' if backslash_escapes => quoted.push_str("\\\\"),
+            '\0' => quoted.push_str("\\0"),
+            '\x08' => quoted.push_str("\\b"),
+            '\x1a' => quoted.push_str("\\Z"),
+            '\n' if backslash_escapes => quoted.push_str("\\n"),
+            '\r' if backslash_escapes => quoted.push_str("\\r"),
+            '\t' if backslash_escapes => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Plain `'...'` literals are only unambiguous with
+/// `standard_conforming_strings = on`, so values containing backslashes or
+/// control characters are written as `E'...'` escape strings, which read the
+/// same under either setting. PostgreSQL text cannot store NUL, so NUL
+/// characters are dropped.
+pub(crate) fn quote_postgres_string(value: &str) -> String {
+    if !value.chars().any(|c| c == '\\' || c.is_control()) {
+        return format!("'{}'", value.replace('\'', "''"));
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 3);
+    quoted.push_str("E'");
+    for c in value.chars() {
+        match c {
+            '\0' => {}
+            '\'' => quoted.push_str("''"),
+            '\\' => quoted.push_str("\\\\"),
+            '\x08' => quoted.push_str("\\b"),
+            '\x0c' => quoted.push_str("\\f"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// ClickHouse reads backslash escapes in string literals; other ASCII
+/// control characters are written as `\xHH`.
+pub(crate) fn quote_clickhouse_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\0' => quoted.push_str("\\0"),
+            '\x08' => quoted.push_str("\\b"),
+            '\x0c' => quoted.push_str("\\f"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_ascii_control() => {
+                let _ = write!(quoted, "\\x{:02x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// For dialects without escapes in string literals (SQLite, SQL Server,
+/// Oracle): characters that `is_literal` rejects are spliced in with a
+/// character function, e.g. `'a' || CHR(0) || 'b'`. `prefix` goes before
+/// every quoted part (`N` for SQL Server).
+pub(crate) fn quote_concat_string(
+    value: &str,
+    prefix: &str,
+    is_literal: impl Fn(char) -> bool,
+    char_expr: impl Fn(u32) -> String,
+    concat: &str,
+) -> String {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    for c in value.chars() {
+        if is_literal(c) {
+            if c == '\'' {
+                literal.push('\'');
+            }
+            literal.push(c);
+        } else {
+            if !literal.is_empty() {
+                parts.push(format!("{}'{}'", prefix, literal));
+                literal.clear();
+            }
+            parts.push(char_expr(c as u32));
+        }
+    }
+    if !literal.is_empty() || parts.is_empty() {
+        parts.push(format!("{}'{}'", prefix, literal));
+    }
+    parts.join(concat)
+}
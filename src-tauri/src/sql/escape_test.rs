@@ -0,0 +1,180 @@
+use super::escape::{quote_binary, quote_identifier, quote_string};
+use super::generator::{generate_sql_inserts_from_batches, generate_sql_update_from_batches};
+use crate::commands::query::Dialect;
+use datafusion::arrow::array::{Int64Array, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+// ─── quote_identifier ─────────────────────────────────────────────────
+
+#[test]
+fn test_quote_identifier_plain() {
+    assert_eq!(quote_identifier("users", &Dialect::MySQL), "`users`");
+    assert_eq!(quote_identifier("users", &Dialect::PostgreSQL), "\"users\"");
+}
+
+#[test]
+fn test_quote_identifier_doubles_embedded_quotes() {
+    assert_eq!(quote_identifier("we`ird", &Dialect::MySQL), "`we``ird`");
+    assert_eq!(
+        quote_identifier("we`ird", &Dialect::MySQLNoBackslashEscapes),
+        "`we``ird`"
+    );
+    assert_eq!(quote_identifier("we\"ird", &Dialect::PostgreSQL), "\"we\"\"ird\"");
+    // The other dialect's quote character needs no escaping.
+    assert_eq!(quote_identifier("we\"ird", &Dialect::MySQL), "`we\"ird`");
+    assert_eq!(quote_identifier("we`ird", &Dialect::PostgreSQL), "\"we`ird\"");
+}
+
+// ─── quote_string: MySQL ──────────────────────────────────────────────
+
+#[test]
+fn test_mysql_string_escapes_backslashes() {
+    assert_eq!(quote_string("C:\\new", &Dialect::MySQL), "'C:\\\\new'");
+    assert_eq!(quote_string("it's", &Dialect::MySQL), "'it''s'");
+    assert_eq!(quote_string("\\'", &Dialect::MySQL), "'\\\\'''");
+}
+
+#[test]
+fn test_mysql_string_escapes_control_characters() {
+    assert_eq!(
+        quote_string("a\0b\nc\rd\te\x08f\x1ag", &Dialect::MySQL),
+        "'a\\0b\\nc\\rd\\te\\bf\\Zg'"
+    );
+}
+
+#[test]
+fn test_mysql_string_unescapable_control_character_uses_hex() {
+    // \x01 has no backslash escape in MySQL.
+    assert_eq!(quote_string("a\x01b", &Dialect::MySQL), "X'610162'");
+}
+
+#[test]
+fn test_mysql_no_backslash_escapes_keeps_backslash_literal() {
+    let dialect = Dialect::MySQLNoBackslashEscapes;
+    assert_eq!(quote_string("C:\\new", &dialect), "'C:\\new'");
+    assert_eq!(quote_string("it's", &dialect), "'it''s'");
+    // Newlines and tabs can be written raw inside the literal.
+    assert_eq!(quote_string("a\nb\tc", &dialect), "'a\nb\tc'");
+}
+
+#[test]
+fn test_mysql_no_backslash_escapes_nul_uses_hex() {
+    assert_eq!(
+        quote_string("a\0b", &Dialect::MySQLNoBackslashEscapes),
+        "X'610062'"
+    );
+}
+
+#[test]
+fn test_mysql_string_keeps_unicode() {
+    assert_eq!(quote_string("naïve 日本 🚀", &Dialect::MySQL), "'naïve 日本 🚀'");
+}
+
+// ─── quote_string: PostgreSQL ─────────────────────────────────────────
+
+#[test]
+fn test_postgres_plain_string() {
+    assert_eq!(quote_string("it's", &Dialect::PostgreSQL), "'it''s'");
+    assert_eq!(quote_string("", &Dialect::PostgreSQL), "''");
+}
+
+#[test]
+fn test_postgres_backslash_uses_escape_string() {
+    assert_eq!(quote_string("C:\\new", &Dialect::PostgreSQL), "E'C:\\\\new'");
+    assert_eq!(quote_string("it's C:\\", &Dialect::PostgreSQL), "E'it''s C:\\\\'");
+}
+
+#[test]
+fn test_postgres_control_characters_use_escape_string() {
+    assert_eq!(
+        quote_string("a\nb\tc\x01d", &Dialect::PostgreSQL),
+        "E'a\\nb\\tc\\u0001d'"
+    );
+}
+
+#[test]
+fn test_postgres_drops_nul() {
+    assert_eq!(quote_string("a\0b", &Dialect::PostgreSQL), "E'ab'");
+}
+
+// ─── quote_binary ─────────────────────────────────────────────────────
+
+#[test]
+fn test_quote_binary() {
+    assert_eq!(quote_binary(&[0x00, 0xff], &Dialect::MySQL), "X'00ff'");
+    assert_eq!(quote_binary(&[0x00, 0xff], &Dialect::PostgreSQL), "decode('00ff', 'hex')");
+}
+
+// ─── generator integration ────────────────────────────────────────────
+
+fn nasty_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("i`d\"", DataType::Int64, true),
+        Field::new("path", DataType::Utf8, true),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1_i64])),
+            Arc::new(StringArray::from(vec!["C:\\new\n'x'"])),
+        ],
+    )
+    .expect("batch")
+}
+
+#[test]
+fn test_inserts_escape_identifiers_and_values_mysql() {
+    let sql = generate_sql_inserts_from_batches(
+        vec![nasty_batch()],
+        "my`table",
+        1000,
+        &Dialect::MySQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "INSERT INTO `my``table` (`i``d\"`, `path`) VALUES\n(1, 'C:\\\\new\\n''x''');"
+    );
+}
+
+#[test]
+fn test_inserts_escape_identifiers_and_values_postgres() {
+    let sql = generate_sql_inserts_from_batches(
+        vec![nasty_batch()],
+        "my\"table",
+        1000,
+        &Dialect::PostgreSQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "INSERT INTO \"my\"\"table\" (\"i`d\"\"\", \"path\") VALUES\n(1, E'C:\\\\new\\n''x''');"
+    );
+}
+
+#[test]
+fn test_update_escape_identifiers_no_backslash_escapes() {
+    let sql = generate_sql_update_from_batches(
+        vec![nasty_batch()],
+        "t",
+        &["i`d\""],
+        &Dialect::MySQLNoBackslashEscapes,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "UPDATE `t` SET `path` = 'C:\\new\n''x''' WHERE `i``d\"` = 1;\n"
+    );
+}
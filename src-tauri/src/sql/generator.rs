@@ -1,16 +1,34 @@
 use crate::commands::query::Dialect;
+use crate::context::error::AppError;
 use crate::context::schema::AppResult;
+use crate::sql::escape::{quote_binary, quote_identifier, quote_string};
+use crate::sql::script::SqlScript;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Offset};
+use datafusion::arrow::array::timezone::Tz;
+use datafusion::arrow::array::{Array, AsArray};
+use datafusion::arrow::datatypes::{
+    DataType, Date32Type, Date64Type, Float16Type, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, Schema, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
 use datafusion::arrow::error::ArrowError;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
 use datafusion::dataframe::DataFrame;
+use futures::StreamExt;
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct ExportColumnConfig {
     pub source_column_name: String,
     pub export_column_name: String,
     pub sql_type: String,
+    /// IANA name or `+hh:mm` offset that zoned timestamps are converted to;
+    /// they are then written as wall-clock time in that zone, without an offset.
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 /// Pre-parsed SQL type category to avoid repeated string allocations in the hot formatting loop.
@@ -19,7 +37,12 @@ pub(crate) enum SqlType {
     Bool,
     Int,
     Float,
+    /// Exact numeric, written with the source's digits.
+    Decimal,
     Text,
+    Date,
+    Timestamp,
+    Binary,
     Unknown,
 }
 
@@ -33,8 +56,16 @@ pub(crate) fn parse_sql_type(sql_type: &str) -> SqlType {
         || upper.starts_with("TINYINT")
     {
         SqlType::Int
-    } else if upper.starts_with("DOUBLE") || upper.starts_with("FLOAT") || upper.starts_with("DECIMAL") || upper.starts_with("NUMERIC") || upper.starts_with("REAL") {
+    } else if upper.starts_with("DECIMAL") || upper.starts_with("NUMERIC") || upper.starts_with("NUMBER") {
+        SqlType::Decimal
+    } else if upper.starts_with("DOUBLE") || upper.starts_with("FLOAT") || upper.starts_with("REAL") {
         SqlType::Float
+    } else if upper.starts_with("DATETIME") || upper.starts_with("TIMESTAMP") {
+        SqlType::Timestamp
+    } else if upper.starts_with("DATE") {
+        SqlType::Date
+    } else if upper.starts_with("BINARY") || upper.starts_with("VARBINARY") || upper.starts_with("BLOB") || upper.starts_with("BYTEA") {
+        SqlType::Binary
     } else if upper.starts_with("TEXT") || upper.starts_with("CHAR") || upper.starts_with("VARCHAR") || upper.starts_with("STR") {
         SqlType::Text
     } else {
@@ -47,6 +78,7 @@ pub(crate) struct ColumnExportSpec {
     pub(crate) source_index: usize,
     pub(crate) export_name: String,
     pub(crate) sql_type: SqlType,
+    pub(crate) timezone: Option<Tz>,
 }
 
 pub(crate) fn resolve_export_specs(
@@ -61,12 +93,13 @@ pub(crate) fn resolve_export_specs(
                 source_index: idx,
                 export_name: name.clone(),
                 sql_type: SqlType::Unknown,
+                timezone: None,
             })
             .collect());
     };
 
     if export_columns.is_empty() {
-        return Err(crate::context::error::AppError::BadRequest {
+        return Err(AppError::BadRequest {
             message: "At least one column must be selected for export".to_string(),
         });
     }
@@ -82,7 +115,7 @@ pub(crate) fn resolve_export_specs(
     for col in export_columns {
         let export_name = col.export_column_name.trim();
         if export_name.is_empty() {
-            return Err(crate::context::error::AppError::BadRequest {
+            return Err(AppError::BadRequest {
                 message: format!(
                     "Export column name cannot be empty for source column '{}'",
                     col.source_column_name
@@ -91,7 +124,7 @@ pub(crate) fn resolve_export_specs(
         }
 
         if seen_names.contains(export_name) {
-            return Err(crate::context::error::AppError::BadRequest {
+            return Err(AppError::BadRequest {
                 message: format!(
                     "Duplicate export column name '{}'. Each export column must have a unique name.",
                     export_name
@@ -103,7 +136,7 @@ pub(crate) fn resolve_export_specs(
         let source_index = header_index
             .get(col.source_column_name.as_str())
             .copied()
-            .ok_or_else(|| crate::context::error::AppError::BadRequest {
+            .ok_or_else(|| AppError::BadRequest {
                 message: format!(
                     "Source column '{}' not found in query result",
                     col.source_column_name
@@ -114,163 +147,476 @@ pub(crate) fn resolve_export_specs(
             source_index,
             export_name: export_name.to_string(),
             sql_type: parse_sql_type(&col.sql_type),
+            timezone: parse_timezone(col)?,
         });
     }
 
     Ok(specs)
 }
 
-pub(crate) fn format_cell_for_sql(
-    formatted_value: &str,
+fn parse_timezone(col: &ExportColumnConfig) -> AppResult<Option<Tz>> {
+    let Some(timezone) = col.timezone.as_deref().map(str::trim) else {
+        return Ok(None);
+    };
+    if timezone.is_empty() {
+        return Ok(None);
+    }
+    timezone
+        .parse::<Tz>()
+        .map(Some)
+        .map_err(|_| AppError::BadRequest {
+            message: format!(
+                "Invalid timezone '{}' for column '{}'",
+                timezone, col.source_column_name
+            ),
+        })
+}
+
+/// Format one Arrow cell as a SQL literal.
+///
+/// The value is read according to the array's `DataType`, so a real NULL and
+/// the text `'NULL'`, or a number and a numeric-looking string such as the zip
+/// code `'00123'`, stay distinct. `col_type` (from `ExportColumnConfig.sql_type`)
+/// only applies as an override on top; `SqlType::Unknown` keeps the Arrow type.
+/// `formatter` must wrap `array` and is used for the display form of types
+/// without a dedicated rule (decimals, times, nested types, ...). Zoned
+/// timestamps are converted to `timezone` when given.
+pub(crate) fn format_array_value_for_sql(
+    array: &dyn Array,
+    formatter: &ArrayFormatter,
+    row: usize,
     col_type: SqlType,
+    timezone: Option<&Tz>,
     empty_text_as_null: bool,
+    db_dialect: &Dialect,
 ) -> String {
-    if formatted_value == "NULL" {
+    if array.is_null(row) {
         return "NULL".to_string();
     }
 
-    // When empty_text_as_null is enabled, treat empty strings as NULL for text-like types
-    if empty_text_as_null && formatted_value.is_empty() {
-        match col_type {
-            SqlType::Text | SqlType::Unknown => return "NULL".to_string(),
-            _ => {}
-        }
-    }
-
-    if col_type == SqlType::Unknown {
-        if formatted_value.parse::<i64>().is_ok()
-            || formatted_value.parse::<f64>().is_ok()
-            || formatted_value == "true"
-            || formatted_value == "false"
-        {
-            return strip_float_zero_suffix(formatted_value);
-        }
-        return format_value_for_sql(formatted_value, true);
-    }
-
-    if col_type == SqlType::Bool {
-        format_bool_for_sql(formatted_value)
-    } else if col_type == SqlType::Int || col_type == SqlType::Float {
-        if formatted_value == "true" {
-            "1".to_string()
-        } else if formatted_value == "false" {
-            "0".to_string()
-        } else if let Ok(i) = formatted_value.parse::<i64>() {
-            i.to_string()
-        } else if let Ok(u) = formatted_value.parse::<u64>() {
-            u.to_string()
-        } else if let Ok(f) = formatted_value.parse::<f64>() {
-            if f.is_finite() {
-                if col_type == SqlType::Int {
-                    format!("{}", f as i64)
-                } else {
-                    formatted_value.to_string()
-                }
-            } else {
-                "NULL".to_string()
+    let data_type = array.data_type();
+    match col_type {
+        SqlType::Decimal => return decimal_literal(array, formatter, row),
+        SqlType::Date => return date_literal(array, formatter, row, timezone, db_dialect),
+        SqlType::Unknown if matches!(data_type, DataType::Date32 | DataType::Date64) => {
+            return date_literal(array, formatter, row, timezone, db_dialect)
+        }
+        SqlType::Timestamp => {
+            return timestamp_literal(array, formatter, row, timezone, db_dialect)
+        }
+        SqlType::Unknown if matches!(data_type, DataType::Timestamp(_, _)) => {
+            return timestamp_literal(array, formatter, row, timezone, db_dialect)
+        }
+        SqlType::Binary if !is_binary(data_type) => {
+            return quote_binary(formatter.value(row).to_string().as_bytes(), db_dialect)
+        }
+        _ => {}
+    }
+
+    match data_type {
+        DataType::Boolean => bool_literal(array.as_boolean().value(row), col_type, db_dialect),
+        DataType::Int8 => int_literal(
+            array.as_primitive::<Int8Type>().value(row).into(),
+            col_type,
+            db_dialect,
+        ),
+        DataType::Int16 => int_literal(
+            array.as_primitive::<Int16Type>().value(row).into(),
+            col_type,
+            db_dialect,
+        ),
+        DataType::Int32 => int_literal(
+            array.as_primitive::<Int32Type>().value(row).into(),
+            col_type,
+            db_dialect,
+        ),
+        DataType::Int64 => int_literal(
+            array.as_primitive::<Int64Type>().value(row),
+            col_type,
+            db_dialect,
+        ),
+        DataType::UInt8 => int_literal(
+            array.as_primitive::<UInt8Type>().value(row).into(),
+            col_type,
+            db_dialect,
+        ),
+        DataType::UInt16 => int_literal(
+            array.as_primitive::<UInt16Type>().value(row).into(),
+            col_type,
+            db_dialect,
+        ),
+        DataType::UInt32 => int_literal(
+            array.as_primitive::<UInt32Type>().value(row).into(),
+            col_type,
+            db_dialect,
+        ),
+        DataType::UInt64 => {
+            let value = array.as_primitive::<UInt64Type>().value(row);
+            match i64::try_from(value) {
+                Ok(value) => int_literal(value, col_type, db_dialect),
+                Err(_) => numeric_literal(&value.to_string(), col_type, db_dialect),
+            }
+        }
+        DataType::Float16 => {
+            let value = array.as_primitive::<Float16Type>().value(row);
+            float_literal(value.to_f64(), value.to_string(), col_type, db_dialect)
+        }
+        DataType::Float32 => {
+            let value = array.as_primitive::<Float32Type>().value(row);
+            float_literal(value.into(), value.to_string(), col_type, db_dialect)
+        }
+        DataType::Float64 => {
+            let value = array.as_primitive::<Float64Type>().value(row);
+            float_literal(value, value.to_string(), col_type, db_dialect)
+        }
+        DataType::Decimal32(_, _)
+        | DataType::Decimal64(_, _)
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _) => {
+            numeric_literal(&formatter.value(row).to_string(), col_type, db_dialect)
+        }
+        DataType::Utf8 => text_literal(
+            array.as_string::<i32>().value(row),
+            col_type,
+            empty_text_as_null,
+            db_dialect,
+        ),
+        DataType::LargeUtf8 => text_literal(
+            array.as_string::<i64>().value(row),
+            col_type,
+            empty_text_as_null,
+            db_dialect,
+        ),
+        DataType::Utf8View => text_literal(
+            array.as_string_view().value(row),
+            col_type,
+            empty_text_as_null,
+            db_dialect,
+        ),
+        DataType::Binary => {
+            binary_literal(array.as_binary::<i32>().value(row), col_type, db_dialect)
+        }
+        DataType::LargeBinary => {
+            binary_literal(array.as_binary::<i64>().value(row), col_type, db_dialect)
+        }
+        DataType::BinaryView => {
+            binary_literal(array.as_binary_view().value(row), col_type, db_dialect)
+        }
+        DataType::FixedSizeBinary(_) => binary_literal(
+            array.as_fixed_size_binary().value(row),
+            col_type,
+            db_dialect,
+        ),
+        // Times, nested types and anything else: Arrow's display form, quoted
+        // unless a numeric/bool override asks for a conversion.
+        _ => {
+            let display = formatter.value(row).to_string();
+            match col_type {
+                SqlType::Text | SqlType::Unknown => quote_string(&display, db_dialect),
+                _ => converted_text_literal(&display, col_type, db_dialect),
             }
-        } else {
-            "NULL".to_string()
         }
-    } else {
-        format_value_for_sql(formatted_value, true)
     }
 }
 
-/// Strip trailing ".0" from float-formatted values that are actually integers
-pub(crate) fn strip_float_zero_suffix(value: &str) -> String {
-    if value.ends_with(".0") {
-        // Check that the part before ".0" is a valid integer
-        let integer_part = &value[..value.len() - 2];
-        if integer_part.parse::<i64>().is_ok() || integer_part.parse::<u64>().is_ok() {
-            return integer_part.to_string();
-        }
+fn bool_literal(value: bool, col_type: SqlType, db_dialect: &Dialect) -> String {
+    match col_type {
+        SqlType::Int | SqlType::Float => if value { "1" } else { "0" }.to_string(),
+        SqlType::Text => quote_string(&value.to_string(), db_dialect),
+        _ => db_dialect.strategy().bool_literal(value).to_string(),
     }
-    value.to_string()
 }
 
-/// Format a value as a SQL boolean literal (true/false, unquoted)
-pub(crate) fn format_bool_for_sql(value: &str) -> String {
-    if value == "NULL" {
-        "NULL".to_string()
-    } else {
-        match value.to_ascii_lowercase().as_str() {
-            "true" | "1" => "true".to_string(),
-            "false" | "0" => "false".to_string(),
+fn int_literal(value: i64, col_type: SqlType, db_dialect: &Dialect) -> String {
+    match col_type {
+        SqlType::Bool => match value {
+            0 => bool_literal(false, col_type, db_dialect),
+            1 => bool_literal(true, col_type, db_dialect),
             _ => "NULL".to_string(),
+        },
+        SqlType::Text => quote_string(&value.to_string(), db_dialect),
+        _ => value.to_string(),
+    }
+}
+
+/// `display` is the shortest representation that round-trips to `value`.
+fn float_literal(value: f64, display: String, col_type: SqlType, db_dialect: &Dialect) -> String {
+    if col_type == SqlType::Text {
+        return quote_string(&display, db_dialect);
+    }
+    if !value.is_finite() {
+        return "NULL".to_string();
+    }
+    match col_type {
+        SqlType::Int => format!("{}", value as i64),
+        SqlType::Bool if value == 0.0 => bool_literal(false, col_type, db_dialect),
+        SqlType::Bool if value == 1.0 => bool_literal(true, col_type, db_dialect),
+        SqlType::Bool => "NULL".to_string(),
+        _ => display,
+    }
+}
+
+/// Exact numbers that don't fit an `i64` (decimals, large `u64`s), given as
+/// their display digits.
+fn numeric_literal(display: &str, col_type: SqlType, db_dialect: &Dialect) -> String {
+    match col_type {
+        SqlType::Text => quote_string(display, db_dialect),
+        SqlType::Bool => match display.parse::<f64>() {
+            Ok(value) => float_literal(value, display.to_string(), col_type, db_dialect),
+            Err(_) => "NULL".to_string(),
+        },
+        SqlType::Int => match display.split('.').next().unwrap_or(display) {
+            "-0" => "0".to_string(),
+            integer => integer.to_string(),
+        },
+        _ => display.to_string(),
+    }
+}
+
+fn text_literal(
+    value: &str,
+    col_type: SqlType,
+    empty_text_as_null: bool,
+    db_dialect: &Dialect,
+) -> String {
+    match col_type {
+        SqlType::Text | SqlType::Unknown if empty_text_as_null && value.is_empty() => {
+            "NULL".to_string()
         }
+        SqlType::Text | SqlType::Unknown => quote_string(value, db_dialect),
+        _ => converted_text_literal(value, col_type, db_dialect),
     }
 }
 
-/// Helper function to format a value from Arrow Array for SQL
-/// When strip_float_suffix is true, removes trailing ".0" from float values
-pub(crate) fn format_value_for_sql(value: &str, strip_float_suffix: bool) -> String {
-    if value == "NULL" {
+/// Text under a Bool, Int or Float override: `true` / `false` or a number,
+/// converted to the target type. Anything else becomes NULL.
+fn converted_text_literal(value: &str, col_type: SqlType, db_dialect: &Dialect) -> String {
+    if value.eq_ignore_ascii_case("true") {
+        bool_literal(true, col_type, db_dialect)
+    } else if value.eq_ignore_ascii_case("false") {
+        bool_literal(false, col_type, db_dialect)
+    } else if let Ok(integer) = value.parse::<i64>() {
+        int_literal(integer, col_type, db_dialect)
+    } else if value.parse::<u64>().is_ok() {
+        numeric_literal(value, col_type, db_dialect)
+    } else if let Ok(float) = value.parse::<f64>() {
+        float_literal(float, value.to_string(), col_type, db_dialect)
+    } else {
         "NULL".to_string()
+    }
+}
+
+/// Binary values as hex literals; a Text override quotes them as (lossy) UTF-8.
+fn binary_literal(value: &[u8], col_type: SqlType, db_dialect: &Dialect) -> String {
+    if col_type == SqlType::Text {
+        return quote_string(&String::from_utf8_lossy(value), db_dialect);
+    }
+    quote_binary(value, db_dialect)
+}
+
+fn is_binary(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_)
+    )
+}
+
+/// Exact numeric literal: integers and decimals keep their digits, floats
+/// their shortest round-trip form, and text must already be a plain number.
+fn decimal_literal(array: &dyn Array, formatter: &ArrayFormatter, row: usize) -> String {
+    if let DataType::Boolean = array.data_type() {
+        return if array.as_boolean().value(row) { "1" } else { "0" }.to_string();
+    }
+    let display = formatter.value(row).to_string();
+    let value = display.trim();
+    let is_number = is_plain_number(value)
+        || (array.data_type().is_floating() && value.parse::<f64>().is_ok_and(f64::is_finite));
+    if is_number {
+        value.to_string()
     } else {
-        let display_value = if strip_float_suffix {
-            strip_float_zero_suffix(value)
-        } else {
-            value.to_string()
-        };
-        // Escape single quotes and wrap in quotes for string values
-        format!("'{}'", display_value.replace("'", "''"))
+        "NULL".to_string()
     }
 }
 
-/// Extract rows from RecordBatch, optionally filtering/renaming columns via export specs.
-fn extract_rows_from_batches<F>(
-    batches: Vec<RecordBatch>,
-    export_specs: &[ColumnExportSpec],
-    empty_text_as_null: bool,
-    mut on_row: F,
-) -> AppResult<()>
-where
-    F: FnMut(Vec<String>) -> AppResult<()>,
-{
-    if batches.is_empty() {
-        return Ok(());
+/// `[+-]digits[.digits]`, as accepted by every dialect's numeric literal.
+fn is_plain_number(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    !(integer.is_empty() && fraction.is_empty())
+        && integer.bytes().all(|b| b.is_ascii_digit())
+        && fraction.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Date literal from a date, the date part of a timestamp, or `YYYY-MM-DD` text.
+fn date_literal(
+    array: &dyn Array,
+    formatter: &ArrayFormatter,
+    row: usize,
+    timezone: Option<&Tz>,
+    db_dialect: &Dialect,
+) -> String {
+    let date = match array.data_type() {
+        DataType::Date32 => array.as_primitive::<Date32Type>().value_as_date(row),
+        DataType::Date64 => array.as_primitive::<Date64Type>().value_as_date(row),
+        DataType::Timestamp(_, _) => {
+            timestamp_parts(array, row, timezone).map(|(date_time, _)| date_time.date())
+        }
+        _ => parse_timestamp_text(&formatter.value(row).to_string(), timezone)
+            .map(|(date_time, _)| date_time.date()),
+    };
+    match date {
+        Some(date) => db_dialect
+            .strategy()
+            .date_literal(&date.format("%Y-%m-%d").to_string()),
+        None => "NULL".to_string(),
     }
+}
 
-    let options = FormatOptions::default().with_null("NULL");
+/// Timestamp literal from a timestamp, a date (at midnight), or ISO text.
+fn timestamp_literal(
+    array: &dyn Array,
+    formatter: &ArrayFormatter,
+    row: usize,
+    timezone: Option<&Tz>,
+    db_dialect: &Dialect,
+) -> String {
+    let parts = match array.data_type() {
+        DataType::Timestamp(_, _) => timestamp_parts(array, row, timezone),
+        DataType::Date32 => array
+            .as_primitive::<Date32Type>()
+            .value_as_date(row)
+            .map(|date| (date.and_time(NaiveTime::MIN), None)),
+        DataType::Date64 => array
+            .as_primitive::<Date64Type>()
+            .value_as_date(row)
+            .map(|date| (date.and_time(NaiveTime::MIN), None)),
+        _ => parse_timestamp_text(&formatter.value(row).to_string(), timezone),
+    };
+    match parts {
+        Some((date_time, offset)) => db_dialect.strategy().timestamp_literal(
+            &date_time.format("%Y-%m-%d %H:%M:%S%.f").to_string(),
+            offset.as_deref(),
+        ),
+        None => "NULL".to_string(),
+    }
+}
 
-    for batch in batches {
-        let formatters = batch
-            .columns()
-            .iter()
-            .map(|c| ArrayFormatter::try_new(c.as_ref(), &options))
-            .collect::<Result<Vec<_>, ArrowError>>()
-            .map_err(|e| crate::context::error::AppError::BadRequest {
-                message: format!("Failed to create formatter: {}", e),
-            })?;
+/// Wall-clock time of a timestamp cell, plus its UTC offset for zoned
+/// columns that are not converted to `timezone`.
+fn timestamp_parts(
+    array: &dyn Array,
+    row: usize,
+    timezone: Option<&Tz>,
+) -> Option<(NaiveDateTime, Option<String>)> {
+    let DataType::Timestamp(unit, column_timezone) = array.data_type() else {
+        return None;
+    };
+    let utc = match unit {
+        TimeUnit::Second => {
+            DateTime::from_timestamp(array.as_primitive::<TimestampSecondType>().value(row), 0)
+        }
+        TimeUnit::Millisecond => DateTime::from_timestamp_millis(
+            array.as_primitive::<TimestampMillisecondType>().value(row),
+        ),
+        TimeUnit::Microsecond => DateTime::from_timestamp_micros(
+            array.as_primitive::<TimestampMicrosecondType>().value(row),
+        ),
+        TimeUnit::Nanosecond => Some(DateTime::from_timestamp_nanos(
+            array.as_primitive::<TimestampNanosecondType>().value(row),
+        )),
+    }?;
 
-        for row_idx in 0..batch.num_rows() {
-            let mut cells = Vec::with_capacity(export_specs.len());
-            for spec in export_specs {
-                let formatted_value = formatters[spec.source_index]
-                    .value(row_idx)
-                    .to_string();
-                cells.push(format_cell_for_sql(&formatted_value, spec.sql_type, empty_text_as_null));
-            }
-            on_row(cells)?;
+    // Naive timestamps already hold wall-clock time.
+    let Some(column_timezone) = column_timezone else {
+        return Some((utc.naive_utc(), None));
+    };
+    match timezone {
+        Some(target) => Some((utc.with_timezone(target).naive_local(), None)),
+        None => {
+            let source = column_timezone.parse::<Tz>().ok()?;
+            let local = utc.with_timezone(&source);
+            Some((local.naive_local(), Some(local.offset().fix().to_string())))
         }
     }
+}
 
-    Ok(())
+/// Parse RFC 3339 (with offset), `YYYY-MM-DD[ HH:MM[:SS[.fff]]]` text.
+fn parse_timestamp_text(
+    text: &str,
+    timezone: Option<&Tz>,
+) -> Option<(NaiveDateTime, Option<String>)> {
+    let text = text.trim();
+    if let Ok(zoned) = DateTime::parse_from_rfc3339(text) {
+        return Some(match timezone {
+            Some(target) => (zoned.with_timezone(target).naive_local(), None),
+            None => (zoned.naive_local(), Some(zoned.offset().to_string())),
+        });
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M"] {
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(text, format) {
+            return Some((date_time, None));
+        }
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .map(|date| (date.and_time(NaiveTime::MIN), None))
+}
+
+/// Convert the rows of one RecordBatch to SQL literals, optionally
+/// filtering/renaming columns via export specs.
+fn extract_rows_from_batch(
+    batch: &RecordBatch,
+    export_specs: &[ColumnExportSpec],
+    empty_text_as_null: bool,
+    db_dialect: &Dialect,
+) -> AppResult<Vec<Vec<String>>> {
+    let options = FormatOptions::default();
+    let formatters = batch
+        .columns()
+        .iter()
+        .map(|c| ArrayFormatter::try_new(c.as_ref(), &options))
+        .collect::<Result<Vec<_>, ArrowError>>()
+        .map_err(|e| AppError::BadRequest {
+            message: format!("Failed to create formatter: {}", e),
+        })?;
+
+    let mut rows = Vec::with_capacity(batch.num_rows());
+    for row_idx in 0..batch.num_rows() {
+        let mut cells = Vec::with_capacity(export_specs.len());
+        for spec in export_specs {
+            cells.push(format_array_value_for_sql(
+                batch.column(spec.source_index).as_ref(),
+                &formatters[spec.source_index],
+                row_idx,
+                spec.sql_type,
+                spec.timezone.as_ref(),
+                empty_text_as_null,
+                db_dialect,
+            ));
+        }
+        rows.push(cells);
+    }
+
+    Ok(rows)
+}
+
+/// Column names of a schema, in order.
+pub fn schema_headers(schema: &Schema) -> Vec<String> {
+    schema
+        .fields()
+        .iter()
+        .map(|f| f.name().to_string())
+        .collect()
 }
 
 /// Helper function to extract column headers from the provided RecordBatches
 fn extract_headers_from_batches(batches: &[RecordBatch]) -> Vec<String> {
     batches
         .first()
-        .map(|batch| {
-            batch
-                .schema()
-                .fields()
-                .iter()
-                .map(|f| f.name().to_string())
-                .collect()
-        })
+        .map(|batch| schema_headers(&batch.schema()))
         .unwrap_or_default()
 }
 
@@ -294,118 +640,320 @@ pub fn truncate_record_batches(batches: Vec<RecordBatch>, max_rows: usize) -> Ve
     result
 }
 
-/// Generate SQL insert statements from collected RecordBatches.
-pub fn generate_sql_inserts_from_batches(
-    batches: Vec<RecordBatch>,
-    table_name: &str,
-    max_values_per_insert: usize,
-    db_dialect: &Dialect,
-    export_columns: Option<&[ExportColumnConfig]>,
-    empty_text_as_null: bool,
+/// Incremental SQL generation: statements are written to `out` as batches
+/// arrive, so only the rows of an unfinished chunk are held in memory.
+pub trait StatementWriter {
+    fn write_batch<W: Write>(
+        &mut self,
+        batch: &RecordBatch,
+        out: &mut SqlScript<W>,
+    ) -> AppResult<()>;
+
+    /// Write whatever is still buffered once the last batch has been seen.
+    fn finish<W: Write>(&mut self, out: &mut SqlScript<W>) -> AppResult<()>;
+
+    /// Whether the script ends with a newline after its last statement.
+    fn terminates_lines(&self) -> bool {
+        true
+    }
+}
+
+/// Stream `df` through `writer` into `script`, one RecordBatch at a time.
+pub async fn write_sql_statements<W: Write>(
+    df: DataFrame,
+    mut writer: impl StatementWriter,
+    mut script: SqlScript<W>,
+) -> AppResult<W> {
+    let mut stream = df
+        .execute_stream()
+        .await
+        .map_err(|e| AppError::BadRequest {
+            message: format!("Failed to execute DataFrame: {}", e),
+        })?;
+    while let Some(batch) = stream.next().await {
+        let batch = batch.map_err(|e| AppError::BadRequest {
+            message: format!("Failed to read DataFrame batch: {}", e),
+        })?;
+        writer.write_batch(&batch, &mut script)?;
+    }
+    writer.finish(&mut script)?;
+    script.finish(writer.terminates_lines())
+}
+
+/// Run `writer` over already collected batches and return the SQL as a String.
+pub fn write_sql_statements_to_string(
+    batches: &[RecordBatch],
+    mut writer: impl StatementWriter,
+    mut script: SqlScript<Vec<u8>>,
 ) -> AppResult<String> {
-    let headers = extract_headers_from_batches(&batches);
-    if headers.is_empty() {
-        return Ok(String::new());
+    for batch in batches {
+        writer.write_batch(batch, &mut script)?;
     }
+    writer.finish(&mut script)?;
+    let out = script.finish(writer.terminates_lines())?;
+    String::from_utf8(out).map_err(|e| AppError::InternalServer {
+        message: format!("Generated SQL is not valid UTF-8: {}", e),
+    })
+}
 
-    let export_specs = resolve_export_specs(&headers, export_columns)?;
+/// Multi-row INSERT statements of at most `max_values_per_insert` rows each,
+/// with a conflict clause (empty for a plain INSERT) appended to every one.
+pub struct InsertWriter<'a> {
+    db_dialect: &'a Dialect,
+    export_specs: Vec<ColumnExportSpec>,
+    empty_text_as_null: bool,
+    table: String,
+    columns: String,
+    conflict_clause: String,
+    chunk_limit: usize,
+    max_statement_bytes: Option<usize>,
+    pending_rows: Vec<Vec<String>>,
+    pending_bytes: usize,
+}
 
-    let mut sql_statements = String::new();
-    let columns = export_specs
-        .iter()
-        .map(|spec| match db_dialect {
-            Dialect::MySQL => format!("`{}`", spec.export_name),
-            Dialect::PostgreSQL => format!("\"{}\"", spec.export_name),
-        })
-        .collect::<Vec<String>>()
-        .join(", ");
-    let insert_header_template = match db_dialect {
-        Dialect::MySQL => format!("INSERT INTO `{}` ({}) VALUES\n", table_name, columns),
-        Dialect::PostgreSQL => format!("INSERT INTO \"{}\" ({}) VALUES\n", table_name, columns),
-    };
-    let chunk_limit = max_values_per_insert.max(1);
-    let mut pending_rows: Vec<Vec<String>> = Vec::with_capacity(chunk_limit);
-    let mut is_first_insert = true;
+impl<'a> InsertWriter<'a> {
+    pub fn new(
+        headers: &[String],
+        table_name: &str,
+        max_values_per_insert: usize,
+        db_dialect: &'a Dialect,
+        export_columns: Option<&[ExportColumnConfig]>,
+        empty_text_as_null: bool,
+    ) -> AppResult<Self> {
+        let export_specs = resolve_export_specs(headers, export_columns)?;
+        Ok(Self::with_specs(
+            table_name,
+            max_values_per_insert,
+            db_dialect,
+            export_specs,
+            empty_text_as_null,
+            String::new(),
+        ))
+    }
 
-    let mut flush_chunk = |rows: &mut Vec<Vec<String>>| -> AppResult<()> {
-        if rows.is_empty() {
-            return Ok(());
-        }
+    /// UPSERT flavour, see [`generate_sql_upsert_from_batches`].
+    pub fn upsert(
+        headers: &[String],
+        table_name: &str,
+        max_values_per_insert: usize,
+        conflict_columns: &[&str],
+        db_dialect: &'a Dialect,
+        export_columns: Option<&[ExportColumnConfig]>,
+        empty_text_as_null: bool,
+    ) -> AppResult<Self> {
+        let export_specs = resolve_export_specs(headers, export_columns)?;
+        let key_specs = resolve_where_column_specs(
+            "UPSERT",
+            headers,
+            &export_specs,
+            export_columns,
+            conflict_columns,
+        )?;
 
-        if !is_first_insert {
-            sql_statements.push('\n');
-        } else {
-            is_first_insert = false;
+        let key_names: Vec<String> = key_specs
+            .iter()
+            .map(|spec| quote_identifier(&spec.export_name, db_dialect))
+            .collect();
+        let update_names: Vec<String> = export_specs
+            .iter()
+            .filter(|spec| !key_specs.iter().any(|key| key.source_index == spec.source_index))
+            .map(|spec| quote_identifier(&spec.export_name, db_dialect))
+            .collect();
+
+        let mut insert_specs = export_specs;
+        for key_spec in key_specs {
+            if !insert_specs
+                .iter()
+                .any(|s| s.source_index == key_spec.source_index)
+            {
+                insert_specs.push(key_spec);
+            }
         }
 
-        sql_statements.push_str(&insert_header_template);
+        let conflict_clause = db_dialect
+            .strategy()
+            .upsert_clause(&key_names, &update_names)?;
 
-        let values_clauses = rows
+        Ok(Self::with_specs(
+            table_name,
+            max_values_per_insert,
+            db_dialect,
+            insert_specs,
+            empty_text_as_null,
+            conflict_clause,
+        ))
+    }
+
+    fn with_specs(
+        table_name: &str,
+        max_values_per_insert: usize,
+        db_dialect: &'a Dialect,
+        export_specs: Vec<ColumnExportSpec>,
+        empty_text_as_null: bool,
+        conflict_clause: String,
+    ) -> Self {
+        let columns = export_specs
             .iter()
-            .map(|row| format!("({})", row.join(", ")))
+            .map(|spec| quote_identifier(&spec.export_name, db_dialect))
             .collect::<Vec<String>>()
-            .join(",\n");
+            .join(", ");
+        let chunk_limit = match db_dialect.strategy().max_rows_per_insert() {
+            Some(dialect_limit) => max_values_per_insert.min(dialect_limit),
+            None => max_values_per_insert,
+        }
+        .max(1);
+        let mut writer = Self {
+            db_dialect,
+            export_specs,
+            empty_text_as_null,
+            table: quote_identifier(table_name, db_dialect),
+            columns,
+            conflict_clause,
+            chunk_limit,
+            max_statement_bytes: None,
+            pending_rows: Vec::with_capacity(chunk_limit),
+            pending_bytes: 0,
+        };
+        writer.pending_bytes = writer.statement_overhead();
+        writer
+    }
+
+    /// Also end a chunk before it grows past `max_statement_bytes`. A single
+    /// row larger than that still gets a statement of its own.
+    pub fn with_max_statement_bytes(mut self, max_statement_bytes: Option<usize>) -> Self {
+        self.max_statement_bytes = max_statement_bytes;
+        self
+    }
+
+    /// Bytes of an INSERT besides its rows.
+    fn statement_overhead(&self) -> usize {
+        self.db_dialect
+            .strategy()
+            .insert_statement_overhead(&self.table, &self.columns)
+            + self.conflict_clause.len()
+            + ";".len()
+    }
 
-        sql_statements.push_str(&values_clauses);
-        sql_statements.push(';');
-        rows.clear();
+    fn flush_chunk<W: Write>(&mut self, out: &mut SqlScript<W>) -> AppResult<()> {
+        if self.pending_rows.is_empty() {
+            return Ok(());
+        }
+
+        let statement = self.db_dialect.strategy().insert_statement(
+            &self.table,
+            &self.columns,
+            &self.pending_rows,
+        );
+        out.statement(&format!("{}{};", statement, self.conflict_clause))?;
+        self.pending_rows.clear();
+        self.pending_bytes = self.statement_overhead();
         Ok(())
-    };
+    }
+}
 
-    extract_rows_from_batches(batches, &export_specs, empty_text_as_null, |row| {
-        pending_rows.push(row);
-        if pending_rows.len() == chunk_limit {
-            flush_chunk(&mut pending_rows)?;
+impl StatementWriter for InsertWriter<'_> {
+    fn write_batch<W: Write>(
+        &mut self,
+        batch: &RecordBatch,
+        out: &mut SqlScript<W>,
+    ) -> AppResult<()> {
+        let rows = extract_rows_from_batch(
+            batch,
+            &self.export_specs,
+            self.empty_text_as_null,
+            self.db_dialect,
+        )?;
+        let row_overhead = self
+            .db_dialect
+            .strategy()
+            .insert_row_overhead(&self.table, &self.columns);
+        for row in rows {
+            // The literals joined by `, `, plus the dialect's per-row framing.
+            let row_bytes =
+                row.iter().map(|value| value.len() + 2).sum::<usize>().saturating_sub(2)
+                    + row_overhead;
+            if let Some(max_bytes) = self.max_statement_bytes {
+                if !self.pending_rows.is_empty() && self.pending_bytes + row_bytes > max_bytes {
+                    self.flush_chunk(out)?;
+                }
+            }
+            self.pending_rows.push(row);
+            self.pending_bytes += row_bytes;
+            if self.pending_rows.len() == self.chunk_limit {
+                self.flush_chunk(out)?;
+            }
         }
         Ok(())
-    })?;
-    flush_chunk(&mut pending_rows)?;
+    }
 
-    Ok(sql_statements)
+    fn finish<W: Write>(&mut self, out: &mut SqlScript<W>) -> AppResult<()> {
+        self.flush_chunk(out)
+    }
+
+    fn terminates_lines(&self) -> bool {
+        false
+    }
 }
 
-/// Generate SQL insert statements from DataFrame
-/// Splits large datasets into multiple INSERT statements for better performance
-pub async fn generate_sql_inserts(
-    df: DataFrame,
+/// Generate SQL insert statements from collected RecordBatches.
+pub fn generate_sql_inserts_from_batches(
+    batches: Vec<RecordBatch>,
     table_name: &str,
     max_values_per_insert: usize,
     db_dialect: &Dialect,
     export_columns: Option<&[ExportColumnConfig]>,
     empty_text_as_null: bool,
 ) -> AppResult<String> {
-    let batches = df
-        .collect()
-        .await
-        .map_err(|e| crate::context::error::AppError::BadRequest {
-            message: format!("Failed to collect DataFrame: {}", e),
-        })?;
-    generate_sql_inserts_from_batches(
-        batches,
+    let headers = extract_headers_from_batches(&batches);
+    if headers.is_empty() {
+        return Ok(String::new());
+    }
+
+    let writer = InsertWriter::new(
+        &headers,
         table_name,
         max_values_per_insert,
         db_dialect,
         export_columns,
         empty_text_as_null,
-    )
+    )?;
+    write_sql_statements_to_string(&batches, writer, SqlScript::plain(Vec::new()))
 }
 
-struct WhereColumnSpec {
-    source_index: usize,
-    export_name: String,
-    sql_type: SqlType,
+/// The WHERE columns picked for `statement`, trimmed and without blanks.
+pub fn where_column_refs<'c>(
+    where_columns: &'c [String],
+    statement: &str,
+) -> AppResult<Vec<&'c str>> {
+    let refs: Vec<&str> = where_columns
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if refs.is_empty() {
+        return Err(missing_where_columns(statement));
+    }
+    Ok(refs)
+}
+
+fn missing_where_columns(statement: &str) -> AppError {
+    AppError::BadRequest {
+        message: format!(
+            "At least one WHERE column is required for {} statements",
+            statement
+        ),
+    }
 }
 
 fn resolve_where_column_specs(
+    statement: &str,
     headers: &[String],
     export_specs: &[ColumnExportSpec],
     export_columns: Option<&[ExportColumnConfig]>,
     where_columns: &[&str],
-) -> AppResult<Vec<WhereColumnSpec>> {
+) -> AppResult<Vec<ColumnExportSpec>> {
     if where_columns.is_empty() {
-        return Err(crate::context::error::AppError::BadRequest {
-            message: "At least one WHERE column is required for UPDATE statements".to_string(),
-        });
+        return Err(missing_where_columns(statement));
     }
 
     let mut seen = std::collections::HashSet::new();
@@ -413,7 +961,7 @@ fn resolve_where_column_specs(
 
     for where_column in where_columns {
         if !seen.insert(*where_column) {
-            return Err(crate::context::error::AppError::BadRequest {
+            return Err(AppError::BadRequest {
                 message: format!("Duplicate WHERE column '{}'", where_column),
             });
         }
@@ -421,17 +969,19 @@ fn resolve_where_column_specs(
         let source_index = headers
             .iter()
             .position(|h| h == where_column)
-            .ok_or_else(|| crate::context::error::AppError::BadRequest {
+            .ok_or_else(|| AppError::BadRequest {
                 message: format!("WHERE column '{}' not found in data", where_column),
             })?;
 
-        let sql_type = export_columns
-            .and_then(|cols| {
-                cols.iter()
-                    .find(|c| c.source_column_name == *where_column)
-                    .map(|c| parse_sql_type(&c.sql_type))
-            })
-            .unwrap_or(SqlType::Text);
+        let config = export_columns
+            .and_then(|cols| cols.iter().find(|c| c.source_column_name == *where_column));
+        let sql_type = config
+            .map(|c| parse_sql_type(&c.sql_type))
+            .unwrap_or(SqlType::Unknown);
+        let timezone = match config {
+            Some(config) => parse_timezone(config)?,
+            None => None,
+        };
 
         let export_name = export_specs
             .iter()
@@ -439,16 +989,136 @@ fn resolve_where_column_specs(
             .map(|spec| spec.export_name.clone())
             .unwrap_or_else(|| where_column.to_string());
 
-        specs.push(WhereColumnSpec {
+        specs.push(ColumnExportSpec {
             source_index,
             export_name,
             sql_type,
+            timezone,
         });
     }
 
     Ok(specs)
 }
 
+/// One UPDATE statement per row, matched on the WHERE columns.
+pub struct UpdateWriter<'a> {
+    db_dialect: &'a Dialect,
+    empty_text_as_null: bool,
+    table: String,
+    extract_specs: Vec<ColumnExportSpec>,
+    where_specs: Vec<ColumnExportSpec>,
+    exported_source_indices: HashSet<usize>,
+    where_source_indices: HashSet<usize>,
+}
+
+impl<'a> UpdateWriter<'a> {
+    pub fn new(
+        headers: &[String],
+        table_name: &str,
+        where_columns: &[&str],
+        db_dialect: &'a Dialect,
+        export_columns: Option<&[ExportColumnConfig]>,
+        empty_text_as_null: bool,
+    ) -> AppResult<Self> {
+        let export_specs = resolve_export_specs(headers, export_columns)?;
+        let where_specs = resolve_where_column_specs(
+            "UPDATE",
+            headers,
+            &export_specs,
+            export_columns,
+            where_columns,
+        )?;
+
+        let where_source_indices: HashSet<usize> =
+            where_specs.iter().map(|spec| spec.source_index).collect();
+        let exported_source_indices: HashSet<usize> =
+            export_specs.iter().map(|s| s.source_index).collect();
+
+        let mut extract_specs = export_specs;
+        for where_spec in &where_specs {
+            if !extract_specs
+                .iter()
+                .any(|s| s.source_index == where_spec.source_index)
+            {
+                extract_specs.push(where_spec.clone());
+            }
+        }
+
+        Ok(Self {
+            db_dialect,
+            empty_text_as_null,
+            table: quote_identifier(table_name, db_dialect),
+            extract_specs,
+            where_specs,
+            exported_source_indices,
+            where_source_indices,
+        })
+    }
+}
+
+impl StatementWriter for UpdateWriter<'_> {
+    fn write_batch<W: Write>(
+        &mut self,
+        batch: &RecordBatch,
+        out: &mut SqlScript<W>,
+    ) -> AppResult<()> {
+        let db_dialect = self.db_dialect;
+        let rows = extract_rows_from_batch(
+            batch,
+            &self.extract_specs,
+            self.empty_text_as_null,
+            db_dialect,
+        )?;
+
+        'rows: for row in rows {
+            let mut set_clauses = Vec::new();
+            let mut values_by_index = HashMap::new();
+
+            for (spec, value) in self.extract_specs.iter().zip(row.iter()) {
+                values_by_index.insert(spec.source_index, value);
+                if self.exported_source_indices.contains(&spec.source_index)
+                    && !self.where_source_indices.contains(&spec.source_index)
+                {
+                    set_clauses.push(format!(
+                        "{} = {}",
+                        quote_identifier(&spec.export_name, db_dialect),
+                        value
+                    ));
+                }
+            }
+
+            if set_clauses.is_empty() {
+                continue;
+            }
+
+            let mut where_clauses = Vec::with_capacity(self.where_specs.len());
+            for where_spec in &self.where_specs {
+                let Some(value) = values_by_index.get(&where_spec.source_index) else {
+                    continue 'rows;
+                };
+                where_clauses.push(format!(
+                    "{} = {}",
+                    quote_identifier(&where_spec.export_name, db_dialect),
+                    value
+                ));
+            }
+
+            out.statement(&format!(
+                "UPDATE {} SET {} WHERE {};",
+                self.table,
+                set_clauses.join(", "),
+                where_clauses.join(" AND ")
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    fn finish<W: Write>(&mut self, _out: &mut SqlScript<W>) -> AppResult<()> {
+        Ok(())
+    }
+}
+
 /// Generate SQL UPDATE statements from collected RecordBatches.
 pub fn generate_sql_update_from_batches(
     batches: Vec<RecordBatch>,
@@ -464,113 +1134,217 @@ pub fn generate_sql_update_from_batches(
         return Ok(String::new());
     }
 
-    let export_specs = resolve_export_specs(&headers, export_columns)?;
-    let where_specs = resolve_where_column_specs(
+    let writer = UpdateWriter::new(
         &headers,
-        &export_specs,
-        export_columns,
+        table_name,
         where_columns,
+        db_dialect,
+        export_columns,
+        empty_text_as_null,
     )?;
+    write_sql_statements_to_string(&batches, writer, SqlScript::plain(Vec::new()))
+}
 
-    let where_source_indices: std::collections::HashSet<usize> =
-        where_specs.iter().map(|spec| spec.source_index).collect();
-
-    let mut extract_specs = export_specs.clone();
-    for where_spec in &where_specs {
-        if !extract_specs
-            .iter()
-            .any(|s| s.source_index == where_spec.source_index)
-        {
-            extract_specs.push(ColumnExportSpec {
-                source_index: where_spec.source_index,
-                export_name: where_spec.export_name.clone(),
-                sql_type: where_spec.sql_type,
-            });
-        }
+/// Generate batched UPSERT statements from collected RecordBatches.
+///
+/// `conflict_columns` name the unique key (they are added to the inserted
+/// columns if not exported) and are excluded from the update list. MySQL
+/// emits `ON DUPLICATE KEY UPDATE`, which fires on any unique key of the
+/// table; PostgreSQL and SQLite emit `ON CONFLICT (...) DO UPDATE` on exactly
+/// those columns, or `DO NOTHING` when every exported column is part of the
+/// key. Other dialects have no single-statement upsert and are rejected.
+pub fn generate_sql_upsert_from_batches(
+    batches: Vec<RecordBatch>,
+    table_name: &str,
+    max_values_per_insert: usize,
+    conflict_columns: &[&str],
+    db_dialect: &Dialect,
+    export_columns: Option<&[ExportColumnConfig]>,
+    empty_text_as_null: bool,
+) -> AppResult<String> {
+    let headers = extract_headers_from_batches(&batches);
+    if headers.is_empty() {
+        return Ok(String::new());
     }
 
-    let exported_source_indices: std::collections::HashSet<usize> =
-        export_specs.iter().map(|s| s.source_index).collect();
+    let writer = InsertWriter::upsert(
+        &headers,
+        table_name,
+        max_values_per_insert,
+        conflict_columns,
+        db_dialect,
+        export_columns,
+        empty_text_as_null,
+    )?;
+    write_sql_statements_to_string(&batches, writer, SqlScript::plain(Vec::new()))
+}
+
+/// DELETE statements matched on key columns, see
+/// [`generate_sql_delete_from_batches`].
+pub struct DeleteWriter<'a> {
+    db_dialect: &'a Dialect,
+    empty_text_as_null: bool,
+    table: String,
+    key_specs: Vec<ColumnExportSpec>,
+    key_names: Vec<String>,
+    key_list: String,
+    row_value_in: bool,
+    chunk_limit: Option<usize>,
+    pending_keys: Vec<String>,
+}
 
-    let mut sql_statements = String::new();
+impl<'a> DeleteWriter<'a> {
+    pub fn new(
+        headers: &[String],
+        table_name: &str,
+        key_columns: &[&str],
+        max_keys_per_delete: Option<usize>,
+        db_dialect: &'a Dialect,
+        export_columns: Option<&[ExportColumnConfig]>,
+        empty_text_as_null: bool,
+    ) -> AppResult<Self> {
+        let export_specs = resolve_export_specs(headers, export_columns)?;
+        let key_specs = resolve_where_column_specs(
+            "DELETE",
+            headers,
+            &export_specs,
+            export_columns,
+            key_columns,
+        )?;
 
-    extract_rows_from_batches(batches, &extract_specs, empty_text_as_null, |row| {
-        let mut set_clauses = Vec::new();
-        let mut values_by_index = std::collections::HashMap::new();
+        let key_names: Vec<String> = key_specs
+            .iter()
+            .map(|spec| quote_identifier(&spec.export_name, db_dialect))
+            .collect();
+        let key_list = if key_names.len() == 1 {
+            key_names[0].clone()
+        } else {
+            format!("({})", key_names.join(", "))
+        };
+        // Composite keys are OR-ed conjunctions where row values aren't supported.
+        let row_value_in = key_names.len() == 1 || db_dialect.strategy().supports_row_value_in();
 
-        for (spec, value) in extract_specs.iter().zip(row.iter()) {
-            values_by_index.insert(spec.source_index, value.clone());
-            if exported_source_indices.contains(&spec.source_index)
-                && !where_source_indices.contains(&spec.source_index)
-            {
-                let export_name = &spec.export_name;
-                set_clauses.push(match db_dialect {
-                    Dialect::MySQL => format!("`{}` = {}", export_name, value),
-                    Dialect::PostgreSQL => format!("\"{}\" = {}", export_name, value),
-                });
-            }
-        }
+        Ok(Self {
+            db_dialect,
+            empty_text_as_null,
+            table: quote_identifier(table_name, db_dialect),
+            key_specs,
+            key_names,
+            key_list,
+            row_value_in,
+            chunk_limit: max_keys_per_delete.map(|limit| limit.max(1)),
+            pending_keys: Vec::new(),
+        })
+    }
+
+    fn row_conditions(&self, row: &[String]) -> String {
+        self.key_names
+            .iter()
+            .zip(row.iter())
+            .map(|(name, value)| {
+                if value == "NULL" {
+                    format!("{} IS NULL", name)
+                } else {
+                    format!("{} = {}", name, value)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" AND ")
+    }
 
-        if set_clauses.is_empty() {
+    fn flush_chunk<W: Write>(&mut self, out: &mut SqlScript<W>) -> AppResult<()> {
+        if self.pending_keys.is_empty() {
             return Ok(());
         }
+        let condition = if self.row_value_in {
+            format!("{} IN ({})", self.key_list, self.pending_keys.join(", "))
+        } else {
+            self.pending_keys.join(" OR ")
+        };
+        out.statement(&format!("DELETE FROM {} WHERE {};", self.table, condition))?;
+        self.pending_keys.clear();
+        Ok(())
+    }
+}
 
-        let mut where_clauses = Vec::with_capacity(where_specs.len());
-        for where_spec in &where_specs {
-            let Some(value) = values_by_index.get(&where_spec.source_index) else {
-                return Ok(());
-            };
-            where_clauses.push(match db_dialect {
-                Dialect::MySQL => format!("`{}` = {}", where_spec.export_name, value),
-                Dialect::PostgreSQL => format!("\"{}\" = {}", where_spec.export_name, value),
-            });
-        }
+impl StatementWriter for DeleteWriter<'_> {
+    fn write_batch<W: Write>(
+        &mut self,
+        batch: &RecordBatch,
+        out: &mut SqlScript<W>,
+    ) -> AppResult<()> {
+        let rows = extract_rows_from_batch(
+            batch,
+            &self.key_specs,
+            self.empty_text_as_null,
+            self.db_dialect,
+        )?;
 
-        let where_clause = where_clauses.join(" AND ");
-        let update_statement = match db_dialect {
-            Dialect::MySQL => format!(
-                "UPDATE `{}` SET {} WHERE {};\n",
-                table_name,
-                set_clauses.join(", "),
-                where_clause
-            ),
-            Dialect::PostgreSQL => format!(
-                "UPDATE \"{}\" SET {} WHERE {};\n",
-                table_name,
-                set_clauses.join(", "),
-                where_clause
-            ),
-        };
-        sql_statements.push_str(&update_statement);
+        for row in rows {
+            let has_null = row.iter().any(|value| value == "NULL");
+            match self.chunk_limit {
+                Some(limit) if !has_null => {
+                    let key = if row.len() == 1 {
+                        row[0].clone()
+                    } else if self.row_value_in {
+                        format!("({})", row.join(", "))
+                    } else {
+                        format!("({})", self.row_conditions(&row))
+                    };
+                    self.pending_keys.push(key);
+                    if self.pending_keys.len() == limit {
+                        self.flush_chunk(out)?;
+                    }
+                }
+                _ => {
+                    out.statement(&format!(
+                        "DELETE FROM {} WHERE {};",
+                        self.table,
+                        self.row_conditions(&row)
+                    ))?;
+                }
+            }
+        }
 
         Ok(())
-    })?;
+    }
 
-    Ok(sql_statements)
+    fn finish<W: Write>(&mut self, out: &mut SqlScript<W>) -> AppResult<()> {
+        self.flush_chunk(out)
+    }
 }
 
-/// Generate SQL UPDATE statements from DataFrame
-/// Creates UPDATE statements with WHERE conditions based on the specified columns
-pub async fn generate_sql_update(
-    df: DataFrame,
+/// Generate DELETE statements for the rows of collected RecordBatches,
+/// matched on `key_columns`.
+///
+/// With `max_keys_per_delete` unset, one `DELETE ... WHERE k = v` is emitted
+/// per row; otherwise rows are grouped into `WHERE k IN (...)` (or
+/// `WHERE (k1, k2) IN ((...), ...)`, which SQLite and SQL Server spell as
+/// `(k1 = .. AND k2 = ..) OR ...`) chunks of at most that many keys. Keys
+/// containing NULL never match `=` or `IN`, so those rows always get their
+/// own statement using `IS NULL`.
+pub fn generate_sql_delete_from_batches(
+    batches: Vec<RecordBatch>,
     table_name: &str,
-    where_columns: &[&str],
+    key_columns: &[&str],
+    max_keys_per_delete: Option<usize>,
     db_dialect: &Dialect,
     export_columns: Option<&[ExportColumnConfig]>,
     empty_text_as_null: bool,
 ) -> AppResult<String> {
-    let batches = df
-        .collect()
-        .await
-        .map_err(|e| crate::context::error::AppError::BadRequest {
-            message: format!("Failed to collect DataFrame: {}", e),
-        })?;
-    generate_sql_update_from_batches(
-        batches,
+    let headers = extract_headers_from_batches(&batches);
+    if headers.is_empty() {
+        return Ok(String::new());
+    }
+
+    let writer = DeleteWriter::new(
+        &headers,
         table_name,
-        where_columns,
+        key_columns,
+        max_keys_per_delete,
         db_dialect,
         export_columns,
         empty_text_as_null,
-    )
+    )?;
+    write_sql_statements_to_string(&batches, writer, SqlScript::plain(Vec::new()))
 }
@@ -1,17 +1,51 @@
 use super::generator::{
-    format_bool_for_sql, format_cell_for_sql, format_value_for_sql,
-    generate_sql_inserts_from_batches, generate_sql_update_from_batches, parse_sql_type,
-    resolve_export_specs, strip_float_zero_suffix, truncate_record_batches, ExportColumnConfig,
-    SqlType,
+    format_array_value_for_sql,
+    generate_sql_delete_from_batches, generate_sql_inserts_from_batches, generate_sql_update_from_batches,
+    generate_sql_upsert_from_batches, parse_sql_type,
+    resolve_export_specs, schema_headers, truncate_record_batches, where_column_refs,
+    write_sql_statements, ExportColumnConfig, InsertWriter, SqlType, StatementWriter,
 };
+use super::script::SqlScript;
 use crate::commands::query::Dialect;
 use datafusion::arrow::array::{
-    BooleanArray, Float64Array, Int32Array, Int64Array, StringArray, TimestampNanosecondArray,
+    Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array,
+    Float64Array, Int32Array, Int64Array, StringArray, TimestampNanosecondArray,
+    TimestampSecondArray, UInt64Array,
 };
 use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use datafusion::prelude::SessionContext;
 use std::sync::Arc;
 
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn cell(array: &dyn Array, row: usize, col_type: SqlType, dialect: &Dialect) -> String {
+    let formatter = ArrayFormatter::try_new(array, &FormatOptions::default()).unwrap();
+    format_array_value_for_sql(array, &formatter, row, col_type, None, false, dialect)
+}
+
+/// A Utf8 cell holding `value`, formatted for MySQL.
+fn text_cell(value: &str, col_type: SqlType, empty_text_as_null: bool) -> String {
+    let array = StringArray::from(vec![value]);
+    let formatter = ArrayFormatter::try_new(&array, &FormatOptions::default()).unwrap();
+    format_array_value_for_sql(
+        &array,
+        &formatter,
+        0,
+        col_type,
+        None,
+        empty_text_as_null,
+        &Dialect::MySQL,
+    )
+}
+
+fn null_text_cell(col_type: SqlType) -> String {
+    cell(&StringArray::from(vec![None::<&str>]), 0, col_type, &Dialect::MySQL)
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Stability Tests
 // ═══════════════════════════════════════════════════════════════════════
@@ -47,8 +81,6 @@ fn test_parse_sql_type_int4_and_float8() {
 fn test_parse_sql_type_float_variants() {
     assert_eq!(parse_sql_type("DOUBLE"), SqlType::Float);
     assert_eq!(parse_sql_type("FLOAT"), SqlType::Float);
-    assert_eq!(parse_sql_type("DECIMAL"), SqlType::Float);
-    assert_eq!(parse_sql_type("NUMERIC"), SqlType::Float);
     assert_eq!(parse_sql_type("REAL"), SqlType::Float);
     assert_eq!(parse_sql_type("DOUBLE PRECISION"), SqlType::Float);
     assert_eq!(parse_sql_type("FLOAT8"), SqlType::Float);
@@ -67,12 +99,22 @@ fn test_parse_sql_type_text_variants() {
 #[test]
 fn test_parse_sql_type_unknown() {
     assert_eq!(parse_sql_type(""), SqlType::Unknown);
-    assert_eq!(parse_sql_type("DATE"), SqlType::Unknown);
-    assert_eq!(parse_sql_type("TIMESTAMP"), SqlType::Unknown);
-    assert_eq!(parse_sql_type("BLOB"), SqlType::Unknown);
     assert_eq!(parse_sql_type("JSON"), SqlType::Unknown);
 }
 
+#[test]
+fn test_parse_sql_type_typed_variants() {
+    assert_eq!(parse_sql_type("DECIMAL(10, 2)"), SqlType::Decimal);
+    assert_eq!(parse_sql_type("NUMERIC"), SqlType::Decimal);
+    assert_eq!(parse_sql_type("NUMBER"), SqlType::Decimal);
+    assert_eq!(parse_sql_type("DATE"), SqlType::Date);
+    assert_eq!(parse_sql_type("DATETIME(6)"), SqlType::Timestamp);
+    assert_eq!(parse_sql_type("timestamp"), SqlType::Timestamp);
+    assert_eq!(parse_sql_type("BLOB"), SqlType::Binary);
+    assert_eq!(parse_sql_type("VARBINARY(16)"), SqlType::Binary);
+    assert_eq!(parse_sql_type("BYTEA"), SqlType::Binary);
+}
+
 // ─── resolve_export_specs ─────────────────────────────────────────────
 
 #[test]
@@ -96,11 +138,13 @@ fn test_resolve_export_specs_with_config() {
             source_column_name: "id".to_string(),
             export_column_name: "user_id".to_string(),
             sql_type: "INT".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "name".to_string(),
             export_column_name: "user_name".to_string(),
             sql_type: "VARCHAR".to_string(),
+            timezone: None,
         },
     ];
     let specs = resolve_export_specs(&headers, Some(&config)).unwrap();
@@ -118,6 +162,7 @@ fn test_resolve_export_specs_rejects_empty_export_name() {
         source_column_name: "id".to_string(),
         export_column_name: "  ".to_string(),
         sql_type: "INT".to_string(),
+        timezone: None,
     }];
     assert!(resolve_export_specs(&headers, Some(&config)).is_err());
 }
@@ -130,11 +175,13 @@ fn test_resolve_export_specs_rejects_duplicate_export_names() {
             source_column_name: "a".to_string(),
             export_column_name: "col".to_string(),
             sql_type: "INT".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "b".to_string(),
             export_column_name: "col".to_string(),
             sql_type: "TEXT".to_string(),
+            timezone: None,
         },
     ];
     let result = resolve_export_specs(&headers, Some(&config));
@@ -154,6 +201,7 @@ fn test_resolve_export_specs_rejects_missing_source_column() {
         source_column_name: "nonexistent".to_string(),
         export_column_name: "col".to_string(),
         sql_type: "INT".to_string(),
+        timezone: None,
     }];
     assert!(resolve_export_specs(&headers, Some(&config)).is_err());
 }
@@ -172,6 +220,7 @@ fn test_resolve_export_specs_trims_export_name() {
         source_column_name: "id".to_string(),
         export_column_name: "  user_id  ".to_string(),
         sql_type: "INT".to_string(),
+        timezone: None,
     }];
     let specs = resolve_export_specs(&headers, Some(&config)).unwrap();
     assert_eq!(specs[0].export_name, "user_id");
@@ -185,11 +234,13 @@ fn test_resolve_export_specs_allows_same_source_different_export() {
             source_column_name: "a".to_string(),
             export_column_name: "col_a".to_string(),
             sql_type: "INT".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "b".to_string(),
             export_column_name: "col_b".to_string(),
             sql_type: "TEXT".to_string(),
+            timezone: None,
         },
     ];
     let specs = resolve_export_specs(&headers, Some(&config)).unwrap();
@@ -198,165 +249,132 @@ fn test_resolve_export_specs_allows_same_source_different_export() {
     assert_eq!(specs[1].export_name, "col_b");
 }
 
-// ─── format_bool_for_sql ──────────────────────────────────────────────
+// ─── format_array_value_for_sql: text under a bool override ──────────
 
 #[test]
-fn test_format_bool_for_sql_known_values() {
-    assert_eq!(format_bool_for_sql("true"), "true");
-    assert_eq!(format_bool_for_sql("false"), "false");
-    assert_eq!(format_bool_for_sql("TRUE"), "true");
-    assert_eq!(format_bool_for_sql("FALSE"), "false");
-    assert_eq!(format_bool_for_sql("1"), "true");
-    assert_eq!(format_bool_for_sql("0"), "false");
-    assert_eq!(format_bool_for_sql("NULL"), "NULL");
+fn test_text_as_bool_known_values() {
+    assert_eq!(text_cell("true", SqlType::Bool, false), "true");
+    assert_eq!(text_cell("false", SqlType::Bool, false), "false");
+    assert_eq!(text_cell("TRUE", SqlType::Bool, false), "true");
+    assert_eq!(text_cell("FALSE", SqlType::Bool, false), "false");
+    assert_eq!(text_cell("1", SqlType::Bool, false), "true");
+    assert_eq!(text_cell("0", SqlType::Bool, false), "false");
+    assert_eq!(null_text_cell(SqlType::Bool), "NULL");
 }
 
 #[test]
-fn test_format_bool_for_sql_unrecognized_returns_null() {
+fn test_text_as_bool_unrecognized_returns_null() {
     // Previously returned a quoted string which caused PostgreSQL type mismatch
-    assert_eq!(format_bool_for_sql("yes"), "NULL");
-    assert_eq!(format_bool_for_sql("no"), "NULL");
-    assert_eq!(format_bool_for_sql("2"), "NULL");
-    assert_eq!(format_bool_for_sql("random"), "NULL");
-}
-
-// ─── format_cell_for_sql: boolean-to-numeric conversion ──────────────
-
-#[test]
-fn test_format_cell_bool_to_int() {
-    assert_eq!(format_cell_for_sql("true", SqlType::Int, false), "1");
-    assert_eq!(format_cell_for_sql("false", SqlType::Int, false), "0");
+    assert_eq!(text_cell("yes", SqlType::Bool, false), "NULL");
+    assert_eq!(text_cell("no", SqlType::Bool, false), "NULL");
+    assert_eq!(text_cell("2", SqlType::Bool, false), "NULL");
+    assert_eq!(text_cell("random", SqlType::Bool, false), "NULL");
+    assert_eq!(text_cell("maybe", SqlType::Bool, false), "NULL");
 }
 
 #[test]
-fn test_format_cell_bool_to_float() {
-    assert_eq!(format_cell_for_sql("true", SqlType::Float, false), "1");
-    assert_eq!(format_cell_for_sql("false", SqlType::Float, false), "0");
-}
-
-// ─── format_cell_for_sql: integer type ────────────────────────────────
+fn test_bool_literals_follow_dialect() {
+    let sqlite = Dialect::SQLite;
+    let booleans = BooleanArray::from(vec![true, false]);
+    let ints = Int32Array::from(vec![1]);
+    let text = StringArray::from(vec!["true"]);
 
-#[test]
-fn test_format_cell_int_type() {
-    assert_eq!(format_cell_for_sql("42", SqlType::Int, false), "42");
-    assert_eq!(format_cell_for_sql("-5", SqlType::Int, false), "-5");
-    assert_eq!(format_cell_for_sql("3.7", SqlType::Int, false), "3"); // truncates float
-    assert_eq!(format_cell_for_sql("NULL", SqlType::Int, false), "NULL");
+    assert_eq!(cell(&booleans, 0, SqlType::Unknown, &sqlite), "1");
+    assert_eq!(cell(&booleans, 1, SqlType::Bool, &sqlite), "0");
+    assert_eq!(cell(&ints, 0, SqlType::Bool, &sqlite), "1");
+    assert_eq!(cell(&text, 0, SqlType::Bool, &sqlite), "1");
+    // Text that merely reads "true" stays text.
+    assert_eq!(cell(&text, 0, SqlType::Unknown, &sqlite), "'true'");
 }
 
-#[test]
-fn test_format_cell_int_large_unsigned() {
-    assert_eq!(
-        format_cell_for_sql("18446744073709551615", SqlType::Int, false),
-        "18446744073709551615"
-    );
-}
+// ─── format_array_value_for_sql: text under a numeric override ───────
 
 #[test]
-fn test_format_cell_int_non_numeric_returns_null() {
-    // Regression: previously produced quoted string like 'hello' which is invalid SQL for INT columns
-    assert_eq!(format_cell_for_sql("hello", SqlType::Int, false), "NULL");
-    assert_eq!(format_cell_for_sql("N/A", SqlType::Int, false), "NULL");
-    assert_eq!(format_cell_for_sql("yes", SqlType::Int, false), "NULL");
-    assert_eq!(format_cell_for_sql("abc123", SqlType::Int, false), "NULL");
+fn test_text_bool_to_int() {
+    assert_eq!(text_cell("true", SqlType::Int, false), "1");
+    assert_eq!(text_cell("false", SqlType::Int, false), "0");
 }
 
-// ─── format_cell_for_sql: float type ──────────────────────────────────
-
 #[test]
-fn test_format_cell_float_type() {
-    assert_eq!(format_cell_for_sql("3.14", SqlType::Float, false), "3.14");
-    assert_eq!(format_cell_for_sql("42", SqlType::Float, false), "42");
-    assert_eq!(format_cell_for_sql("NULL", SqlType::Float, false), "NULL");
+fn test_text_bool_to_float() {
+    assert_eq!(text_cell("true", SqlType::Float, false), "1");
+    assert_eq!(text_cell("false", SqlType::Float, false), "0");
 }
 
 #[test]
-fn test_format_cell_float_non_numeric_returns_null() {
-    // Regression: previously produced quoted string like 'hello' which is invalid SQL for FLOAT columns
-    assert_eq!(format_cell_for_sql("hello", SqlType::Float, false), "NULL");
-    assert_eq!(format_cell_for_sql("N/A", SqlType::Float, false), "NULL");
-    assert_eq!(format_cell_for_sql("yes", SqlType::Float, false), "NULL");
-    assert_eq!(format_cell_for_sql("abc123", SqlType::Float, false), "NULL");
+fn test_text_as_int() {
+    assert_eq!(text_cell("42", SqlType::Int, false), "42");
+    assert_eq!(text_cell("-5", SqlType::Int, false), "-5");
+    assert_eq!(text_cell("3.7", SqlType::Int, false), "3"); // truncates float
+    assert_eq!(null_text_cell(SqlType::Int), "NULL");
 }
 
 #[test]
-fn test_format_cell_non_finite_float_returns_null() {
-    // NaN and Infinity are invalid SQL literals; must return NULL instead
-    assert_eq!(format_cell_for_sql("NaN", SqlType::Float, false), "NULL");
-    assert_eq!(format_cell_for_sql("inf", SqlType::Float, false), "NULL");
-    assert_eq!(format_cell_for_sql("-inf", SqlType::Float, false), "NULL");
-    assert_eq!(format_cell_for_sql("infinity", SqlType::Float, false), "NULL");
-    assert_eq!(format_cell_for_sql("-infinity", SqlType::Float, false), "NULL");
+fn test_text_as_int_large_unsigned() {
+    assert_eq!(
+        text_cell("18446744073709551615", SqlType::Int, false),
+        "18446744073709551615"
+    );
 }
 
 #[test]
-fn test_format_cell_non_finite_int_returns_null() {
-    // NaN and Infinity must not be silently cast to 0 or i64::MAX
-    assert_eq!(format_cell_for_sql("NaN", SqlType::Int, false), "NULL");
-    assert_eq!(format_cell_for_sql("inf", SqlType::Int, false), "NULL");
-    assert_eq!(format_cell_for_sql("-inf", SqlType::Int, false), "NULL");
-    assert_eq!(format_cell_for_sql("infinity", SqlType::Int, false), "NULL");
-    assert_eq!(format_cell_for_sql("-infinity", SqlType::Int, false), "NULL");
+fn test_text_as_int_non_numeric_returns_null() {
+    assert_eq!(text_cell("hello", SqlType::Int, false), "NULL");
+    assert_eq!(text_cell("N/A", SqlType::Int, false), "NULL");
+    assert_eq!(text_cell("yes", SqlType::Int, false), "NULL");
+    assert_eq!(text_cell("abc123", SqlType::Int, false), "NULL");
 }
 
-// ─── format_cell_for_sql: bool type ───────────────────────────────────
-
 #[test]
-fn test_format_cell_bool_type() {
-    assert_eq!(format_cell_for_sql("true", SqlType::Bool, false), "true");
-    assert_eq!(format_cell_for_sql("false", SqlType::Bool, false), "false");
-    assert_eq!(format_cell_for_sql("1", SqlType::Bool, false), "true");
-    assert_eq!(format_cell_for_sql("0", SqlType::Bool, false), "false");
-    assert_eq!(format_cell_for_sql("NULL", SqlType::Bool, false), "NULL");
+fn test_text_as_float() {
+    assert_eq!(text_cell("3.14", SqlType::Float, false), "3.14");
+    assert_eq!(text_cell("42", SqlType::Float, false), "42");
+    assert_eq!(null_text_cell(SqlType::Float), "NULL");
 }
 
 #[test]
-fn test_format_cell_bool_type_unrecognized_returns_null() {
-    assert_eq!(format_cell_for_sql("maybe", SqlType::Bool, false), "NULL");
-    assert_eq!(format_cell_for_sql("2", SqlType::Bool, false), "NULL");
+fn test_text_as_float_non_numeric_returns_null() {
+    assert_eq!(text_cell("hello", SqlType::Float, false), "NULL");
+    assert_eq!(text_cell("N/A", SqlType::Float, false), "NULL");
+    assert_eq!(text_cell("yes", SqlType::Float, false), "NULL");
+    assert_eq!(text_cell("abc123", SqlType::Float, false), "NULL");
 }
 
-// ─── format_cell_for_sql: text type ───────────────────────────────────
-
 #[test]
-fn test_format_cell_text_type() {
-    assert_eq!(format_cell_for_sql("hello", SqlType::Text, false), "'hello'");
-    assert_eq!(format_cell_for_sql("it's", SqlType::Text, false), "'it''s'"); // escapes quotes
-    assert_eq!(format_cell_for_sql("NULL", SqlType::Text, false), "NULL");
+fn test_text_non_finite_float_returns_null() {
+    assert_eq!(text_cell("NaN", SqlType::Float, false), "NULL");
+    assert_eq!(text_cell("inf", SqlType::Float, false), "NULL");
+    assert_eq!(text_cell("-inf", SqlType::Float, false), "NULL");
+    assert_eq!(text_cell("infinity", SqlType::Float, false), "NULL");
+    assert_eq!(text_cell("-infinity", SqlType::Float, false), "NULL");
 }
 
-// ─── format_cell_for_sql: unknown type ────────────────────────────────
-
 #[test]
-fn test_format_cell_unknown_type() {
-    assert_eq!(format_cell_for_sql("42", SqlType::Unknown, false), "42");
-    assert_eq!(format_cell_for_sql("3.14", SqlType::Unknown, false), "3.14");
-    assert_eq!(format_cell_for_sql("true", SqlType::Unknown, false), "true");
-    assert_eq!(format_cell_for_sql("false", SqlType::Unknown, false), "false");
-    assert_eq!(format_cell_for_sql("hello", SqlType::Unknown, false), "'hello'");
-    assert_eq!(format_cell_for_sql("NULL", SqlType::Unknown, false), "NULL");
+fn test_text_non_finite_int_returns_null() {
+    assert_eq!(text_cell("NaN", SqlType::Int, false), "NULL");
+    assert_eq!(text_cell("inf", SqlType::Int, false), "NULL");
+    assert_eq!(text_cell("-inf", SqlType::Int, false), "NULL");
+    assert_eq!(text_cell("infinity", SqlType::Int, false), "NULL");
+    assert_eq!(text_cell("-infinity", SqlType::Int, false), "NULL");
 }
 
-// ─── strip_float_zero_suffix ──────────────────────────────────────────
+// ─── format_array_value_for_sql: text kept as text ────────────────────
 
 #[test]
-fn test_strip_float_zero_suffix() {
-    assert_eq!(strip_float_zero_suffix("42.0"), "42");
-    assert_eq!(strip_float_zero_suffix("3.14"), "3.14");
-    assert_eq!(strip_float_zero_suffix("hello"), "hello");
-    assert_eq!(strip_float_zero_suffix("-1.0"), "-1");
+fn test_text_as_text() {
+    assert_eq!(text_cell("hello", SqlType::Text, false), "'hello'");
+    assert_eq!(text_cell("it's", SqlType::Text, false), "'it''s'"); // escapes quotes
+    assert_eq!(text_cell("NULL", SqlType::Text, false), "'NULL'");
+    assert_eq!(null_text_cell(SqlType::Text), "NULL");
 }
 
-// ─── format_value_for_sql ─────────────────────────────────────────────
-
 #[test]
-fn test_format_value_for_sql() {
-    assert_eq!(format_value_for_sql("hello", false), "'hello'");
-    assert_eq!(format_value_for_sql("it's", false), "'it''s'");
-    assert_eq!(format_value_for_sql("NULL", false), "NULL");
-    // strip_float_suffix strips ".0" but still wraps in quotes (this is a string formatter)
-    assert_eq!(format_value_for_sql("42.0", true), "'42'");
-    assert_eq!(format_value_for_sql("3.14", true), "'3.14'");
+fn test_text_with_unknown_type_stays_text() {
+    assert_eq!(text_cell("42", SqlType::Unknown, false), "'42'");
+    assert_eq!(text_cell("3.14", SqlType::Unknown, false), "'3.14'");
+    assert_eq!(text_cell("true", SqlType::Unknown, false), "'true'");
+    assert_eq!(text_cell("hello", SqlType::Unknown, false), "'hello'");
+    assert_eq!(null_text_cell(SqlType::Unknown), "NULL");
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -414,12 +432,13 @@ fn test_perf_parse_sql_type_enum_approach() {
 }
 
 #[test]
-fn test_perf_format_cell_for_sql_enum_dispatch() {
+fn test_perf_format_array_value_for_sql_text_overrides() {
     use std::time::Instant;
 
-    let values = [
+    let values = StringArray::from(vec![
         "42", "3.14", "true", "false", "hello", "NULL", "1", "0", "-5", "100.0",
-    ];
+    ]);
+    let formatter = ArrayFormatter::try_new(&values, &FormatOptions::default()).unwrap();
     let types = [
         SqlType::Int,
         SqlType::Float,
@@ -427,13 +446,16 @@ fn test_perf_format_cell_for_sql_enum_dispatch() {
         SqlType::Text,
         SqlType::Unknown,
     ];
+    let dialect = Dialect::MySQL;
     let iterations = 50_000;
 
     let start = Instant::now();
     for _ in 0..iterations {
-        for v in &values {
+        for row in 0..values.len() {
             for t in &types {
-                std::hint::black_box(format_cell_for_sql(v, *t, false));
+                std::hint::black_box(format_array_value_for_sql(
+                    &values, &formatter, row, *t, None, false, &dialect,
+                ));
             }
         }
     }
@@ -441,7 +463,7 @@ fn test_perf_format_cell_for_sql_enum_dispatch() {
     let total_calls = iterations * values.len() * types.len();
 
     eprintln!(
-        "[PERF] test_perf_format_cell_for_sql_enum_dispatch | duration={}ms | iterations={} | total_calls={} | per_call={:.3}µs",
+        "[PERF] test_perf_format_array_value_for_sql_text_overrides | duration={}ms | iterations={} | total_calls={} | per_call={:.3}µs",
         duration.as_millis(),
         iterations,
         total_calls,
@@ -450,7 +472,7 @@ fn test_perf_format_cell_for_sql_enum_dispatch() {
 
     assert!(
         duration.as_secs() < 2,
-        "format_cell_for_sql hot loop too slow: {:?} for {} iterations",
+        "format_array_value_for_sql hot loop too slow: {:?} for {} iterations",
         duration,
         iterations
     );
@@ -473,6 +495,7 @@ fn test_perf_resolve_export_specs_large_columns() {
             } else {
                 "BOOL".to_string()
             },
+            timezone: None,
         })
         .collect();
 
@@ -500,30 +523,24 @@ fn test_perf_resolve_export_specs_large_columns() {
 // ─── empty_text_as_null ──────────────────────────────────────────────
 
 #[test]
-fn test_format_cell_empty_text_as_null_enabled() {
-    assert_eq!(format_cell_for_sql("", SqlType::Text, true), "NULL");
-    assert_eq!(format_cell_for_sql("", SqlType::Unknown, true), "NULL");
-    assert_eq!(format_cell_for_sql("hello", SqlType::Text, true), "'hello'");
-    assert_eq!(format_cell_for_sql("42", SqlType::Unknown, true), "42");
+fn test_text_empty_text_as_null_enabled() {
+    assert_eq!(text_cell("", SqlType::Text, true), "NULL");
+    assert_eq!(text_cell("", SqlType::Unknown, true), "NULL");
+    assert_eq!(text_cell("hello", SqlType::Text, true), "'hello'");
+    assert_eq!(text_cell("42", SqlType::Unknown, true), "'42'");
 }
 
 #[test]
-fn test_format_cell_empty_text_as_null_disabled() {
-    assert_eq!(format_cell_for_sql("", SqlType::Text, false), "''");
-    assert_eq!(format_cell_for_sql("", SqlType::Unknown, false), "''");
+fn test_text_empty_text_as_null_disabled() {
+    assert_eq!(text_cell("", SqlType::Text, false), "''");
+    assert_eq!(text_cell("", SqlType::Unknown, false), "''");
 }
 
 #[test]
-fn test_format_cell_empty_text_as_null_no_effect_on_non_text_types() {
-    assert_eq!(format_cell_for_sql("", SqlType::Int, true), "NULL");
-    assert_eq!(format_cell_for_sql("", SqlType::Float, true), "NULL");
-    assert_eq!(format_cell_for_sql("", SqlType::Bool, true), "NULL");
-}
-
-#[test]
-fn test_format_cell_null_value_ignores_empty_text_as_null() {
-    assert_eq!(format_cell_for_sql("NULL", SqlType::Text, true), "NULL");
-    assert_eq!(format_cell_for_sql("NULL", SqlType::Text, false), "NULL");
+fn test_text_empty_text_as_null_no_effect_on_non_text_types() {
+    assert_eq!(text_cell("", SqlType::Int, true), "NULL");
+    assert_eq!(text_cell("", SqlType::Float, true), "NULL");
+    assert_eq!(text_cell("", SqlType::Bool, true), "NULL");
 }
 
 // ─── truncate_record_batches ──────────────────────────────────────────
@@ -581,9 +598,9 @@ fn sample_ts_nanos() -> i64 {
         .expect("timestamp fits in i64")
 }
 
-/// The arrow-58 `ArrayFormatter` rendering of `sample_ts_nanos()` for a
-/// `Timestamp(Nanosecond, None)` column. Locks the display contract.
-const TS_DISPLAY: &str = "2026-05-26T19:41:00";
+/// The literal body of `sample_ts_nanos()` for a `Timestamp(Nanosecond, None)`
+/// column. Locks the timestamp literal contract.
+const TS_DISPLAY: &str = "2026-05-26 19:41:00";
 
 /// Build a multi-type RecordBatch exercising every arrow array kind the SQL
 /// generator can encounter: Int64, Float64, Utf8, Boolean, Timestamp(ns).
@@ -625,21 +642,25 @@ fn typed_columns() -> Vec<ExportColumnConfig> {
             source_column_name: "id".to_string(),
             export_column_name: "id".to_string(),
             sql_type: "BIGINT".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "price".to_string(),
             export_column_name: "price".to_string(),
             sql_type: "DOUBLE".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "name".to_string(),
             export_column_name: "name".to_string(),
             sql_type: "TEXT".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "active".to_string(),
             export_column_name: "active".to_string(),
             sql_type: "BOOL".to_string(),
+            timezone: None,
         },
     ]
 }
@@ -724,8 +745,8 @@ fn test_inserts_multitype_postgres_double_quotes() {
 
 #[test]
 fn test_inserts_timestamp_literal_contract() {
-    // No export config => Unknown type => timestamp rendered as quoted string.
-    // Locks the arrow-58 timestamp display format.
+    // No export config => Unknown type => typed timestamp literal, independent
+    // of Arrow's display format.
     let schema = Schema::new(vec![Field::new(
         "ts",
         DataType::Timestamp(TimeUnit::Nanosecond, None),
@@ -744,7 +765,7 @@ fn test_inserts_timestamp_literal_contract() {
             .expect("ok");
     assert_eq!(
         sql,
-        format!("INSERT INTO `t` (`ts`) VALUES\n('{}');", TS_DISPLAY)
+        format!("INSERT INTO `t` (`ts`) VALUES\n(TIMESTAMP '{}');", TS_DISPLAY)
     );
 }
 
@@ -803,11 +824,13 @@ fn test_inserts_column_subset_and_rename() {
             source_column_name: "name".to_string(),
             export_column_name: "full_name".to_string(),
             sql_type: "TEXT".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "id".to_string(),
             export_column_name: "user_id".to_string(),
             sql_type: "BIGINT".to_string(),
+            timezone: None,
         },
     ];
     let sql = generate_sql_inserts_from_batches(
@@ -842,6 +865,7 @@ fn test_inserts_empty_text_as_null_toggle() {
         source_column_name: "note".to_string(),
         export_column_name: "note".to_string(),
         sql_type: "TEXT".to_string(),
+        timezone: None,
     }];
 
     let sql_null = generate_sql_inserts_from_batches(
@@ -934,11 +958,13 @@ fn test_update_where_column_renamed() {
             source_column_name: "id".to_string(),
             export_column_name: "user_id".to_string(),
             sql_type: "BIGINT".to_string(),
+            timezone: None,
         },
         ExportColumnConfig {
             source_column_name: "name".to_string(),
             export_column_name: "full_name".to_string(),
             sql_type: "TEXT".to_string(),
+            timezone: None,
         },
     ];
     let schema = Schema::new(vec![
@@ -978,6 +1004,7 @@ fn test_update_where_column_not_in_export_used_for_value_only() {
         source_column_name: "name".to_string(),
         export_column_name: "name".to_string(),
         sql_type: "TEXT".to_string(),
+        timezone: None,
     }];
     let schema = Schema::new(vec![
         Field::new("id", DataType::Int64, true),
@@ -1001,9 +1028,9 @@ fn test_update_where_column_not_in_export_used_for_value_only() {
         false,
     )
     .expect("ok");
-    // The WHERE column is absent from the export config, so its SQL type defaults
-    // to Text (`unwrap_or(SqlType::Text)`); the numeric key is therefore quoted.
-    assert_eq!(sql, "UPDATE `t` SET `name` = 'zoe' WHERE `id` = '9';\n");
+    // The WHERE column is absent from the export config, so it keeps its Arrow
+    // type and the numeric key is not quoted.
+    assert_eq!(sql, "UPDATE `t` SET `name` = 'zoe' WHERE `id` = 9;\n");
 }
 
 #[test]
@@ -1032,6 +1059,36 @@ fn test_update_missing_where_column_errors() {
     );
 }
 
+#[test]
+fn test_missing_where_columns_name_the_statement() {
+    let blank = vec![" ".to_string(), String::new()];
+    let msg = format!("{:?}", where_column_refs(&blank, "DELETE").unwrap_err());
+    assert!(msg.contains("for DELETE statements"), "got: {}", msg);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)])),
+        vec![Arc::new(Int64Array::from(vec![Some(1_i64)]))],
+    )
+    .expect("batch");
+    let result = generate_sql_delete_from_batches(
+        vec![batch],
+        "t",
+        &[],
+        None,
+        &Dialect::MySQL,
+        None,
+        false,
+    );
+    let msg = format!("{:?}", result.unwrap_err());
+    assert!(msg.contains("for DELETE statements"), "got: {}", msg);
+}
+
+#[test]
+fn test_where_column_refs_trims_and_skips_blanks() {
+    let columns = vec![" id ".to_string(), "".to_string(), "tenant".to_string()];
+    assert_eq!(where_column_refs(&columns, "UPDATE").unwrap(), vec!["id", "tenant"]);
+}
+
 #[test]
 fn test_update_multiple_where_columns_and_joined() {
     let schema = Schema::new(vec![
@@ -1128,3 +1185,589 @@ fn test_update_duplicate_where_columns_errors() {
         msg
     );
 }
+
+// ─── Arrow-native literals ────────────────────────────────────────────
+
+/// Render a single-column batch as one multi-row INSERT, with an optional
+/// `sql_type` override for the column.
+fn insert_values(array: ArrayRef, sql_type: Option<&str>, dialect: &Dialect) -> String {
+    let batch = RecordBatch::try_from_iter(vec![("v", array)]).expect("batch");
+    let config = sql_type.map(|sql_type| {
+        vec![ExportColumnConfig {
+            source_column_name: "v".to_string(),
+            export_column_name: "v".to_string(),
+            sql_type: sql_type.to_string(),
+            timezone: None,
+        }]
+    });
+    generate_sql_inserts_from_batches(vec![batch], "t", 1000, dialect, config.as_deref(), false)
+        .expect("ok")
+}
+
+#[test]
+fn test_native_null_text_is_not_sql_null() {
+    let sql = insert_values(
+        Arc::new(StringArray::from(vec![Some("NULL"), None])),
+        None,
+        &Dialect::MySQL,
+    );
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n('NULL'),\n(NULL);");
+}
+
+#[test]
+fn test_native_numeric_looking_text_stays_quoted() {
+    let sql = insert_values(
+        Arc::new(StringArray::from(vec!["00123", "1.0", "true"])),
+        None,
+        &Dialect::MySQL,
+    );
+    assert_eq!(
+        sql,
+        "INSERT INTO `t` (`v`) VALUES\n('00123'),\n('1.0'),\n('true');"
+    );
+}
+
+#[test]
+fn test_native_numbers_unquoted_without_config() {
+    let sql = insert_values(
+        Arc::new(Float32Array::from(vec![9.87_f32, 2.0])),
+        None,
+        &Dialect::MySQL,
+    );
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n(9.87),\n(2);");
+
+    let sql = insert_values(
+        Arc::new(UInt64Array::from(vec![u64::MAX])),
+        None,
+        &Dialect::MySQL,
+    );
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n(18446744073709551615);");
+}
+
+#[test]
+fn test_native_decimal_keeps_exact_digits() {
+    let decimals = Decimal128Array::from(vec![Some(12345_i128), Some(-5)])
+        .with_precision_and_scale(10, 2)
+        .expect("decimal");
+    let sql = insert_values(Arc::new(decimals.clone()), None, &Dialect::MySQL);
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n(123.45),\n(-0.05);");
+
+    let sql = insert_values(Arc::new(decimals), Some("INT"), &Dialect::MySQL);
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n(123),\n(0);");
+}
+
+#[test]
+fn test_native_date_is_typed_literal() {
+    // 19_000 days after the epoch is 2022-01-08.
+    let sql = insert_values(Arc::new(Date32Array::from(vec![19_000])), None, &Dialect::MySQL);
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n(DATE '2022-01-08');");
+
+    // A TEXT override keeps the quoted string.
+    let sql = insert_values(
+        Arc::new(Date32Array::from(vec![19_000])),
+        Some("TEXT"),
+        &Dialect::MySQL,
+    );
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n('2022-01-08');");
+}
+
+#[test]
+fn test_native_binary_hex_literal_per_dialect() {
+    let array: ArrayRef = Arc::new(BinaryArray::from(vec![&b"hi"[..]]));
+    assert_eq!(
+        insert_values(array.clone(), None, &Dialect::MySQL),
+        "INSERT INTO `t` (`v`) VALUES\n(X'6869');"
+    );
+    assert_eq!(
+        insert_values(array, None, &Dialect::PostgreSQL),
+        "INSERT INTO \"t\" (\"v\") VALUES\n(decode('6869', 'hex'));"
+    );
+}
+
+#[test]
+fn test_native_overrides_apply_on_top_of_arrow_type() {
+    // Int column exported as TEXT is quoted; Bool column exported as INT is 1/0.
+    let sql = insert_values(Arc::new(Int32Array::from(vec![7])), Some("TEXT"), &Dialect::MySQL);
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n('7');");
+
+    let sql = insert_values(
+        Arc::new(BooleanArray::from(vec![true, false])),
+        Some("INT"),
+        &Dialect::MySQL,
+    );
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n(1),\n(0);");
+
+    // Text exported as INT is parsed, as before.
+    let sql = insert_values(
+        Arc::new(StringArray::from(vec!["00123", "abc"])),
+        Some("INT"),
+        &Dialect::MySQL,
+    );
+    assert_eq!(sql, "INSERT INTO `t` (`v`) VALUES\n(123),\n(NULL);");
+}
+
+// ─── generate_sql_upsert_from_batches ─────────────────────────────────
+
+fn id_name_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("name", DataType::Utf8, true),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1_i64, 2, 3])),
+            Arc::new(StringArray::from(vec![Some("alice"), Some(""), None])),
+        ],
+    )
+    .expect("batch")
+}
+
+#[test]
+fn test_upsert_mysql_on_duplicate_key_update() {
+    let sql = generate_sql_upsert_from_batches(
+        vec![id_name_batch()],
+        "t",
+        2,
+        &["id"],
+        &Dialect::MySQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "INSERT INTO `t` (`id`, `name`) VALUES\n(1, 'alice'),\n(2, '')\n\
+         ON DUPLICATE KEY UPDATE `name` = VALUES(`name`);\n\
+         INSERT INTO `t` (`id`, `name`) VALUES\n(3, NULL)\n\
+         ON DUPLICATE KEY UPDATE `name` = VALUES(`name`);"
+    );
+}
+
+#[test]
+fn test_upsert_postgres_on_conflict_with_empty_text_as_null() {
+    let sql = generate_sql_upsert_from_batches(
+        vec![id_name_batch()],
+        "t",
+        1000,
+        &["id"],
+        &Dialect::PostgreSQL,
+        None,
+        true,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "INSERT INTO \"t\" (\"id\", \"name\") VALUES\n(1, 'alice'),\n(2, NULL),\n(3, NULL)\n\
+         ON CONFLICT (\"id\") DO UPDATE SET \"name\" = EXCLUDED.\"name\";"
+    );
+}
+
+#[test]
+fn test_upsert_adds_unexported_key_column_and_renames() {
+    let config = vec![ExportColumnConfig {
+        source_column_name: "name".to_string(),
+        export_column_name: "full_name".to_string(),
+        sql_type: "TEXT".to_string(),
+        timezone: None,
+    }];
+    let batch = id_name_batch().slice(0, 1);
+    let sql = generate_sql_upsert_from_batches(
+        vec![batch],
+        "t",
+        1000,
+        &["id"],
+        &Dialect::PostgreSQL,
+        Some(&config),
+        false,
+    )
+    .expect("ok");
+    // `id` is not exported, so it keeps its Arrow type like UPDATE.
+    assert_eq!(
+        sql,
+        "INSERT INTO \"t\" (\"full_name\", \"id\") VALUES\n('alice', 1)\n\
+         ON CONFLICT (\"id\") DO UPDATE SET \"full_name\" = EXCLUDED.\"full_name\";"
+    );
+}
+
+#[test]
+fn test_upsert_only_key_columns() {
+    let batch = id_name_batch().slice(0, 1);
+    let mysql = generate_sql_upsert_from_batches(
+        vec![batch.clone()],
+        "t",
+        1000,
+        &["id", "name"],
+        &Dialect::MySQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        mysql,
+        "INSERT INTO `t` (`id`, `name`) VALUES\n(1, 'alice')\nON DUPLICATE KEY UPDATE `id` = `id`;"
+    );
+
+    let postgres = generate_sql_upsert_from_batches(
+        vec![batch],
+        "t",
+        1000,
+        &["id", "name"],
+        &Dialect::PostgreSQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert!(postgres.ends_with("ON CONFLICT (\"id\", \"name\") DO NOTHING;"));
+}
+
+#[test]
+fn test_upsert_composite_key_keeps_numeric_type() {
+    let config = vec![ExportColumnConfig {
+        source_column_name: "name".to_string(),
+        export_column_name: "name".to_string(),
+        sql_type: "TEXT".to_string(),
+        timezone: None,
+    }];
+    let sql = generate_sql_upsert_from_batches(
+        vec![key_pair_batch().slice(0, 2)],
+        "t",
+        1000,
+        &["tenant", "id"],
+        &Dialect::MySQL,
+        Some(&config),
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "INSERT INTO `t` (`name`, `tenant`, `id`) VALUES\n('x', 'a', 1),\n('y', 'a', 2)\n\
+         ON DUPLICATE KEY UPDATE `name` = VALUES(`name`);"
+    );
+}
+
+#[test]
+fn test_upsert_missing_conflict_column_errors() {
+    let result = generate_sql_upsert_from_batches(
+        vec![id_name_batch()],
+        "t",
+        1000,
+        &["nope"],
+        &Dialect::MySQL,
+        None,
+        false,
+    );
+    assert!(result.is_err());
+}
+
+// ─── generate_sql_delete_from_batches ─────────────────────────────────
+
+fn key_pair_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("tenant", DataType::Utf8, true),
+        Field::new("id", DataType::Int64, true),
+        Field::new("name", DataType::Utf8, true),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(StringArray::from(vec!["a", "a", "b"])),
+            Arc::new(Int64Array::from(vec![1_i64, 2, 3])),
+            Arc::new(StringArray::from(vec!["x", "y", "z"])),
+        ],
+    )
+    .expect("batch")
+}
+
+#[test]
+fn test_delete_per_row_mysql() {
+    let config = vec![ExportColumnConfig {
+        source_column_name: "id".to_string(),
+        export_column_name: "id".to_string(),
+        sql_type: "INT".to_string(),
+        timezone: None,
+    }];
+    let sql = generate_sql_delete_from_batches(
+        vec![id_name_batch()],
+        "t",
+        &["id"],
+        None,
+        &Dialect::MySQL,
+        Some(&config),
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "DELETE FROM `t` WHERE `id` = 1;\n\
+         DELETE FROM `t` WHERE `id` = 2;\n\
+         DELETE FROM `t` WHERE `id` = 3;\n"
+    );
+}
+
+#[test]
+fn test_delete_per_row_null_key_uses_is_null() {
+    let sql = generate_sql_delete_from_batches(
+        vec![id_name_batch()],
+        "t",
+        &["name"],
+        None,
+        &Dialect::PostgreSQL,
+        None,
+        true,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "DELETE FROM \"t\" WHERE \"name\" = 'alice';\n\
+         DELETE FROM \"t\" WHERE \"name\" IS NULL;\n\
+         DELETE FROM \"t\" WHERE \"name\" IS NULL;\n"
+    );
+}
+
+#[test]
+fn test_delete_batched_single_key() {
+    let sql = generate_sql_delete_from_batches(
+        vec![key_pair_batch()],
+        "t",
+        &["tenant"],
+        Some(2),
+        &Dialect::PostgreSQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "DELETE FROM \"t\" WHERE \"tenant\" IN ('a', 'a');\n\
+         DELETE FROM \"t\" WHERE \"tenant\" IN ('b');\n"
+    );
+}
+
+#[test]
+fn test_delete_batched_composite_key() {
+    let sql = generate_sql_delete_from_batches(
+        vec![key_pair_batch()],
+        "t",
+        &["tenant", "id"],
+        Some(1000),
+        &Dialect::MySQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    // Keys not in the export config keep their Arrow type, as for INSERT.
+    assert_eq!(
+        sql,
+        "DELETE FROM `t` WHERE (`tenant`, `id`) IN (('a', 1), ('a', 2), ('b', 3));\n"
+    );
+}
+
+#[test]
+fn test_delete_batched_null_key_gets_own_statement() {
+    let sql = generate_sql_delete_from_batches(
+        vec![id_name_batch()],
+        "t",
+        &["name"],
+        Some(1000),
+        &Dialect::MySQL,
+        None,
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "DELETE FROM `t` WHERE `name` IS NULL;\n\
+         DELETE FROM `t` WHERE `name` IN ('alice', '');\n"
+    );
+}
+
+#[test]
+fn test_delete_validates_key_columns() {
+    let missing = generate_sql_delete_from_batches(
+        vec![id_name_batch()],
+        "t",
+        &["nope"],
+        None,
+        &Dialect::MySQL,
+        None,
+        false,
+    );
+    assert!(missing.is_err());
+
+    let duplicate = generate_sql_delete_from_batches(
+        vec![id_name_batch()],
+        "t",
+        &["id", "id"],
+        None,
+        &Dialect::MySQL,
+        None,
+        false,
+    );
+    assert!(duplicate.is_err());
+}
+
+// ─── typed overrides ──────────────────────────────────────────────────
+
+#[test]
+fn test_typed_date_and_timestamp_from_text() {
+    let text: ArrayRef = Arc::new(StringArray::from(vec![
+        "2024-02-29",
+        "2024-02-29T13:45:00.5",
+        "nope",
+    ]));
+    assert_eq!(
+        insert_values(text.clone(), Some("DATE"), &Dialect::PostgreSQL),
+        "INSERT INTO \"t\" (\"v\") VALUES\n\
+         (DATE '2024-02-29'),\n(DATE '2024-02-29'),\n(NULL);"
+    );
+    assert_eq!(
+        insert_values(text, Some("TIMESTAMP"), &Dialect::PostgreSQL),
+        "INSERT INTO \"t\" (\"v\") VALUES\n\
+         (TIMESTAMP '2024-02-29 00:00:00'),\n\
+         (TIMESTAMP '2024-02-29 13:45:00.500'),\n\
+         (NULL);"
+    );
+}
+
+#[test]
+fn test_zoned_timestamp_keeps_offset_or_converts() {
+    // 2024-01-01T00:00:00Z shown in Asia/Tokyo (+09:00).
+    let zoned: ArrayRef = Arc::new(
+        TimestampSecondArray::from(vec![1_704_067_200]).with_timezone("+09:00"),
+    );
+    assert_eq!(
+        insert_values(zoned.clone(), None, &Dialect::PostgreSQL),
+        "INSERT INTO \"t\" (\"v\") VALUES\n\
+         (TIMESTAMP WITH TIME ZONE '2024-01-01 09:00:00+09:00');"
+    );
+
+    let batch = RecordBatch::try_from_iter(vec![("v", zoned)]).expect("batch");
+    let config = vec![ExportColumnConfig {
+        source_column_name: "v".to_string(),
+        export_column_name: "v".to_string(),
+        sql_type: "DATETIME".to_string(),
+        timezone: Some("-05:00".to_string()),
+    }];
+    let sql = generate_sql_inserts_from_batches(
+        vec![batch],
+        "t",
+        1000,
+        &Dialect::MySQL,
+        Some(&config),
+        false,
+    )
+    .expect("ok");
+    assert_eq!(
+        sql,
+        "INSERT INTO `t` (`v`) VALUES\n(TIMESTAMP '2023-12-31 19:00:00');"
+    );
+}
+
+#[test]
+fn test_invalid_timezone_errors() {
+    let config = vec![ExportColumnConfig {
+        source_column_name: "v".to_string(),
+        export_column_name: "v".to_string(),
+        sql_type: "TIMESTAMP".to_string(),
+        timezone: Some("Mars/Olympus".to_string()),
+    }];
+    let batch = RecordBatch::try_from_iter(vec![(
+        "v",
+        Arc::new(Int64Array::from(vec![1_i64])) as ArrayRef,
+    )])
+    .expect("batch");
+    let result = generate_sql_inserts_from_batches(
+        vec![batch],
+        "t",
+        1000,
+        &Dialect::MySQL,
+        Some(&config),
+        false,
+    );
+    let msg = format!("{:?}", result.unwrap_err());
+    assert!(msg.contains("Invalid timezone"), "got: {}", msg);
+}
+
+#[test]
+fn test_typed_decimal_keeps_exact_digits() {
+    let values: ArrayRef = Arc::new(StringArray::from(vec![
+        "12345678901234567890.123456789",
+        "-0.5",
+        "1e3",
+    ]));
+    assert_eq!(
+        insert_values(values, Some("DECIMAL(38, 9)"), &Dialect::MySQL),
+        "INSERT INTO `t` (`v`) VALUES\n\
+         (12345678901234567890.123456789),\n(-0.5),\n(NULL);"
+    );
+
+    let decimals = Decimal128Array::from(vec![Some(12345_i128)])
+        .with_precision_and_scale(10, 2)
+        .expect("decimal");
+    assert_eq!(
+        insert_values(Arc::new(decimals), Some("NUMERIC"), &Dialect::PostgreSQL),
+        "INSERT INTO \"t\" (\"v\") VALUES\n(123.45);"
+    );
+}
+
+#[test]
+fn test_typed_binary_from_text() {
+    let text: ArrayRef = Arc::new(StringArray::from(vec!["hi"]));
+    assert_eq!(
+        insert_values(text.clone(), Some("BLOB"), &Dialect::MySQL),
+        "INSERT INTO `t` (`v`) VALUES\n(X'6869');"
+    );
+    assert_eq!(
+        insert_values(text, Some("BYTEA"), &Dialect::PostgreSQL),
+        "INSERT INTO \"t\" (\"v\") VALUES\n(decode('6869', 'hex'));"
+    );
+}
+
+// ─── streaming ────────────────────────────────────────────────────────
+
+fn id_batch(ids: Vec<i64>) -> RecordBatch {
+    RecordBatch::try_from_iter(vec![("id", Arc::new(Int64Array::from(ids)) as ArrayRef)])
+        .expect("batch")
+}
+
+#[test]
+fn test_insert_writer_chunks_span_batches() {
+    let headers = vec!["id".to_string()];
+    let mut writer =
+        InsertWriter::new(&headers, "t", 3, &Dialect::MySQL, None, false).expect("writer");
+    let mut out = SqlScript::plain(Vec::new());
+
+    writer.write_batch(&id_batch(vec![1, 2]), &mut out).expect("ok");
+    assert!(out.get_ref().is_empty(), "an unfinished chunk is buffered");
+
+    writer.write_batch(&id_batch(vec![3, 4]), &mut out).expect("ok");
+    assert_eq!(
+        String::from_utf8(out.get_ref().clone()).expect("utf8"),
+        "INSERT INTO `t` (`id`) VALUES\n(1),\n(2),\n(3);"
+    );
+
+    writer.finish(&mut out).expect("ok");
+    assert!(String::from_utf8(out.finish(false).expect("ok"))
+        .expect("utf8")
+        .ends_with(";\nINSERT INTO `t` (`id`) VALUES\n(4);"));
+}
+
+#[tokio::test]
+async fn test_write_sql_statements_streams_dataframe() {
+    let batches = vec![id_batch(vec![1, 2]), id_batch(vec![3])];
+    let ctx = SessionContext::new();
+    let df = ctx.read_batches(batches.clone()).expect("df");
+    let headers = schema_headers(df.schema().inner());
+
+    let writer =
+        InsertWriter::new(&headers, "t", 2, &Dialect::MySQL, None, false).expect("writer");
+    let out = write_sql_statements(df, writer, SqlScript::plain(Vec::new()))
+        .await
+        .expect("ok");
+    assert_eq!(
+        String::from_utf8(out).expect("utf8"),
+        generate_sql_inserts_from_batches(batches, "t", 2, &Dialect::MySQL, None, false)
+            .expect("ok")
+    );
+}
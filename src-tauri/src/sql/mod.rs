@@ -1,5 +1,16 @@
+pub mod ddl;
+pub mod dialect;
+pub mod escape;
 pub mod generator;
-pub mod parse;
+pub mod script;
 
+#[cfg(test)]
+mod ddl_test;
+#[cfg(test)]
+mod dialect_test;
+#[cfg(test)]
+mod escape_test;
 #[cfg(test)]
 mod generator_test;
+#[cfg(test)]
+mod script_test;
@@ -0,0 +1,167 @@
+use crate::commands::query::Dialect;
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use serde::Deserialize;
+use std::io::Write;
+
+/// How the statements of a SQL export are wrapped into a script.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SqlScriptOptions {
+    /// Wrap the statements in `BEGIN; ... COMMIT;`.
+    pub transaction: bool,
+    /// Commit and start a new transaction after this many statements.
+    /// Requires `transaction`.
+    pub commit_every: Option<usize>,
+    /// Split INSERT and UPSERT chunks so no statement exceeds this many
+    /// bytes, e.g. to stay under MySQL's `max_allowed_packet`.
+    pub max_statement_bytes: Option<usize>,
+    /// Turn foreign key checks off for the session while the script runs.
+    pub disable_foreign_keys: bool,
+}
+
+/// Writes statements one per line to `out`, adding the transaction and
+/// foreign key statements requested by [`SqlScriptOptions`].
+///
+/// The wrapping is only written once the first statement arrives, so an
+/// export without rows stays empty.
+pub struct SqlScript<W: Write> {
+    out: W,
+    /// `(begin, commit)`, `begin` being `None` where a transaction starts
+    /// implicitly.
+    transaction: Option<(Option<&'static str>, &'static str)>,
+    commit_every: Option<usize>,
+    /// `(disable, enable)`.
+    foreign_key_checks: Option<(&'static str, &'static str)>,
+    /// Written before the first line.
+    lead: Option<&'static str>,
+    lines: usize,
+    statements: usize,
+    uncommitted: usize,
+}
+
+impl<W: Write> SqlScript<W> {
+    pub fn new(out: W, db_dialect: &Dialect, options: &SqlScriptOptions) -> AppResult<Self> {
+        let strategy = db_dialect.strategy();
+        if options.commit_every == Some(0) {
+            return Err(AppError::BadRequest {
+                message: "Commit interval must be at least 1 statement".to_string(),
+            });
+        }
+        if options.commit_every.is_some() && !options.transaction {
+            return Err(AppError::BadRequest {
+                message: "A commit interval requires the statements to run in a transaction"
+                    .to_string(),
+            });
+        }
+        if options.max_statement_bytes == Some(0) {
+            return Err(AppError::BadRequest {
+                message: "Max statement size must be at least 1 byte".to_string(),
+            });
+        }
+
+        let transaction = if options.transaction {
+            Some((strategy.begin_transaction()?, "COMMIT;"))
+        } else {
+            None
+        };
+        let foreign_key_checks = if options.disable_foreign_keys {
+            Some(strategy.foreign_key_checks()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            out,
+            transaction,
+            commit_every: options.commit_every,
+            foreign_key_checks,
+            lead: None,
+            lines: 0,
+            statements: 0,
+            uncommitted: 0,
+        })
+    }
+
+    /// A script of bare statements.
+    pub fn plain(out: W) -> Self {
+        Self {
+            out,
+            transaction: None,
+            commit_every: None,
+            foreign_key_checks: None,
+            lead: None,
+            lines: 0,
+            statements: 0,
+            uncommitted: 0,
+        }
+    }
+
+    /// Write `lead` ahead of the script, e.g. to separate it from a `CREATE
+    /// TABLE` statement. Like the wrapping, it is only written once the first
+    /// statement arrives.
+    pub fn with_lead(mut self, lead: &'static str) -> Self {
+        self.lead = Some(lead);
+        self
+    }
+
+    /// Append one statement, including its trailing semicolon.
+    pub fn statement(&mut self, sql: &str) -> AppResult<()> {
+        if self.statements == 0 {
+            if let Some((disable, _)) = self.foreign_key_checks {
+                self.line(disable)?;
+            }
+        }
+        if let Some((begin, commit)) = self.transaction {
+            if self.uncommitted == 0 {
+                if let Some(begin) = begin {
+                    self.line(begin)?;
+                }
+            }
+            self.line(sql)?;
+            self.uncommitted += 1;
+            if self.commit_every == Some(self.uncommitted) {
+                self.line(commit)?;
+                self.uncommitted = 0;
+            }
+        } else {
+            self.line(sql)?;
+        }
+        self.statements += 1;
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    /// Close the open transaction and restore foreign key checks, ending the
+    /// script with a newline if `terminate_line` is set.
+    pub fn finish(mut self, terminate_line: bool) -> AppResult<W> {
+        if let Some((_, commit)) = self.transaction {
+            if self.uncommitted > 0 {
+                self.line(commit)?;
+            }
+        }
+        if self.statements > 0 {
+            if let Some((_, enable)) = self.foreign_key_checks {
+                self.line(enable)?;
+            }
+        }
+        if terminate_line && self.lines > 0 {
+            self.out.write_all(b"\n")?;
+        }
+        Ok(self.out)
+    }
+
+    fn line(&mut self, sql: &str) -> AppResult<()> {
+        if self.lines > 0 {
+            self.out.write_all(b"\n")?;
+        } else if let Some(lead) = self.lead {
+            self.out.write_all(lead.as_bytes())?;
+        }
+        self.out.write_all(sql.as_bytes())?;
+        self.lines += 1;
+        Ok(())
+    }
+}
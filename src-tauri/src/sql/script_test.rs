@@ -0,0 +1,242 @@
+use super::generator::{write_sql_statements_to_string, InsertWriter, UpdateWriter};
+use super::script::{SqlScript, SqlScriptOptions};
+use crate::commands::query::Dialect;
+use datafusion::arrow::array::{ArrayRef, Int64Array, StringArray};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// id  name
+/// 1   a
+/// 2   b
+/// 3   c
+fn rows() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(vec![1_i64, 2, 3])) as ArrayRef),
+        ("name", Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef),
+    ])
+    .expect("batch")
+}
+
+fn headers() -> Vec<String> {
+    vec!["id".to_string(), "name".to_string()]
+}
+
+fn updates(dialect: &Dialect, options: &SqlScriptOptions) -> String {
+    let writer =
+        UpdateWriter::new(&headers(), "t", &["id"], dialect, None, false).expect("writer");
+    let script = SqlScript::new(Vec::new(), dialect, options).expect("script");
+    write_sql_statements_to_string(&[rows()], writer, script).expect("ok")
+}
+
+fn inserts(max_values: usize, options: &SqlScriptOptions) -> String {
+    let writer = InsertWriter::new(&headers(), "t", max_values, &Dialect::MySQL, None, false)
+        .expect("writer")
+        .with_max_statement_bytes(options.max_statement_bytes);
+    let script = SqlScript::new(Vec::new(), &Dialect::MySQL, options).expect("script");
+    write_sql_statements_to_string(&[rows()], writer, script).expect("ok")
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+// ─── transactions ─────────────────────────────────────────────────────
+
+#[test]
+fn test_transaction_wraps_statements() {
+    let options = SqlScriptOptions {
+        transaction: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        updates(&Dialect::MySQL, &options),
+        "START TRANSACTION;\n\
+         UPDATE `t` SET `name` = 'a' WHERE `id` = 1;\n\
+         UPDATE `t` SET `name` = 'b' WHERE `id` = 2;\n\
+         UPDATE `t` SET `name` = 'c' WHERE `id` = 3;\n\
+         COMMIT;\n"
+    );
+}
+
+#[test]
+fn test_commit_every_starts_new_transactions() {
+    let options = SqlScriptOptions {
+        transaction: true,
+        commit_every: Some(2),
+        ..Default::default()
+    };
+    let sql = updates(&Dialect::PostgreSQL, &options);
+    assert_eq!(sql.matches("BEGIN;").count(), 2);
+    assert_eq!(sql.matches("COMMIT;").count(), 2);
+    assert!(sql.ends_with("WHERE \"id\" = 3;\nCOMMIT;\n"), "got: {}", sql);
+}
+
+#[test]
+fn test_oracle_transaction_has_no_begin() {
+    let options = SqlScriptOptions {
+        transaction: true,
+        ..Default::default()
+    };
+    let sql = updates(&Dialect::Oracle, &options);
+    assert!(sql.starts_with("UPDATE \"t\""), "got: {}", sql);
+    assert!(sql.ends_with(";\nCOMMIT;\n"));
+}
+
+#[test]
+fn test_insert_script_keeps_no_trailing_newline() {
+    let options = SqlScriptOptions {
+        transaction: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        inserts(1000, &options),
+        "START TRANSACTION;\n\
+         INSERT INTO `t` (`id`, `name`) VALUES\n(1, 'a'),\n(2, 'b'),\n(3, 'c');\n\
+         COMMIT;"
+    );
+}
+
+#[test]
+fn test_empty_export_has_no_wrapping() {
+    let options = SqlScriptOptions {
+        transaction: true,
+        disable_foreign_keys: true,
+        ..Default::default()
+    };
+    let writer = InsertWriter::new(&headers(), "t", 10, &Dialect::MySQL, None, false)
+        .expect("writer");
+    let script = SqlScript::new(Vec::new(), &Dialect::MySQL, &options).expect("script");
+    let sql = write_sql_statements_to_string(&[rows().slice(0, 0)], writer, script).expect("ok");
+    assert_eq!(sql, "");
+}
+
+#[test]
+fn test_lead_is_written_before_the_first_statement_only() {
+    let writer = InsertWriter::new(&headers(), "t", 10, &Dialect::MySQL, None, false)
+        .expect("writer");
+    let script = SqlScript::plain(Vec::new()).with_lead("\n\n");
+    let sql = write_sql_statements_to_string(&[rows()], writer, script).expect("ok");
+    assert_eq!(sql, "\n\nINSERT INTO `t` (`id`, `name`) VALUES\n(1, 'a'),\n(2, 'b'),\n(3, 'c');");
+
+    let writer = InsertWriter::new(&headers(), "t", 10, &Dialect::MySQL, None, false)
+        .expect("writer");
+    let script = SqlScript::plain(Vec::new()).with_lead("\n\n");
+    let sql = write_sql_statements_to_string(&[rows().slice(0, 0)], writer, script).expect("ok");
+    assert_eq!(sql, "");
+}
+
+#[test]
+fn test_invalid_script_options_error() {
+    let zero_commit = SqlScriptOptions {
+        transaction: true,
+        commit_every: Some(0),
+        ..Default::default()
+    };
+    let clickhouse_transaction = SqlScriptOptions {
+        transaction: true,
+        ..Default::default()
+    };
+    let oracle_foreign_keys = SqlScriptOptions {
+        disable_foreign_keys: true,
+        ..Default::default()
+    };
+    let commit_without_transaction = SqlScriptOptions {
+        commit_every: Some(100),
+        ..Default::default()
+    };
+    for (dialect, options) in [
+        (Dialect::MySQL, zero_commit),
+        (Dialect::MySQL, commit_without_transaction),
+        (Dialect::ClickHouse, clickhouse_transaction),
+        (Dialect::Oracle, oracle_foreign_keys),
+    ] {
+        assert!(SqlScript::new(Vec::new(), &dialect, &options).is_err());
+    }
+}
+
+// ─── foreign keys ─────────────────────────────────────────────────────
+
+#[test]
+fn test_foreign_key_checks_wrap_transaction() {
+    let options = SqlScriptOptions {
+        transaction: true,
+        disable_foreign_keys: true,
+        ..Default::default()
+    };
+    let cases = [
+        (
+            Dialect::MySQL,
+            "SET FOREIGN_KEY_CHECKS=0;\nSTART TRANSACTION;\n",
+            "COMMIT;\nSET FOREIGN_KEY_CHECKS=1;\n",
+        ),
+        (
+            Dialect::PostgreSQL,
+            "SET session_replication_role = replica;\nBEGIN;\n",
+            "COMMIT;\nSET session_replication_role = DEFAULT;\n",
+        ),
+        (
+            Dialect::SQLite,
+            "PRAGMA foreign_keys = OFF;\nBEGIN;\n",
+            "COMMIT;\nPRAGMA foreign_keys = ON;\n",
+        ),
+    ];
+    for (dialect, prologue, epilogue) in cases {
+        let sql = updates(&dialect, &options);
+        assert!(sql.starts_with(prologue), "got: {}", sql);
+        assert!(sql.ends_with(epilogue), "got: {}", sql);
+    }
+}
+
+// ─── statement size ───────────────────────────────────────────────────
+
+#[test]
+fn test_max_statement_bytes_splits_inserts() {
+    let header = "INSERT INTO `t` (`id`, `name`) VALUES\n";
+    // Room for two rows, but not three.
+    let options = SqlScriptOptions {
+        max_statement_bytes: Some(header.len() + 24),
+        ..Default::default()
+    };
+    let sql = inserts(1000, &options);
+    assert_eq!(
+        sql,
+        format!("{}(1, 'a'),\n(2, 'b');\n{}(3, 'c');", header, header)
+    );
+    for statement in sql.split_inclusive(';') {
+        assert!(statement.trim_start().len() <= header.len() + 24);
+    }
+}
+
+#[test]
+fn test_max_statement_bytes_counts_oracle_insert_all_rows() {
+    let two_rows = "INSERT ALL\n\
+                    \x20 INTO \"t\" (\"id\", \"name\") VALUES (1, 'a')\n\
+                    \x20 INTO \"t\" (\"id\", \"name\") VALUES (2, 'b')\n\
+                    SELECT 1 FROM DUAL;";
+    let options = SqlScriptOptions {
+        max_statement_bytes: Some(two_rows.len()),
+        ..Default::default()
+    };
+    let writer = InsertWriter::new(&headers(), "t", 1000, &Dialect::Oracle, None, false)
+        .expect("writer")
+        .with_max_statement_bytes(options.max_statement_bytes);
+    let script = SqlScript::new(Vec::new(), &Dialect::Oracle, &options).expect("script");
+    let sql = write_sql_statements_to_string(&[rows()], writer, script).expect("ok");
+    assert!(sql.starts_with(two_rows), "got: {}", sql);
+    for statement in sql.split_inclusive(';') {
+        assert!(statement.trim_start().len() <= two_rows.len());
+    }
+}
+
+#[test]
+fn test_oversized_row_gets_own_statement() {
+    let options = SqlScriptOptions {
+        max_statement_bytes: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(inserts(1000, &options).matches("INSERT INTO").count(), 3);
+}
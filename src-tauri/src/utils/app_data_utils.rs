@@ -13,3 +13,7 @@ pub fn get_app_data_dir(app: &tauri::AppHandle) -> AppResult<PathBuf> {
     }
 }
 
+/// Directory holding query results spilled from the in-memory result cache.
+pub fn get_result_cache_dir(app: &tauri::AppHandle) -> AppResult<PathBuf> {
+    Ok(get_app_data_dir(app)?.join("result_cache"))
+}
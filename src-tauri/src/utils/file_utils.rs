@@ -1,6 +1,7 @@
 use crate::context::error::AppError;
 use crate::context::schema::AppResult;
-use glob::glob;
+use crate::reader::zip::open_archive;
+use glob::{glob_with, MatchOptions, Pattern};
 use std::path::Path;
 
 fn has_glob_metacharacters(path: &str) -> bool {
@@ -27,10 +28,18 @@ pub fn ensure_path_exists(path: &str) -> AppResult<()> {
     }
 }
 
+/// How [`find_files`] and [`find_zip_members`] match patterns: `*` stays
+/// within a directory and `**` crosses them.
+const GLOB_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
 pub fn find_files(pattern: &str) -> AppResult<Vec<String>> {
     let mut files: Vec<String> = Vec::new();
 
-    for entry in glob(pattern)? {
+    for entry in glob_with(pattern, GLOB_OPTIONS)? {
         let path = entry?;
         if path.is_file() {
             if let Some(path) = path.to_str() {
@@ -47,3 +56,36 @@ pub fn find_files(pattern: &str) -> AppResult<Vec<String>> {
         Ok(files)
     }
 }
+
+/// Split a path into a zip archive inside it, `export.zip!/2024/*.csv` giving
+/// `("export.zip", "2024/*.csv")`. Returns `None` for other paths.
+pub fn split_zip_path(path: &str) -> Option<(&str, &str)> {
+    let index = path.to_ascii_lowercase().find(".zip!")? + ".zip".len();
+    let member = path[index + 1..].trim_start_matches(['/', '\\']);
+    Some((&path[..index], member))
+}
+
+/// Names of the files in the zip archive `archive` matching `pattern`, in
+/// archive order. The pattern is matched like [`find_files`] matches paths.
+pub fn find_zip_members(archive: &str, pattern: &str) -> AppResult<Vec<String>> {
+    if !Path::new(archive).is_file() {
+        return Err(AppError::FileNotFound {
+            file_name: archive.to_string(),
+        });
+    }
+    let matcher = Pattern::new(pattern)?;
+    let zip = open_archive(archive)?;
+    let files: Vec<String> = zip
+        .file_names()
+        .filter(|name| !name.ends_with('/') && matcher.matches_with(name, GLOB_OPTIONS))
+        .map(str::to_string)
+        .collect();
+
+    if files.is_empty() {
+        Err(AppError::FileNotFound {
+            file_name: format!("{}!/{}", archive, pattern),
+        })
+    } else {
+        Ok(files)
+    }
+}
@@ -1,7 +1,9 @@
-use super::file_utils::{ensure_path_exists, find_files};
+use super::file_utils::{ensure_path_exists, find_files, find_zip_members, split_zip_path};
 use crate::context::error::AppError;
 use std::fs::File;
 use std::io::Write;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
 
 #[test]
 fn ensure_path_exists_rejects_missing_literal_path() {
@@ -33,3 +35,41 @@ fn find_files_rejects_pattern_with_no_matches() {
     let err = find_files("/tmp/easydb_no_match_*.csv").unwrap_err();
     assert!(matches!(err, AppError::FileNotFound { .. }));
 }
+
+#[test]
+fn split_zip_path_separates_archive_and_member() {
+    assert_eq!(
+        split_zip_path("/data/export.zip!/2024/*.csv"),
+        Some(("/data/export.zip", "2024/*.csv"))
+    );
+    assert_eq!(
+        split_zip_path("C:\\data\\Export.ZIP!\\a.csv"),
+        Some(("C:\\data\\Export.ZIP", "a.csv"))
+    );
+    assert_eq!(split_zip_path("/data/export.zip"), None);
+}
+
+#[test]
+fn find_zip_members_matches_glob_per_directory() {
+    let path = std::env::temp_dir().join("easydb_file_utils_members.zip");
+    let mut zip = ZipWriter::new(File::create(&path).unwrap());
+    for name in ["2024/a.csv", "2024/b.json", "2024/q1/c.csv", "d.csv"] {
+        zip.start_file(name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"a\n1\n").unwrap();
+    }
+    zip.finish().unwrap();
+    let archive = path.to_str().unwrap();
+
+    assert_eq!(
+        find_zip_members(archive, "2024/*.csv").unwrap(),
+        ["2024/a.csv"]
+    );
+    assert_eq!(
+        find_zip_members(archive, "**/*.csv").unwrap(),
+        ["2024/a.csv", "2024/q1/c.csv", "d.csv"]
+    );
+    let err = find_zip_members(archive, "*.parquet").unwrap_err();
+    assert!(matches!(err, AppError::FileNotFound { .. }));
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,215 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::writer::CountingWriter;
+use bzip2::write::BzEncoder;
+use flate2::write::GzEncoder;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+type Sink = CountingWriter<BufWriter<File>>;
+
+/// How the files of an export are compressed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportCompression {
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+    /// Write the files uncompressed, then bundle them into one `.zip`.
+    Zip,
+}
+
+impl ExportCompression {
+    /// Parse the `compression` option: `none` (default), `gzip`, `zstd`,
+    /// `bzip2` or `zip`.
+    pub fn parse(compression: Option<&str>) -> AppResult<Self> {
+        let Some(compression) = compression.map(|c| c.trim().to_lowercase()) else {
+            return Ok(ExportCompression::None);
+        };
+        match compression.as_str() {
+            "" | "none" => Ok(ExportCompression::None),
+            "gzip" | "gz" => Ok(ExportCompression::Gzip),
+            "zstd" | "zst" => Ok(ExportCompression::Zstd),
+            "bzip2" | "bz2" => Ok(ExportCompression::Bzip2),
+            "zip" => Ok(ExportCompression::Zip),
+            _ => Err(AppError::BadRequest {
+                message: format!(
+                    "Unsupported compression '{}'. Supported: none, gzip, zstd, bzip2, zip",
+                    compression
+                ),
+            }),
+        }
+    }
+
+    /// The compression a file is written with, from its name.
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("gz") => ExportCompression::Gzip,
+            Some("zst") => ExportCompression::Zstd,
+            Some("bz2") => ExportCompression::Bzip2,
+            _ => ExportCompression::None,
+        }
+    }
+
+    /// Extension added after the file type's, e.g. `gz` for `.csv.gz`.
+    /// `None` for uncompressed files and zip bundles.
+    pub fn suffix(&self) -> Option<&'static str> {
+        match self {
+            ExportCompression::Gzip => Some("gz"),
+            ExportCompression::Zstd => Some("zst"),
+            ExportCompression::Bzip2 => Some("bz2"),
+            ExportCompression::None | ExportCompression::Zip => None,
+        }
+    }
+}
+
+enum Encoder {
+    Plain(Sink),
+    Gzip(GzEncoder<Sink>),
+    Zstd(zstd::stream::write::Encoder<'static, Sink>),
+    Bzip2(BzEncoder<Sink>),
+}
+
+/// An export file, compressed while it is written when its name ends in
+/// `.gz`, `.zst` or `.bz2`.
+///
+/// [`OutputFile::finish`] must be called to write the end of the
+/// compressed stream.
+pub struct OutputFile {
+    encoder: Encoder,
+    /// Whether the file had no content before this output.
+    starts_empty: bool,
+}
+
+impl OutputFile {
+    pub fn create(path: impl AsRef<Path>) -> AppResult<Self> {
+        Self::open(path.as_ref(), File::create(path.as_ref())?)
+    }
+
+    /// Write after the existing content. Compressed files get a new gzip
+    /// member, zstd frame or bzip2 stream, which decompressors read as one.
+    pub fn append(path: impl AsRef<Path>) -> AppResult<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.as_ref())?;
+        Self::open(path.as_ref(), file)
+    }
+
+    fn open(path: &Path, file: File) -> AppResult<Self> {
+        let starts_empty = file.metadata()?.len() == 0;
+        let sink = CountingWriter::new(BufWriter::new(file));
+        let encoder = match ExportCompression::from_path(path) {
+            ExportCompression::Gzip => {
+                Encoder::Gzip(GzEncoder::new(sink, flate2::Compression::default()))
+            }
+            ExportCompression::Zstd => Encoder::Zstd(zstd::stream::write::Encoder::new(sink, 0)?),
+            ExportCompression::Bzip2 => {
+                Encoder::Bzip2(BzEncoder::new(sink, bzip2::Compression::default()))
+            }
+            ExportCompression::None | ExportCompression::Zip => Encoder::Plain(sink),
+        };
+        Ok(Self {
+            encoder,
+            starts_empty,
+        })
+    }
+
+    pub fn starts_empty(&self) -> bool {
+        self.starts_empty
+    }
+
+    /// Bytes written to the file so far, after compression. The encoder
+    /// holds back some output until its buffer fills.
+    pub fn bytes(&self) -> u64 {
+        match &self.encoder {
+            Encoder::Plain(sink) => sink.bytes(),
+            Encoder::Gzip(encoder) => encoder.get_ref().bytes(),
+            Encoder::Zstd(encoder) => encoder.get_ref().bytes(),
+            Encoder::Bzip2(encoder) => encoder.get_ref().bytes(),
+        }
+    }
+
+    /// End the compressed stream and flush the file.
+    pub fn finish(self) -> AppResult<()> {
+        let mut sink = match self.encoder {
+            Encoder::Plain(sink) => sink,
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            Encoder::Zstd(encoder) => encoder.finish()?,
+            Encoder::Bzip2(encoder) => encoder.finish()?,
+        };
+        sink.flush()?;
+        Ok(())
+    }
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.encoder {
+            Encoder::Plain(sink) => sink.write(buf),
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            Encoder::Zstd(encoder) => encoder.write(buf),
+            Encoder::Bzip2(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.encoder {
+            Encoder::Plain(sink) => sink.flush(),
+            Encoder::Gzip(encoder) => encoder.flush(),
+            Encoder::Zstd(encoder) => encoder.flush(),
+            Encoder::Bzip2(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Pack `files` into the zip archive `archive`, each named by its path
+/// relative to `base` so partition directories are kept, then remove them.
+///
+/// The files are removed even if the archive can't be written, in which
+/// case the partial archive is removed too.
+pub fn bundle_zip(archive: &Path, base: &Path, files: &[PathBuf]) -> AppResult<()> {
+    let result = write_zip(archive, base, files);
+    if result.is_err() {
+        let _ = fs::remove_file(archive);
+    }
+    for file in files {
+        let _ = fs::remove_file(file);
+        // Drop partition directories left empty; non-empty ones fail.
+        let mut directory = file.parent();
+        while let Some(dir) = directory.filter(|dir| *dir != base && dir.starts_with(base)) {
+            if fs::remove_dir(dir).is_err() {
+                break;
+            }
+            directory = dir.parent();
+        }
+    }
+    result
+}
+
+fn write_zip(archive: &Path, base: &Path, files: &[PathBuf]) -> AppResult<()> {
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(archive)?));
+    // Zip64 headers so members over 4 GiB can be written.
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+    for file in files {
+        let name = file
+            .strip_prefix(base)
+            .unwrap_or(file)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        zip.start_file(name, options)?;
+        io::copy(&mut File::open(file)?, &mut zip)?;
+    }
+    zip.finish()?.flush()?;
+    Ok(())
+}
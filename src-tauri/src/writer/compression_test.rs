@@ -0,0 +1,178 @@
+use super::compression::{bundle_zip, ExportCompression, OutputFile};
+use super::csv::{CsvExportOptions, CsvWriter};
+use super::destination::ExportDestination;
+use super::split::{FileSplitter, SplitOptions};
+use super::BatchWriter;
+use chrono::Local;
+use datafusion::arrow::array::{ArrayRef, Int64Array};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn temp_dir(name: &str) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!(
+        "easydb_compression_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).expect("temp dir");
+    path
+}
+
+fn rows() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![(
+        "id",
+        Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef,
+    )])
+    .expect("batch")
+}
+
+fn write_csv(path: &Path) {
+    let mut writer = CsvWriter::new(
+        &path.to_string_lossy(),
+        &rows().schema(),
+        b',',
+        &CsvExportOptions::default(),
+    )
+    .expect("writer");
+    writer.write_batch(&rows()).expect("write");
+    writer.finish().expect("finish");
+}
+
+fn decompress(path: &Path) -> String {
+    let file = std::fs::File::open(path).expect("open");
+    let mut text = String::new();
+    match ExportCompression::from_path(path) {
+        ExportCompression::Gzip => flate2::read::MultiGzDecoder::new(file)
+            .read_to_string(&mut text)
+            .expect("gunzip"),
+        ExportCompression::Zstd => zstd::stream::read::Decoder::new(file)
+            .expect("decoder")
+            .read_to_string(&mut text)
+            .expect("unzstd"),
+        ExportCompression::Bzip2 => bzip2::read::MultiBzDecoder::new(file)
+            .read_to_string(&mut text)
+            .expect("bunzip2"),
+        ExportCompression::None | ExportCompression::Zip => {
+            return std::fs::read_to_string(path).expect("read")
+        }
+    };
+    text
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_parse_compression() {
+    assert_eq!(
+        ExportCompression::parse(None).unwrap(),
+        ExportCompression::None
+    );
+    assert_eq!(
+        ExportCompression::parse(Some("GZIP")).unwrap(),
+        ExportCompression::Gzip
+    );
+    assert_eq!(
+        ExportCompression::parse(Some("zst")).unwrap(),
+        ExportCompression::Zstd
+    );
+    assert_eq!(
+        ExportCompression::parse(Some("bzip2")).unwrap(),
+        ExportCompression::Bzip2
+    );
+    assert_eq!(
+        ExportCompression::parse(Some("zip")).unwrap(),
+        ExportCompression::Zip
+    );
+    assert!(ExportCompression::parse(Some("rar")).is_err());
+}
+
+#[test]
+fn test_compressed_csv_round_trips() {
+    let dir = temp_dir("round_trip");
+    for name in ["out.csv.gz", "out.csv.zst", "out.csv.bz2"] {
+        let path = dir.join(name);
+        write_csv(&path);
+        let bytes = std::fs::read(&path).expect("read");
+        assert!(!bytes.starts_with(b"id"), "{} is compressed", name);
+        assert_eq!(decompress(&path), "id\n1\n2\n3\n", "{}", name);
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_append_adds_compressed_member() {
+    let dir = temp_dir("append");
+    let path = dir.join("out.sql.gz");
+    for statement in ["SELECT 1;\n", "SELECT 2;\n"] {
+        let mut out = OutputFile::append(&path).expect("open");
+        out.write_all(statement.as_bytes()).expect("write");
+        out.finish().expect("finish");
+    }
+    assert_eq!(decompress(&path), "SELECT 1;\nSELECT 2;\n");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_split_files_keep_compression_suffix() {
+    let dir = temp_dir("split");
+    let target = ExportDestination {
+        directory: Some(dir.to_string_lossy().to_string()),
+        file_name_template: Some("out".to_string()),
+        ..Default::default()
+    }
+    .resolve("csv.gz", None, true, Local::now())
+    .expect("target");
+    let options = SplitOptions {
+        max_rows_per_file: Some(2),
+        ..Default::default()
+    };
+    let mut splitter = FileSplitter::new(&target, &rows().schema(), &options, |path: &str| {
+        CsvWriter::new(path, &rows().schema(), b',', &CsvExportOptions::default())
+    })
+    .expect("splitter");
+    splitter.write_batch(&rows()).expect("write");
+    let files = splitter.finish().expect("finish");
+    assert_eq!(
+        files,
+        [dir.join("out_0001.csv.gz"), dir.join("out_0002.csv.gz")]
+    );
+    assert_eq!(decompress(&files[1]), "id\n3\n");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_bundle_zip_keeps_partition_directories() {
+    let dir = temp_dir("zip");
+    let east = dir.join("region=east").join("out.csv");
+    let west = dir.join("region=west").join("out.csv");
+    for file in [&east, &west] {
+        std::fs::create_dir_all(file.parent().unwrap()).expect("dir");
+        write_csv(file);
+    }
+    let archive = dir.join("out.zip");
+    bundle_zip(&archive, &dir, &[east.clone(), west.clone()]).expect("zip");
+
+    assert!(!east.exists() && !dir.join("region=east").exists());
+    assert!(!west.exists() && !dir.join("region=west").exists());
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(&archive).expect("open")).expect("zip");
+    let mut names: Vec<String> = zip.file_names().map(str::to_string).collect();
+    names.sort();
+    assert_eq!(names, ["region=east/out.csv", "region=west/out.csv"]);
+    let mut content = String::new();
+    zip.by_name("region=east/out.csv")
+        .expect("member")
+        .read_to_string(&mut content)
+        .expect("read");
+    assert_eq!(content, "id\n1\n2\n3\n");
+    let _ = std::fs::remove_dir_all(&dir);
+}
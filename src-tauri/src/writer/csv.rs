@@ -0,0 +1,256 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::writer::compression::OutputFile;
+use crate::writer::BatchWriter;
+use chrono::format::StrftimeItems;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::error::ArrowError;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use encoding_rs::{Encoding, GB18030, GBK};
+use serde::Deserialize;
+use std::io::{self, Write};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Options for a CSV or TSV export. Unset fields keep the defaults: a `,`
+/// (CSV) or tab (TSV) delimiter, `"` quotes where needed, a header row, empty
+/// NULLs, ISO 8601 dates, `\n` line endings and UTF-8 without BOM.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CsvExportOptions {
+    /// Single ASCII character separating fields.
+    pub delimiter: Option<String>,
+    /// Single ASCII character quoting fields.
+    pub quote: Option<String>,
+    /// Quote every field, not just those containing a delimiter, quote or
+    /// line break.
+    pub always_quote: bool,
+    pub header: Option<bool>,
+    /// Text written for NULL values.
+    pub null_value: Option<String>,
+    /// chrono format string for date columns, e.g. `%Y/%m/%d`.
+    pub date_format: Option<String>,
+    /// chrono format string for timestamp columns, e.g. `%Y-%m-%d %H:%M:%S`.
+    pub timestamp_format: Option<String>,
+    /// `lf` or `crlf`.
+    pub line_terminator: Option<String>,
+    /// `utf-8`, `utf-8-bom`, `gbk` or `gb18030`. Excel on Windows only detects
+    /// UTF-8 with a BOM; characters GBK cannot represent become `&#NNNN;`.
+    pub encoding: Option<String>,
+}
+
+/// Writes RecordBatches as delimited text.
+pub struct CsvWriter {
+    csv: csv::Writer<EncodedFile>,
+    null_value: String,
+    date_format: Option<String>,
+    timestamp_format: Option<String>,
+}
+
+impl CsvWriter {
+    /// `default_delimiter` applies when `options.delimiter` is unset.
+    pub fn new(
+        path: &str,
+        schema: &Schema,
+        default_delimiter: u8,
+        options: &CsvExportOptions,
+    ) -> AppResult<Self> {
+        Self::open(path, schema, default_delimiter, options, false)
+    }
+
+    /// Add rows to the end of an existing file. The header and BOM are only
+    /// written when the file is empty.
+    pub fn append(
+        path: &str,
+        schema: &Schema,
+        default_delimiter: u8,
+        options: &CsvExportOptions,
+    ) -> AppResult<Self> {
+        Self::open(path, schema, default_delimiter, options, true)
+    }
+
+    fn open(
+        path: &str,
+        schema: &Schema,
+        default_delimiter: u8,
+        options: &CsvExportOptions,
+        append: bool,
+    ) -> AppResult<Self> {
+        let delimiter = match &options.delimiter {
+            Some(delimiter) => single_byte("delimiter", delimiter)?,
+            None => default_delimiter,
+        };
+        let quote = match &options.quote {
+            Some(quote) => single_byte("quote", quote)?,
+            None => b'"',
+        };
+        let terminator = match options.line_terminator.as_deref().map(str::to_lowercase) {
+            None => csv::Terminator::Any(b'\n'),
+            Some(terminator) => match terminator.as_str() {
+                "lf" | "\n" => csv::Terminator::Any(b'\n'),
+                "crlf" | "\r\n" => csv::Terminator::CRLF,
+                _ => {
+                    return Err(AppError::BadRequest {
+                        message: format!(
+                            "Unsupported line terminator '{}'. Supported: lf, crlf",
+                            terminator
+                        ),
+                    })
+                }
+            },
+        };
+        let (encoding, bom) = match options.encoding.as_deref().map(str::to_lowercase) {
+            None => (None, false),
+            Some(encoding) => match encoding.as_str() {
+                "utf-8" | "utf8" => (None, false),
+                "utf-8-bom" | "utf8-bom" => (None, true),
+                "gbk" => (Some(GBK), false),
+                "gb18030" => (Some(GB18030), false),
+                _ => {
+                    return Err(AppError::BadRequest {
+                        message: format!(
+                            "Unsupported encoding '{}'. Supported: utf-8, utf-8-bom, gbk, gb18030",
+                            encoding
+                        ),
+                    })
+                }
+            },
+        };
+        for format in [&options.date_format, &options.timestamp_format]
+            .into_iter()
+            .flatten()
+        {
+            if StrftimeItems::new(format).parse().is_err() {
+                return Err(AppError::BadRequest {
+                    message: format!("Invalid date format '{}'", format),
+                });
+            }
+        }
+
+        let mut file = if append {
+            OutputFile::append(path)?
+        } else {
+            OutputFile::create(path)?
+        };
+        let preamble = file.starts_empty();
+        if bom && preamble {
+            file.write_all(UTF8_BOM)?;
+        }
+        let csv = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .quote(quote)
+            .quote_style(if options.always_quote {
+                csv::QuoteStyle::Always
+            } else {
+                csv::QuoteStyle::Necessary
+            })
+            .terminator(terminator)
+            .from_writer(EncodedFile {
+                file,
+                encoding,
+                pending: Vec::new(),
+            });
+
+        let mut writer = Self {
+            csv,
+            null_value: options.null_value.clone().unwrap_or_default(),
+            date_format: options.date_format.clone(),
+            timestamp_format: options.timestamp_format.clone(),
+        };
+        if preamble && options.header.unwrap_or(true) {
+            writer
+                .csv
+                .write_record(schema.fields().iter().map(|f| f.name().as_str()))
+                .map_err(csv_error)?;
+            writer.csv.flush()?;
+        }
+        Ok(writer)
+    }
+}
+
+/// The export file under the CSV writer, transcoding from UTF-8 when an
+/// encoding is set.
+struct EncodedFile {
+    file: OutputFile,
+    /// `None` for UTF-8.
+    encoding: Option<&'static Encoding>,
+    /// The start of a character split across writes.
+    pending: Vec<u8>,
+}
+
+impl Write for EncodedFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(encoding) = self.encoding else {
+            return self.file.write(buf);
+        };
+        self.pending.extend_from_slice(buf);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(e) => e.valid_up_to(),
+        };
+        if let Ok(text) = std::str::from_utf8(&self.pending[..valid]) {
+            let (bytes, _, _) = encoding.encode(text);
+            self.file.write_all(&bytes)?;
+        }
+        self.pending.drain(..valid);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl BatchWriter for CsvWriter {
+    fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        let options = FormatOptions::default()
+            .with_null(&self.null_value)
+            .with_date_format(self.date_format.as_deref())
+            .with_timestamp_format(self.timestamp_format.as_deref())
+            .with_timestamp_tz_format(self.timestamp_format.as_deref());
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|c| ArrayFormatter::try_new(c.as_ref(), &options))
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        let mut record = Vec::with_capacity(formatters.len());
+        for row_idx in 0..batch.num_rows() {
+            record.clear();
+            for formatter in &formatters {
+                record.push(formatter.value(row_idx).try_to_string()?);
+            }
+            self.csv.write_record(&record).map_err(csv_error)?;
+        }
+        self.csv.flush()?;
+        Ok(())
+    }
+
+    fn finish(self) -> AppResult<()> {
+        let sink = self
+            .csv
+            .into_inner()
+            .map_err(|err| AppError::from(err.into_error()))?;
+        sink.file.finish()
+    }
+
+    fn bytes_written(&self) -> Option<u64> {
+        Some(self.csv.get_ref().file.bytes())
+    }
+}
+
+fn single_byte(name: &str, value: &str) -> AppResult<u8> {
+    match value.as_bytes() {
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(AppError::BadRequest {
+            message: format!("CSV {} must be a single ASCII character", name),
+        }),
+    }
+}
+
+fn csv_error(error: csv::Error) -> AppError {
+    AppError::InternalServer {
+        message: error.to_string(),
+    }
+}
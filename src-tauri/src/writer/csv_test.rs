@@ -0,0 +1,191 @@
+use super::csv::{CsvExportOptions, CsvWriter};
+use super::BatchWriter;
+use datafusion::arrow::array::{
+    ArrayRef, Date32Array, Int64Array, StringArray, TimestampSecondArray,
+};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// id  name    born        seen_at
+/// 1   a,b     2022-01-08  2023-11-14T22:13:20
+/// 2   NULL    NULL        NULL
+fn rows() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+        (
+            "name",
+            Arc::new(StringArray::from(vec![Some("a,b"), None])) as ArrayRef,
+        ),
+        (
+            "born",
+            Arc::new(Date32Array::from(vec![Some(19_000), None])) as ArrayRef,
+        ),
+        (
+            "seen_at",
+            Arc::new(TimestampSecondArray::from(vec![Some(1_700_000_000), None])) as ArrayRef,
+        ),
+    ])
+    .expect("batch")
+}
+
+fn write_bytes(name: &str, default_delimiter: u8, options: &CsvExportOptions) -> Vec<u8> {
+    let mut path: PathBuf = std::env::temp_dir();
+    path.push(format!("easydb_writer_{}_{}.csv", name, std::process::id()));
+    let batch = rows();
+    let mut writer = CsvWriter::new(
+        &path.to_string_lossy(),
+        &batch.schema(),
+        default_delimiter,
+        options,
+    )
+    .expect("writer");
+    writer.write_batch(&batch).expect("write");
+    writer.finish().expect("finish");
+    let bytes = std::fs::read(&path).expect("read");
+    let _ = std::fs::remove_file(&path);
+    bytes
+}
+
+fn write(name: &str, default_delimiter: u8, options: &CsvExportOptions) -> String {
+    String::from_utf8(write_bytes(name, default_delimiter, options)).expect("utf-8")
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_default_csv() {
+    assert_eq!(
+        write("default", b',', &CsvExportOptions::default()),
+        "id,name,born,seen_at\n\
+         1,\"a,b\",2022-01-08,2023-11-14T22:13:20\n\
+         2,,,\n"
+    );
+}
+
+#[test]
+fn test_tsv_uses_tab_delimiter() {
+    assert_eq!(
+        write("tsv", b'\t', &CsvExportOptions::default()),
+        "id\tname\tborn\tseen_at\n\
+         1\ta,b\t2022-01-08\t2023-11-14T22:13:20\n\
+         2\t\t\t\n"
+    );
+}
+
+#[test]
+fn test_custom_options() {
+    let options = CsvExportOptions {
+        delimiter: Some(";".to_string()),
+        quote: Some("'".to_string()),
+        always_quote: true,
+        header: Some(false),
+        null_value: Some("NULL".to_string()),
+        date_format: Some("%d/%m/%Y".to_string()),
+        timestamp_format: Some("%Y-%m-%d %H:%M:%S".to_string()),
+        line_terminator: Some("crlf".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        write("custom", b',', &options),
+        "'1';'a,b';'08/01/2022';'2023-11-14 22:13:20'\r\n\
+         '2';'NULL';'NULL';'NULL'\r\n"
+    );
+}
+
+#[test]
+fn test_utf8_bom() {
+    let options = CsvExportOptions {
+        encoding: Some("utf-8-bom".to_string()),
+        ..Default::default()
+    };
+    let bytes = write_bytes("bom", b',', &options);
+    assert!(bytes.starts_with(b"\xEF\xBB\xBFid,name"));
+}
+
+#[test]
+fn test_gbk_encoding() {
+    let batch = RecordBatch::try_from_iter(vec![(
+        "名称",
+        Arc::new(StringArray::from(vec!["中文", "😀"])) as ArrayRef,
+    )])
+    .expect("batch");
+    let mut path: PathBuf = std::env::temp_dir();
+    path.push(format!("easydb_writer_gbk_{}.csv", std::process::id()));
+    let options = CsvExportOptions {
+        encoding: Some("GBK".to_string()),
+        ..Default::default()
+    };
+    let mut writer =
+        CsvWriter::new(&path.to_string_lossy(), &batch.schema(), b',', &options).expect("writer");
+    writer.write_batch(&batch).expect("write");
+    writer.finish().expect("finish");
+    let bytes = std::fs::read(&path).expect("read");
+    let _ = std::fs::remove_file(&path);
+
+    assert!(String::from_utf8(bytes.clone()).is_err(), "not UTF-8");
+    let (text, _, had_errors) = encoding_rs::GBK.decode(&bytes);
+    assert!(!had_errors);
+    // GBK has no emoji, so it is written as a character reference.
+    assert_eq!(text, "名称\n中文\n&#128512;\n");
+}
+
+#[test]
+fn test_invalid_options_error() {
+    let invalid = [
+        CsvExportOptions {
+            delimiter: Some(";;".to_string()),
+            ..Default::default()
+        },
+        CsvExportOptions {
+            quote: Some("”".to_string()),
+            ..Default::default()
+        },
+        CsvExportOptions {
+            line_terminator: Some("cr".to_string()),
+            ..Default::default()
+        },
+        CsvExportOptions {
+            encoding: Some("latin1".to_string()),
+            ..Default::default()
+        },
+        CsvExportOptions {
+            date_format: Some("%Q".to_string()),
+            ..Default::default()
+        },
+    ];
+    let mut path: PathBuf = std::env::temp_dir();
+    path.push(format!("easydb_writer_invalid_{}.csv", std::process::id()));
+    for options in &invalid {
+        let result = CsvWriter::new(&path.to_string_lossy(), &rows().schema(), b',', options);
+        assert!(result.is_err(), "{:?}", options);
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_append_skips_header() {
+    let mut path: PathBuf = std::env::temp_dir();
+    path.push(format!("easydb_writer_append_{}.csv", std::process::id()));
+    let options = CsvExportOptions {
+        encoding: Some("utf-8-bom".to_string()),
+        ..Default::default()
+    };
+    let batch = rows().slice(1, 1);
+    for _ in 0..2 {
+        let mut writer =
+            CsvWriter::append(&path.to_string_lossy(), &batch.schema(), b',', &options)
+                .expect("writer");
+        writer.write_batch(&batch).expect("write");
+        writer.finish().expect("finish");
+    }
+    let bytes = std::fs::read(&path).expect("read");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(bytes, b"\xEF\xBB\xBFid,name,born,seen_at\n2,,,\n2,,,\n");
+}
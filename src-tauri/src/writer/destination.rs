@@ -0,0 +1,285 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::writer::compression::OutputFile;
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_FILE_NAME_TEMPLATE: &str = "easydb_{timestamp}";
+
+/// Where an export is written. With nothing set the file goes to the
+/// Downloads folder (or the home folder when there is none) as
+/// `easydb_<timestamp>.<ext>`, replacing any file of the same name.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ExportDestination {
+    /// Full path of the output file, e.g. from a save dialog. The template
+    /// and directory are ignored when it is set.
+    pub file_path: Option<String>,
+    /// Directory the templated file name is placed in.
+    pub directory: Option<String>,
+    /// File name without extension. Supports `{query_name}`, `{table}`,
+    /// `{date}` (YYYYMMDD), `{time}` (HHMMSS) and `{timestamp}`
+    /// (YYYYMMDDHHMMSS).
+    pub file_name_template: Option<String>,
+    /// Value of `{query_name}`.
+    pub query_name: Option<String>,
+    /// `overwrite` (default), `append` or `fail`.
+    pub if_exists: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ExistsPolicy {
+    Overwrite,
+    Append,
+    Fail,
+}
+
+/// A validated output file.
+#[derive(Debug)]
+pub struct ExportTarget {
+    pub path: PathBuf,
+    /// Write after the existing content instead of replacing it.
+    pub append: bool,
+    /// Length of the file before an append, restored by `discard`.
+    existing_len: u64,
+    policy: ExistsPolicy,
+}
+
+impl ExportDestination {
+    /// Resolve the output file for `extension` and check that it can be
+    /// written, so a bad destination fails before the query runs.
+    /// `appendable` tells whether the file type can be appended to.
+    pub fn resolve(
+        &self,
+        extension: &str,
+        table: Option<&str>,
+        appendable: bool,
+        now: DateTime<Local>,
+    ) -> AppResult<ExportTarget> {
+        let policy = match self.if_exists.as_deref().map(str::to_lowercase) {
+            None => ExistsPolicy::Overwrite,
+            Some(policy) => match policy.as_str() {
+                "overwrite" => ExistsPolicy::Overwrite,
+                "append" => ExistsPolicy::Append,
+                "fail" => ExistsPolicy::Fail,
+                _ => {
+                    return Err(AppError::BadRequest {
+                        message: format!(
+                            "Unsupported if_exists policy '{}'. Supported: overwrite, append, fail",
+                            policy
+                        ),
+                    })
+                }
+            },
+        };
+        if policy == ExistsPolicy::Append && !appendable {
+            return Err(AppError::BadRequest {
+                message: format!("Appending is not supported for {} exports", extension),
+            });
+        }
+
+        let path = match &self.file_path {
+            Some(file_path) if !file_path.trim().is_empty() => PathBuf::from(file_path.trim()),
+            _ => {
+                let directory = match &self.directory {
+                    Some(directory) if !directory.trim().is_empty() => {
+                        PathBuf::from(directory.trim())
+                    }
+                    _ => default_directory()?,
+                };
+                let template = self
+                    .file_name_template
+                    .as_deref()
+                    .filter(|template| !template.trim().is_empty())
+                    .unwrap_or(DEFAULT_FILE_NAME_TEMPLATE);
+                let name = render_file_name(template, self.query_name.as_deref(), table, now)?;
+                directory.join(with_extension(name, extension))
+            }
+        };
+
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        if !directory.is_dir() {
+            return Err(AppError::BadRequest {
+                message: format!("Export directory '{}' does not exist", directory.display()),
+            });
+        }
+        if path.is_dir() {
+            return Err(AppError::BadRequest {
+                message: format!("Export path '{}' is a directory", path.display()),
+            });
+        }
+
+        let existing_len = match fs::metadata(&path) {
+            Ok(metadata) => {
+                if policy == ExistsPolicy::Fail {
+                    return Err(AppError::BadRequest {
+                        message: format!("Export file '{}' already exists", path.display()),
+                    });
+                }
+                if metadata.permissions().readonly() {
+                    return Err(AppError::BadRequest {
+                        message: format!("Export file '{}' is read-only", path.display()),
+                    });
+                }
+                metadata.len()
+            }
+            Err(_) => {
+                check_writable(&directory)?;
+                0
+            }
+        };
+
+        Ok(ExportTarget {
+            path,
+            append: policy == ExistsPolicy::Append && existing_len > 0,
+            existing_len,
+            policy,
+        })
+    }
+}
+
+impl ExportTarget {
+    /// Partitioned and split exports write new files next to `path`, which
+    /// can't be appended to.
+    pub fn check_split(&self) -> AppResult<()> {
+        if self.policy == ExistsPolicy::Append {
+            return Err(AppError::BadRequest {
+                message: "Appending is not supported for partitioned or split exports".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Apply the exists policy to a file derived from `path`.
+    pub fn check_new_file(&self, path: &Path) -> AppResult<()> {
+        if self.policy == ExistsPolicy::Fail && path != self.path && path.exists() {
+            return Err(AppError::BadRequest {
+                message: format!("Export file '{}' already exists", path.display()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Open the file for writing, after the existing content when appending.
+    pub fn open(&self) -> AppResult<OutputFile> {
+        if self.append {
+            OutputFile::append(&self.path)
+        } else {
+            OutputFile::create(&self.path)
+        }
+    }
+
+    /// Undo a failed export: drop what was appended, or remove the file.
+    pub fn discard(&self) {
+        if self.append {
+            if let Ok(file) = OpenOptions::new().write(true).open(&self.path) {
+                let _ = file.set_len(self.existing_len);
+            }
+        } else {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Downloads, falling back to the home folder for sessions without one.
+fn default_directory() -> AppResult<PathBuf> {
+    dirs::download_dir()
+        .filter(|directory| directory.is_dir())
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| AppError::BadRequest {
+            message: "Couldn't find a Downloads or home directory; choose an export directory"
+                .to_string(),
+        })
+}
+
+/// Create and remove a probe file, since permission bits don't tell the
+/// whole story (ACLs, read-only mounts).
+fn check_writable(directory: &Path) -> AppResult<()> {
+    let probe = directory.join(format!(".easydb_write_check_{}", std::process::id()));
+    match File::create(&probe) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            Ok(())
+        }
+        Err(e) => Err(AppError::BadRequest {
+            message: format!(
+                "Export directory '{}' is not writable: {}",
+                directory.display(),
+                e
+            ),
+        }),
+    }
+}
+
+/// Fill in the placeholders of a file name template.
+pub fn render_file_name(
+    template: &str,
+    query_name: Option<&str>,
+    table: Option<&str>,
+    now: DateTime<Local>,
+) -> AppResult<String> {
+    let mut name = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| AppError::BadRequest {
+                message: format!("Unclosed placeholder in file name template '{}'", template),
+            })?
+            + start;
+        let value = match &rest[start + 1..end] {
+            "query_name" => query_name.unwrap_or("query").to_string(),
+            "table" => table.or(query_name).unwrap_or("export").to_string(),
+            "date" => now.format("%Y%m%d").to_string(),
+            "time" => now.format("%H%M%S").to_string(),
+            "timestamp" => now.format("%Y%m%d%H%M%S").to_string(),
+            placeholder => {
+                return Err(AppError::BadRequest {
+                    message: format!(
+                        "Unknown placeholder '{{{}}}' in file name template. Supported: {{query_name}}, {{table}}, {{date}}, {{time}}, {{timestamp}}",
+                        placeholder
+                    ),
+                })
+            }
+        };
+        name.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    name.push_str(rest);
+
+    let name = sanitize_file_name(name.trim());
+    if name.is_empty() || name.chars().all(|c| c == '.') {
+        return Err(AppError::BadRequest {
+            message: format!("File name template '{}' gives an empty file name", template),
+        });
+    }
+    Ok(name)
+}
+
+/// Replace characters that are not allowed in file names on any platform.
+pub(crate) fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+fn with_extension(name: String, extension: &str) -> String {
+    if name
+        .to_lowercase()
+        .ends_with(&format!(".{}", extension.to_lowercase()))
+    {
+        name
+    } else {
+        format!("{}.{}", name, extension)
+    }
+}
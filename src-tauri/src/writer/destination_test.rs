@@ -0,0 +1,160 @@
+use super::destination::{render_file_name, ExportDestination};
+use chrono::{DateTime, Local, TimeZone};
+use std::path::{Path, PathBuf};
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn now() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2024, 3, 9, 7, 5, 1).unwrap()
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!(
+        "easydb_destination_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).expect("temp dir");
+    path
+}
+
+fn in_dir(dir: &Path, if_exists: Option<&str>) -> ExportDestination {
+    ExportDestination {
+        directory: Some(dir.to_string_lossy().to_string()),
+        file_name_template: Some("out".to_string()),
+        if_exists: if_exists.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+// ─── file name templates ──────────────────────────────────────────────
+
+#[test]
+fn test_render_placeholders() {
+    let name = render_file_name(
+        "{query_name}_{table}_{date}_{time}",
+        Some("daily"),
+        Some("orders"),
+        now(),
+    )
+    .expect("name");
+    assert_eq!(name, "daily_orders_20240309_070501");
+    assert_eq!(
+        render_file_name("easydb_{timestamp}", None, None, now()).expect("name"),
+        "easydb_20240309070501"
+    );
+}
+
+#[test]
+fn test_render_sanitizes_values() {
+    let name = render_file_name("{query_name}", Some("a/b:c?"), None, now()).expect("name");
+    assert_eq!(name, "a_b_c_");
+}
+
+#[test]
+fn test_render_rejects_bad_templates() {
+    for template in ["{unknown}", "open_{date", "{query_name}"] {
+        assert!(
+            render_file_name(template, Some("  "), None, now()).is_err(),
+            "{}",
+            template
+        );
+    }
+}
+
+// ─── resolving the target ─────────────────────────────────────────────
+
+#[test]
+fn test_resolve_in_directory_adds_extension() {
+    let dir = temp_dir("extension");
+    let target = in_dir(&dir, None)
+        .resolve("csv", None, true, now())
+        .expect("target");
+    assert_eq!(target.path, dir.join("out.csv"));
+    assert!(!target.append);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_file_path_wins_over_template() {
+    let dir = temp_dir("file_path");
+    let file = dir.join("chosen.data");
+    let destination = ExportDestination {
+        file_path: Some(file.to_string_lossy().to_string()),
+        ..in_dir(&dir, None)
+    };
+    let target = destination
+        .resolve("csv", None, true, now())
+        .expect("target");
+    assert_eq!(target.path, file);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_exists_policies() {
+    let dir = temp_dir("policies");
+    let file = dir.join("out.csv");
+    std::fs::write(&file, "id\n1\n").expect("write");
+
+    let overwrite = in_dir(&dir, Some("overwrite"))
+        .resolve("csv", None, true, now())
+        .expect("overwrite");
+    assert!(!overwrite.append);
+
+    let append = in_dir(&dir, Some("APPEND"))
+        .resolve("csv", None, true, now())
+        .expect("append");
+    assert!(append.append);
+
+    assert!(in_dir(&dir, Some("fail"))
+        .resolve("csv", None, true, now())
+        .is_err());
+    assert!(in_dir(&dir, Some("append"))
+        .resolve("parquet", None, false, now())
+        .is_err());
+    assert!(in_dir(&dir, Some("replace"))
+        .resolve("csv", None, true, now())
+        .is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_discard_restores_appended_file() {
+    let dir = temp_dir("discard");
+    let file = dir.join("out.sql");
+    std::fs::write(&file, "SELECT 1;").expect("write");
+    let target = in_dir(&dir, Some("append"))
+        .resolve("sql", None, true, now())
+        .expect("target");
+    {
+        use std::io::Write;
+        let mut out = target.open().expect("open");
+        out.write_all(b"\nINSERT").expect("append");
+    }
+    target.discard();
+    assert_eq!(std::fs::read_to_string(&file).expect("read"), "SELECT 1;");
+
+    let fresh = in_dir(&dir, None)
+        .resolve("csv", None, true, now())
+        .expect("fresh");
+    fresh.open().expect("create");
+    fresh.discard();
+    assert!(!dir.join("out.csv").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_missing_directory_fails_before_writing() {
+    let dir = temp_dir("missing").join("nope");
+    assert!(in_dir(&dir, None)
+        .resolve("csv", None, true, now())
+        .is_err());
+}
@@ -0,0 +1,345 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::writer::BatchWriter;
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use datafusion::arrow::array::timezone::Tz;
+use datafusion::arrow::array::{Array, AsArray, Float64Array};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{
+    ArrowTimestampType, DataType, Date32Type, Date64Type, Float64Type, SchemaRef, TimeUnit,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType,
+};
+use datafusion::arrow::error::ArrowError;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+
+/// Rows in an Excel worksheet, including the header row.
+pub const EXCEL_MAX_ROWS: usize = 1_048_576;
+/// Columns in an Excel worksheet.
+pub const EXCEL_MAX_COLUMNS: usize = 16_384;
+/// Characters in one Excel cell; longer text is cut off.
+const EXCEL_MAX_STRING_LEN: usize = 32_767;
+/// Integers above this lose digits as an Excel number and are written as text.
+const EXCEL_MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_992.0;
+/// Widest column Excel allows, in characters.
+const EXCEL_MAX_COLUMN_WIDTH: usize = 255;
+/// Data rows measured for column widths; later rows don't widen a column.
+const AUTOFIT_SAMPLE_ROWS: usize = 1_000;
+
+/// Writes RecordBatches into an .xlsx workbook.
+///
+/// Numbers, booleans, dates and timestamps become native cells; everything
+/// else is written as its display text. Each sheet starts with a bold, frozen
+/// header row, and a new sheet (`Sheet2`, `Sheet3`, ...) is started whenever
+/// one is full.
+///
+/// Sheets are written in constant memory mode, so finished rows go to a temp
+/// file instead of staying in memory. Column widths fit the header and the
+/// first [`AUTOFIT_SAMPLE_ROWS`] rows.
+pub struct ExcelWriter {
+    path: String,
+    schema: SchemaRef,
+    max_rows_per_sheet: usize,
+    workbook: Workbook,
+    sheet: Worksheet,
+    sheet_count: usize,
+    sheet_rows: usize,
+    /// Widest value seen per column, in characters.
+    widths: Vec<usize>,
+    sampled_rows: usize,
+    header_format: Format,
+    date_format: Format,
+    datetime_format: Format,
+}
+
+impl ExcelWriter {
+    pub fn new(path: String, schema: SchemaRef) -> AppResult<Self> {
+        if schema.fields().len() > EXCEL_MAX_COLUMNS {
+            return Err(AppError::BadRequest {
+                message: format!(
+                    "Excel supports at most {} columns, the result has {}",
+                    EXCEL_MAX_COLUMNS,
+                    schema.fields().len()
+                ),
+            });
+        }
+
+        let widths = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().chars().count())
+            .collect();
+        let mut writer = Self {
+            path,
+            schema,
+            max_rows_per_sheet: EXCEL_MAX_ROWS - 1,
+            workbook: Workbook::new(),
+            sheet: Worksheet::new(),
+            sheet_count: 0,
+            sheet_rows: 0,
+            widths,
+            sampled_rows: 0,
+            header_format: Format::new().set_bold(),
+            date_format: Format::new().set_num_format("yyyy-mm-dd"),
+            datetime_format: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+        };
+        writer.sheet = writer.new_sheet()?;
+        Ok(writer)
+    }
+
+    /// Data rows per sheet, below the header.
+    pub fn with_max_rows_per_sheet(mut self, max_rows_per_sheet: usize) -> Self {
+        self.max_rows_per_sheet = max_rows_per_sheet.clamp(1, EXCEL_MAX_ROWS - 1);
+        self
+    }
+
+    fn new_sheet(&mut self) -> AppResult<Worksheet> {
+        self.sheet_count += 1;
+        let mut sheet = self.workbook.new_worksheet_with_constant_memory();
+        sheet.set_name(format!("Sheet{}", self.sheet_count))?;
+        for (col_idx, field) in self.schema.fields().iter().enumerate() {
+            sheet.write_string_with_format(
+                0,
+                col_idx as u16,
+                field.name().as_str(),
+                &self.header_format,
+            )?;
+        }
+        sheet.set_freeze_panes(1, 0)?;
+        Ok(sheet)
+    }
+
+    fn write_cell(
+        &mut self,
+        row: u32,
+        col: u16,
+        array: &dyn Array,
+        cells: &ColumnCells,
+        formatter: &ArrayFormatter,
+        index: usize,
+    ) -> AppResult<()> {
+        let sheet = &mut self.sheet;
+        match cells {
+            ColumnCells::Boolean => {
+                sheet.write_boolean(row, col, array.as_boolean().value(index))?;
+            }
+            ColumnCells::Number { values, integer } => {
+                let value = values.value(index);
+                if *integer && value.abs() >= EXCEL_MAX_EXACT_INTEGER {
+                    sheet.write_string(row, col, formatter.value(index).to_string())?;
+                } else {
+                    sheet.write_number(row, col, value)?;
+                }
+            }
+            ColumnCells::Date => {
+                let date = match array.data_type() {
+                    DataType::Date32 => array.as_primitive::<Date32Type>().value_as_date(index),
+                    _ => array.as_primitive::<Date64Type>().value_as_date(index),
+                };
+                match date.filter(in_excel_range) {
+                    Some(date) => {
+                        sheet.write_datetime_with_format(row, col, date, &self.date_format)?;
+                    }
+                    None => {
+                        sheet.write_string(row, col, formatter.value(index).to_string())?;
+                    }
+                }
+            }
+            ColumnCells::Timestamp(timezone) => {
+                match timestamp_value(array, index, timezone.as_ref())
+                    .filter(|date_time| in_excel_range(&date_time.date()))
+                {
+                    Some(date_time) => {
+                        sheet.write_datetime_with_format(
+                            row,
+                            col,
+                            date_time,
+                            &self.datetime_format,
+                        )?;
+                    }
+                    None => {
+                        sheet.write_string(row, col, formatter.value(index).to_string())?;
+                    }
+                }
+            }
+            ColumnCells::Text => {
+                let text = formatter.value(index).to_string();
+                let text = match text.char_indices().nth(EXCEL_MAX_STRING_LEN) {
+                    Some((end, _)) => text[..end].to_string(),
+                    None => text,
+                };
+                sheet.write_string(row, col, text)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BatchWriter for ExcelWriter {
+    fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        let options = FormatOptions::default();
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|c| ArrayFormatter::try_new(c.as_ref(), &options))
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+        let cells = batch
+            .columns()
+            .iter()
+            .map(|c| ColumnCells::new(c.as_ref()))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        for row_idx in 0..batch.num_rows() {
+            if self.sheet_rows == self.max_rows_per_sheet {
+                let next = self.new_sheet()?;
+                let mut full = std::mem::replace(&mut self.sheet, next);
+                fit_columns(&mut full, &self.widths)?;
+                self.workbook.push_worksheet(full);
+                self.sheet_rows = 0;
+            }
+            self.sheet_rows += 1;
+            let row = self.sheet_rows as u32;
+
+            for (col_idx, column) in batch.columns().iter().enumerate() {
+                if column.is_null(row_idx) {
+                    continue;
+                }
+                self.write_cell(
+                    row,
+                    col_idx as u16,
+                    column.as_ref(),
+                    &cells[col_idx],
+                    &formatters[col_idx],
+                    row_idx,
+                )?;
+            }
+
+            if self.sampled_rows < AUTOFIT_SAMPLE_ROWS {
+                self.sampled_rows += 1;
+                for (col_idx, column) in batch.columns().iter().enumerate() {
+                    if !column.is_null(row_idx) {
+                        let width = cell_width(&cells[col_idx], &formatters[col_idx], row_idx);
+                        self.widths[col_idx] = self.widths[col_idx].max(width);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Save the workbook to `path`.
+    fn finish(mut self) -> AppResult<()> {
+        fit_columns(&mut self.sheet, &self.widths)?;
+        self.workbook.push_worksheet(self.sheet);
+        self.workbook.save(&self.path)?;
+        Ok(())
+    }
+}
+
+/// How the cells of one column are written, prepared once per batch.
+enum ColumnCells {
+    Boolean,
+    /// Numeric columns cast to f64; `integer` marks integer sources, whose
+    /// values past 2^53 are written as text instead.
+    Number { values: Float64Array, integer: bool },
+    Date,
+    /// Wall-clock time, in the column's own time zone if it has one.
+    Timestamp(Option<Tz>),
+    Text,
+}
+
+impl ColumnCells {
+    fn new(array: &dyn Array) -> AppResult<Self> {
+        Ok(match array.data_type() {
+            DataType::Boolean => ColumnCells::Boolean,
+            data_type if data_type.is_integer() || data_type.is_floating() => {
+                ColumnCells::Number {
+                    values: cast(array, &DataType::Float64)?
+                        .as_primitive::<Float64Type>()
+                        .clone(),
+                    integer: data_type.is_integer(),
+                }
+            }
+            DataType::Decimal32(_, _)
+            | DataType::Decimal64(_, _)
+            | DataType::Decimal128(_, _)
+            | DataType::Decimal256(_, _) => ColumnCells::Number {
+                values: cast(array, &DataType::Float64)?
+                    .as_primitive::<Float64Type>()
+                    .clone(),
+                integer: false,
+            },
+            DataType::Date32 | DataType::Date64 => ColumnCells::Date,
+            DataType::Timestamp(_, timezone) => ColumnCells::Timestamp(
+                timezone
+                    .as_ref()
+                    .and_then(|timezone| timezone.parse::<Tz>().ok()),
+            ),
+            _ => ColumnCells::Text,
+        })
+    }
+}
+
+/// Characters a cell shows with its number format applied.
+fn cell_width(cells: &ColumnCells, formatter: &ArrayFormatter, index: usize) -> usize {
+    match cells {
+        ColumnCells::Boolean => "FALSE".len(),
+        ColumnCells::Date => "yyyy-mm-dd".len(),
+        ColumnCells::Timestamp(_) => "yyyy-mm-dd hh:mm:ss".len(),
+        ColumnCells::Number { .. } | ColumnCells::Text => {
+            formatter.value(index).to_string().chars().count()
+        }
+    }
+}
+
+fn fit_columns(sheet: &mut Worksheet, widths: &[usize]) -> AppResult<()> {
+    for (col_idx, width) in widths.iter().enumerate() {
+        let width = (width + 2).min(EXCEL_MAX_COLUMN_WIDTH);
+        sheet.set_column_width(col_idx as u16, width as f64)?;
+    }
+    Ok(())
+}
+
+fn timestamp_value(
+    array: &dyn Array,
+    index: usize,
+    timezone: Option<&Tz>,
+) -> Option<NaiveDateTime> {
+    match array.data_type() {
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            local_time::<TimestampSecondType>(array, index, timezone)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            local_time::<TimestampMillisecondType>(array, index, timezone)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            local_time::<TimestampMicrosecondType>(array, index, timezone)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            local_time::<TimestampNanosecondType>(array, index, timezone)
+        }
+        _ => None,
+    }
+}
+
+fn local_time<T: ArrowTimestampType>(
+    array: &dyn Array,
+    index: usize,
+    timezone: Option<&Tz>,
+) -> Option<NaiveDateTime> {
+    let values = array.as_primitive::<T>();
+    match timezone {
+        Some(timezone) => values
+            .value_as_datetime_with_tz(index, *timezone)
+            .map(|date_time| date_time.naive_local()),
+        None => values.value_as_datetime(index),
+    }
+}
+
+/// Excel dates run from 1900-01-01 to 9999-12-31.
+fn in_excel_range(date: &NaiveDate) -> bool {
+    (1900..=9999).contains(&date.year())
+}
@@ -0,0 +1,227 @@
+use super::excel::{ExcelWriter, EXCEL_MAX_COLUMNS};
+use super::BatchWriter;
+use calamine::{open_workbook, Data, Range, Reader, Xlsx};
+use datafusion::arrow::array::{
+    ArrayRef, BooleanArray, Date32Array, Float64Array, Int64Array, StringArray,
+    TimestampSecondArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn temp_xlsx(name: &str) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_writer_{}_{}.xlsx", name, std::process::id()));
+    path
+}
+
+/// id                 price  active  name  born        seen_at (UTC+09:00)
+/// 1                  1.5    true    a     2022-01-08  2023-11-15 07:13:20
+/// 9007199254740993   NULL   false   NULL  NULL        NULL
+fn typed_batch() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![
+        (
+            "id",
+            Arc::new(Int64Array::from(vec![1, 9_007_199_254_740_993])) as ArrayRef,
+        ),
+        (
+            "price",
+            Arc::new(Float64Array::from(vec![Some(1.5), None])) as ArrayRef,
+        ),
+        (
+            "active",
+            Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+        ),
+        (
+            "name",
+            Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+        ),
+        (
+            "born",
+            Arc::new(Date32Array::from(vec![Some(19_000), None])) as ArrayRef,
+        ),
+        (
+            "seen_at",
+            Arc::new(
+                TimestampSecondArray::from(vec![Some(1_700_000_000), None])
+                    .with_timezone("+09:00"),
+            ) as ArrayRef,
+        ),
+    ])
+    .expect("batch")
+}
+
+fn write(path: &Path, batches: &[RecordBatch], max_rows_per_sheet: Option<usize>) {
+    let schema = batches[0].schema();
+    let mut writer =
+        ExcelWriter::new(path.to_string_lossy().to_string(), schema).expect("writer");
+    if let Some(max_rows) = max_rows_per_sheet {
+        writer = writer.with_max_rows_per_sheet(max_rows);
+    }
+    for batch in batches {
+        writer.write_batch(batch).expect("write");
+    }
+    writer.finish().expect("save");
+}
+
+fn read(path: &Path) -> Vec<(String, Range<Data>)> {
+    let mut workbook: Xlsx<_> = open_workbook(path).expect("open");
+    let sheets = workbook
+        .sheet_names()
+        .into_iter()
+        .map(|name| {
+            let range = workbook.worksheet_range(&name).expect("sheet");
+            (name, range)
+        })
+        .collect();
+    let _ = std::fs::remove_file(path);
+    sheets
+}
+
+/// Column widths from the first sheet's `<col>` elements, in column order.
+fn column_widths(path: &Path) -> Vec<f64> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path).expect("open")).expect("zip");
+    let mut xml = String::new();
+    archive
+        .by_name("xl/worksheets/sheet1.xml")
+        .expect("sheet1")
+        .read_to_string(&mut xml)
+        .expect("read");
+    xml.split("<col ")
+        .skip(1)
+        .map(|col| {
+            let width = col.split("width=\"").nth(1).expect("width");
+            width[..width.find('"').expect("quote")].parse().expect("number")
+        })
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_writes_native_cells() {
+    let path = temp_xlsx("native");
+    write(&path, &[typed_batch()], None);
+    let sheets = read(&path);
+    assert_eq!(sheets.len(), 1);
+    let (name, range) = &sheets[0];
+    assert_eq!(name, "Sheet1");
+
+    let header: Vec<String> = range
+        .rows()
+        .next()
+        .unwrap()
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(header, ["id", "price", "active", "name", "born", "seen_at"]);
+
+    let row = &range.rows().nth(1).unwrap();
+    assert_eq!(row[0], Data::Float(1.0));
+    assert_eq!(row[1], Data::Float(1.5));
+    assert_eq!(row[2], Data::Bool(true));
+    assert_eq!(row[3], Data::String("a".to_string()));
+    match &row[4] {
+        Data::DateTime(date) => assert_eq!(
+            date.as_datetime().unwrap().to_string(),
+            "2022-01-08 00:00:00"
+        ),
+        other => panic!("expected a date, got {:?}", other),
+    }
+    // Zoned timestamps are written as wall-clock time in their own zone.
+    match &row[5] {
+        Data::DateTime(date) => assert_eq!(
+            date.as_datetime().unwrap().to_string(),
+            "2023-11-15 07:13:20"
+        ),
+        other => panic!("expected a timestamp, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_large_integers_and_nulls() {
+    let path = temp_xlsx("large_int");
+    write(&path, &[typed_batch()], None);
+    let sheets = read(&path);
+    let row = sheets[0].1.rows().nth(2).unwrap().to_vec();
+    // Past 2^53 an Excel number would lose the last digit.
+    assert_eq!(row[0], Data::String("9007199254740993".to_string()));
+    assert_eq!(row[1], Data::Empty);
+    assert_eq!(row[2], Data::Bool(false));
+    assert_eq!(row[3], Data::Empty);
+}
+
+#[test]
+fn test_splits_rows_across_sheets() {
+    let path = temp_xlsx("split");
+    let batch = RecordBatch::try_from_iter(vec![(
+        "id",
+        Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef,
+    )])
+    .expect("batch");
+    write(&path, &[batch.slice(0, 3), batch.slice(3, 2)], Some(2));
+
+    let sheets = read(&path);
+    let names: Vec<&str> = sheets.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["Sheet1", "Sheet2", "Sheet3"]);
+    for (index, (_, range)) in sheets.iter().enumerate() {
+        let rows: Vec<Vec<Data>> = range.rows().map(|row| row.to_vec()).collect();
+        assert_eq!(rows[0], [Data::String("id".to_string())], "header on every sheet");
+        let expected = if index < 2 { 2 } else { 1 };
+        assert_eq!(rows.len() - 1, expected);
+        assert_eq!(rows[1][0], Data::Float((index * 2 + 1) as f64));
+    }
+}
+
+#[test]
+fn test_empty_result_keeps_header() {
+    let path = temp_xlsx("empty");
+    let batch = typed_batch().slice(0, 0);
+    write(&path, &[batch], None);
+    let sheets = read(&path);
+    assert_eq!(sheets[0].1.rows().count(), 1);
+}
+
+#[test]
+fn test_rejects_too_many_columns() {
+    let fields: Vec<Field> = (0..=EXCEL_MAX_COLUMNS)
+        .map(|i| Field::new(format!("c{}", i), DataType::Int64, true))
+        .collect();
+    let result = ExcelWriter::new(
+        temp_xlsx("wide").to_string_lossy().to_string(),
+        Arc::new(Schema::new(fields)),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_column_widths_fit_the_sampled_rows() {
+    let path = temp_xlsx("widths");
+    // A long value in the first rows widens its column; one past the sampled
+    // rows doesn't.
+    let mut short = vec!["a".to_string(); 1_500];
+    short[1_400] = "x".repeat(200);
+    let mut long = vec!["b".to_string(); 1_500];
+    long[0] = "y".repeat(40);
+    let batch = RecordBatch::try_from_iter(vec![
+        ("short", Arc::new(StringArray::from(short)) as ArrayRef),
+        ("long", Arc::new(StringArray::from(long)) as ArrayRef),
+    ])
+    .expect("batch");
+    write(&path, &[batch], None);
+
+    let widths = column_widths(&path);
+    let rows = read(&path)[0].1.rows().count();
+    assert_eq!(rows, 1_501);
+    assert_eq!(widths.len(), 2);
+    assert!(widths[0] < 10.0, "short column: {:?}", widths);
+    assert!(widths[1] > 40.0, "long column: {:?}", widths);
+}
@@ -0,0 +1,35 @@
+use crate::context::schema::AppResult;
+use crate::writer::compression::OutputFile;
+use crate::writer::BatchWriter;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::ipc::writer::FileWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+
+/// Writes RecordBatches to an Arrow IPC file (`.arrow`, a.k.a. Feather v2).
+pub struct ArrowIpcWriter {
+    writer: FileWriter<OutputFile>,
+}
+
+impl ArrowIpcWriter {
+    pub fn new(path: &str, schema: &Schema) -> AppResult<Self> {
+        Ok(Self {
+            writer: FileWriter::try_new(OutputFile::create(path)?, schema)?,
+        })
+    }
+}
+
+impl BatchWriter for ArrowIpcWriter {
+    fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        self.writer.write(batch)?;
+        Ok(())
+    }
+
+    fn finish(mut self) -> AppResult<()> {
+        self.writer.finish()?;
+        self.writer.into_inner()?.finish()
+    }
+
+    fn bytes_written(&self) -> Option<u64> {
+        Some(self.writer.get_ref().bytes())
+    }
+}
@@ -0,0 +1,38 @@
+use super::ipc::ArrowIpcWriter;
+use super::BatchWriter;
+use datafusion::arrow::array::{ArrayRef, Int64Array, StringArray};
+use datafusion::arrow::ipc::reader::FileReader;
+use datafusion::arrow::record_batch::RecordBatch;
+use std::fs::File;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_arrow_ipc_round_trip() {
+    let batch = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+        (
+            "name",
+            Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+        ),
+    ])
+    .expect("batch");
+
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_writer_{}_round_trip.arrow", std::process::id()));
+    let mut writer =
+        ArrowIpcWriter::new(path.to_str().unwrap(), &batch.schema()).expect("writer");
+    writer.write_batch(&batch).expect("write");
+    writer.write_batch(&batch.slice(1, 1)).expect("write");
+    writer.finish().expect("finish");
+
+    let reader = FileReader::try_new(File::open(&path).expect("open"), None).expect("reader");
+    let batches = reader.collect::<Result<Vec<_>, _>>().expect("batches");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0], batch);
+    assert_eq!(batches[1], batch.slice(1, 1));
+}
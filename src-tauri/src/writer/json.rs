@@ -0,0 +1,74 @@
+use crate::context::schema::AppResult;
+use crate::writer::compression::OutputFile;
+use crate::writer::BatchWriter;
+use datafusion::arrow::json::writer::{JsonArray, LineDelimited, Writer, WriterBuilder};
+use datafusion::arrow::record_batch::RecordBatch;
+
+/// Writes RecordBatches as JSON objects, one per row, keyed by column name.
+///
+/// Null values are written as explicit `null`s so every object carries every
+/// column.
+pub enum JsonWriter {
+    /// A single JSON array.
+    Array(Writer<OutputFile, JsonArray>),
+    /// Newline-delimited JSON, one object per line.
+    Lines(Writer<OutputFile, LineDelimited>),
+}
+
+impl JsonWriter {
+    pub fn array(path: &str) -> AppResult<Self> {
+        Ok(JsonWriter::Array(
+            WriterBuilder::new()
+                .with_explicit_nulls(true)
+                .build(OutputFile::create(path)?),
+        ))
+    }
+
+    pub fn lines(path: &str) -> AppResult<Self> {
+        Ok(JsonWriter::Lines(
+            WriterBuilder::new()
+                .with_explicit_nulls(true)
+                .build(OutputFile::create(path)?),
+        ))
+    }
+
+    /// Newline-delimited JSON added to the end of an existing file.
+    pub fn append_lines(path: &str) -> AppResult<Self> {
+        Ok(JsonWriter::Lines(
+            WriterBuilder::new()
+                .with_explicit_nulls(true)
+                .build(OutputFile::append(path)?),
+        ))
+    }
+}
+
+impl BatchWriter for JsonWriter {
+    fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        match self {
+            JsonWriter::Array(writer) => writer.write(batch)?,
+            JsonWriter::Lines(writer) => writer.write(batch)?,
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> AppResult<()> {
+        let file = match self {
+            JsonWriter::Array(mut writer) => {
+                writer.finish()?;
+                writer.into_inner()
+            }
+            JsonWriter::Lines(mut writer) => {
+                writer.finish()?;
+                writer.into_inner()
+            }
+        };
+        file.finish()
+    }
+
+    fn bytes_written(&self) -> Option<u64> {
+        Some(match self {
+            JsonWriter::Array(writer) => writer.get_ref().bytes(),
+            JsonWriter::Lines(writer) => writer.get_ref().bytes(),
+        })
+    }
+}
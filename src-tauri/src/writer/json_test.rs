@@ -0,0 +1,77 @@
+use super::json::JsonWriter;
+use super::BatchWriter;
+use datafusion::arrow::array::{ArrayRef, Int64Array, StringArray};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn temp_path(name: &str) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_writer_{}_{}", std::process::id(), name));
+    path
+}
+
+fn rows() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+        (
+            "name",
+            Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+        ),
+    ])
+    .expect("batch")
+}
+
+fn write(mut writer: JsonWriter, path: &Path, batches: &[RecordBatch]) -> String {
+    for batch in batches {
+        writer.write_batch(batch).expect("write");
+    }
+    writer.finish().expect("finish");
+    let content = std::fs::read_to_string(path).expect("read");
+    let _ = std::fs::remove_file(path);
+    content
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_json_array_spans_batches() {
+    let path = temp_path("array.json");
+    let writer = JsonWriter::array(path.to_str().unwrap()).expect("writer");
+    let content = write(writer, &path, &[rows(), rows().slice(0, 1)]);
+    let value: serde_json::Value = serde_json::from_str(&content).expect("valid json");
+    assert_eq!(
+        value,
+        serde_json::json!([
+            {"id": 1, "name": "a"},
+            {"id": 2, "name": null},
+            {"id": 1, "name": "a"}
+        ])
+    );
+}
+
+#[test]
+fn test_ndjson_one_object_per_line() {
+    let path = temp_path("lines.ndjson");
+    let writer = JsonWriter::lines(path.to_str().unwrap()).expect("writer");
+    let content = write(writer, &path, &[rows()]);
+    assert_eq!(
+        content,
+        "{\"id\":1,\"name\":\"a\"}\n{\"id\":2,\"name\":null}\n"
+    );
+}
+
+#[test]
+fn test_empty_json_array() {
+    let path = temp_path("empty.json");
+    let writer = JsonWriter::array(path.to_str().unwrap()).expect("writer");
+    let content = write(writer, &path, &[]);
+    let value: serde_json::Value = serde_json::from_str(&content).expect("valid json");
+    assert_eq!(value, serde_json::json!([]));
+}
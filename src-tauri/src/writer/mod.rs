@@ -0,0 +1,75 @@
+use crate::context::schema::AppResult;
+use datafusion::arrow::record_batch::RecordBatch;
+use std::io::{self, Write};
+
+pub mod compression;
+pub mod csv;
+pub mod destination;
+pub mod excel;
+pub mod ipc;
+pub mod json;
+pub mod parquet;
+pub mod split;
+pub mod spool;
+pub mod sql;
+
+#[cfg(test)]
+mod compression_test;
+#[cfg(test)]
+mod csv_test;
+#[cfg(test)]
+mod destination_test;
+#[cfg(test)]
+mod excel_test;
+#[cfg(test)]
+mod ipc_test;
+#[cfg(test)]
+mod json_test;
+#[cfg(test)]
+mod parquet_test;
+#[cfg(test)]
+mod split_test;
+#[cfg(test)]
+mod spool_test;
+
+/// A file format that query results are written to one batch at a time.
+pub trait BatchWriter {
+    fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()>;
+
+    /// Complete the file once the last batch has been written.
+    fn finish(self) -> AppResult<()>;
+
+    /// Bytes written so far, used to split exports by size. `None` for
+    /// formats that only know their size once finished.
+    fn bytes_written(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Counts the bytes written through it.
+pub struct CountingWriter<W: Write> {
+    inner: W,
+    bytes: u64,
+}
+
+impl<W: Write> CountingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, bytes: 0 }
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
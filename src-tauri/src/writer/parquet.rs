@@ -0,0 +1,84 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::writer::BatchWriter;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::parquet::basic::{Compression, ZstdLevel};
+use datafusion::parquet::file::properties::WriterProperties;
+use serde::Deserialize;
+use std::fs::File;
+use std::str::FromStr;
+
+/// Rows per row group when none is given, as in DataFusion.
+const DEFAULT_ROW_GROUP_SIZE: usize = 1024 * 1024;
+
+/// Options for a Parquet export; unset fields keep DataFusion's defaults
+/// (`zstd(3)`, 1Mi rows per row group).
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ParquetExportOptions {
+    /// Codec name as DataFusion spells it, e.g. `snappy`, `zstd(3)`,
+    /// `gzip(6)` or `uncompressed`.
+    pub compression: Option<String>,
+    /// Maximum rows per row group.
+    pub row_group_size: Option<usize>,
+}
+
+impl ParquetExportOptions {
+    pub fn writer_properties(&self) -> AppResult<WriterProperties> {
+        let compression = match &self.compression {
+            Some(compression) => {
+                let compression = compression.trim().to_lowercase();
+                Compression::from_str(&compression).map_err(|e| AppError::BadRequest {
+                    message: format!("Invalid Parquet compression '{}': {}", compression, e),
+                })?
+            }
+            None => Compression::ZSTD(ZstdLevel::try_new(3)?),
+        };
+        let row_group_size = match self.row_group_size {
+            Some(0) => {
+                return Err(AppError::BadRequest {
+                    message: "Parquet row group size must be at least 1".to_string(),
+                })
+            }
+            Some(row_group_size) => row_group_size,
+            None => DEFAULT_ROW_GROUP_SIZE,
+        };
+        Ok(WriterProperties::builder()
+            .set_compression(compression)
+            .set_max_row_group_row_count(Some(row_group_size))
+            .build())
+    }
+}
+
+/// Writes RecordBatches to a Parquet file.
+pub struct ParquetWriter {
+    writer: ArrowWriter<File>,
+}
+
+impl ParquetWriter {
+    pub fn new(path: &str, schema: SchemaRef, options: &ParquetExportOptions) -> AppResult<Self> {
+        let properties = options.writer_properties()?;
+        Ok(Self {
+            writer: ArrowWriter::try_new(File::create(path)?, schema, Some(properties))?,
+        })
+    }
+}
+
+impl BatchWriter for ParquetWriter {
+    fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        self.writer.write(batch)?;
+        Ok(())
+    }
+
+    fn finish(self) -> AppResult<()> {
+        self.writer.close()?;
+        Ok(())
+    }
+
+    /// Includes the estimated size of the row group still being encoded.
+    fn bytes_written(&self) -> Option<u64> {
+        Some((self.writer.bytes_written() + self.writer.in_progress_size()) as u64)
+    }
+}
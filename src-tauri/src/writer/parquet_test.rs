@@ -0,0 +1,75 @@
+use super::parquet::{ParquetExportOptions, ParquetWriter};
+use super::BatchWriter;
+use datafusion::arrow::array::{ArrayRef, Int64Array};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::parquet::basic::{Compression, ZstdLevel};
+use datafusion::parquet::file::reader::{FileReader, SerializedFileReader};
+use datafusion::parquet::schema::types::ColumnPath;
+use std::fs::File;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_default_options_keep_datafusion_defaults() {
+    let properties = ParquetExportOptions::default()
+        .writer_properties()
+        .expect("ok");
+    assert_eq!(
+        properties.compression(&ColumnPath::from("id")),
+        Compression::ZSTD(ZstdLevel::try_new(3).unwrap())
+    );
+    assert_eq!(properties.max_row_group_row_count(), Some(1024 * 1024));
+}
+
+#[test]
+fn test_invalid_options_rejected() {
+    let bad_codec = ParquetExportOptions {
+        compression: Some("zip".to_string()),
+        ..Default::default()
+    };
+    let msg = format!("{:?}", bad_codec.writer_properties().unwrap_err());
+    assert!(msg.contains("Invalid Parquet compression"), "got: {}", msg);
+
+    let empty_groups = ParquetExportOptions {
+        row_group_size: Some(0),
+        ..Default::default()
+    };
+    assert!(empty_groups.writer_properties().is_err());
+}
+
+#[test]
+fn test_write_parquet_with_options() {
+    let batch = RecordBatch::try_from_iter(vec![(
+        "id",
+        Arc::new(Int64Array::from((1..=10).collect::<Vec<i64>>())) as ArrayRef,
+    )])
+    .expect("batch");
+    let options = ParquetExportOptions {
+        compression: Some("Snappy".to_string()),
+        row_group_size: Some(4),
+    };
+
+    let mut path = std::env::temp_dir();
+    path.push(format!(
+        "easydb_writer_{}_options.parquet",
+        std::process::id()
+    ));
+    let mut writer =
+        ParquetWriter::new(path.to_str().unwrap(), batch.schema(), &options).expect("writer");
+    writer.write_batch(&batch).expect("write");
+    assert!(writer.bytes_written().unwrap() > 0);
+    writer.finish().expect("finish");
+
+    let reader = SerializedFileReader::new(File::open(&path).expect("open")).expect("reader");
+    let metadata = reader.metadata();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(metadata.file_metadata().num_rows(), 10);
+    assert_eq!(metadata.num_row_groups(), 3);
+    assert_eq!(
+        metadata.row_group(0).column(0).compression(),
+        Compression::SNAPPY
+    );
+}
@@ -0,0 +1,505 @@
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use crate::writer::compression::ExportCompression;
+use crate::writer::destination::ExportTarget;
+use crate::writer::BatchWriter;
+use datafusion::arrow::array::{Array, UInt32Array};
+use datafusion::arrow::compute::take_record_batch;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::error::ArrowError;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use datafusion::dataframe::DataFrame;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Rows written at a time when splitting by size, so a file grows past its
+/// limit by at most this many rows.
+const SIZE_CHECK_ROWS: usize = 1024;
+/// Partition files kept open at once. Past this the least recently written
+/// one is finished, and the partition continues in a new file.
+const MAX_OPEN_FILES: usize = 64;
+/// Hive's directory value for NULL partitions.
+const HIVE_NULL_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// How an export is spread over several files. With nothing set everything
+/// goes into the single target file.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SplitOptions {
+    /// Columns whose values pick the file of each row.
+    pub partition_by: Vec<String>,
+    /// `hive` (default) for `col=value/name.ext` directories, or `suffix` for
+    /// `name_value.ext` files.
+    pub partition_style: Option<String>,
+    /// Start a new file after this many rows.
+    pub max_rows_per_file: Option<usize>,
+    /// Start a new file once this many bytes are written. Not supported for
+    /// xlsx, whose size is only known once it is saved.
+    pub max_bytes_per_file: Option<u64>,
+}
+
+impl SplitOptions {
+    pub fn is_split(&self) -> bool {
+        !self.partition_by.is_empty()
+            || self.max_rows_per_file.is_some()
+            || self.max_bytes_per_file.is_some()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PartitionStyle {
+    Hive,
+    Suffix,
+}
+
+/// The files of one partition, or of the whole export.
+struct Output<W> {
+    /// File path before numbering.
+    path: PathBuf,
+    writer: Option<W>,
+    /// Number each file, for exports split by size.
+    numbered: bool,
+    /// Number of the current file, from 1.
+    part: usize,
+    rows: usize,
+    /// When the current file was last written to, for closing the least
+    /// recently written file.
+    last_write: u64,
+}
+
+/// Where the files of each partition go.
+struct Layout<'a> {
+    target: &'a ExportTarget,
+    /// `(index, name)` of the partition columns.
+    partition_columns: Vec<(usize, String)>,
+    style: PartitionStyle,
+    numbered: bool,
+}
+
+/// Routes RecordBatches to the files of a partitioned or size-split export,
+/// opening each one with `open`.
+///
+/// Split files are numbered `name_0001.ext`, `name_0002.ext`, ... Partitions
+/// keep their file open until the export finishes, up to
+/// [`MAX_OPEN_FILES`]; a partition whose file was closed to stay under the
+/// limit continues in `name_0002.ext`, `name_0003.ext`, ...
+pub struct FileSplitter<'a, W, F> {
+    layout: Layout<'a>,
+    open: F,
+    max_rows: Option<usize>,
+    max_bytes: Option<u64>,
+    max_open_files: usize,
+    writes: u64,
+    outputs: HashMap<Vec<Option<String>>, Output<W>>,
+    /// Partition of each output path, lowercased to also catch paths that
+    /// only differ in case on case-insensitive file systems.
+    partition_paths: HashMap<String, Vec<Option<String>>>,
+    files: Vec<PathBuf>,
+}
+
+impl<'a, W, F> FileSplitter<'a, W, F>
+where
+    W: BatchWriter,
+    F: FnMut(&str) -> AppResult<W>,
+{
+    pub fn new(
+        target: &'a ExportTarget,
+        schema: &Schema,
+        options: &SplitOptions,
+        open: F,
+    ) -> AppResult<Self> {
+        if options.is_split() {
+            target.check_split()?;
+        }
+        let style = match options.partition_style.as_deref().map(str::to_lowercase) {
+            None => PartitionStyle::Hive,
+            Some(style) => match style.as_str() {
+                "hive" => PartitionStyle::Hive,
+                "suffix" => PartitionStyle::Suffix,
+                _ => {
+                    return Err(AppError::BadRequest {
+                        message: format!(
+                            "Unsupported partition style '{}'. Supported: hive, suffix",
+                            style
+                        ),
+                    })
+                }
+            },
+        };
+        if options.max_rows_per_file == Some(0) || options.max_bytes_per_file == Some(0) {
+            return Err(AppError::BadRequest {
+                message: "Max rows and bytes per file must be at least 1".to_string(),
+            });
+        }
+        let partition_columns = options
+            .partition_by
+            .iter()
+            .map(|name| {
+                schema
+                    .index_of(name)
+                    .map(|index| (index, name.clone()))
+                    .map_err(|_| AppError::BadRequest {
+                        message: format!("Partition column '{}' not found in the result", name),
+                    })
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(Self {
+            layout: Layout {
+                target,
+                partition_columns,
+                style,
+                numbered: options.max_rows_per_file.is_some()
+                    || options.max_bytes_per_file.is_some(),
+            },
+            open,
+            max_rows: options.max_rows_per_file,
+            max_bytes: options.max_bytes_per_file,
+            max_open_files: MAX_OPEN_FILES,
+            writes: 0,
+            outputs: HashMap::new(),
+            partition_paths: HashMap::new(),
+            files: Vec::new(),
+        })
+    }
+
+    /// Partition files kept open at once, at least 1.
+    pub fn with_max_open_files(mut self, max_open_files: usize) -> Self {
+        self.max_open_files = max_open_files.max(1);
+        self
+    }
+
+    /// Stream `df` into the files and return their paths in the order they
+    /// were created. Files already written are removed if the export fails.
+    pub async fn run(mut self, df: DataFrame) -> AppResult<Vec<PathBuf>> {
+        let result = async {
+            let mut stream = df.execute_stream().await?;
+            while let Some(batch) = stream.next().await {
+                self.write_batch(&batch?)?;
+            }
+            self.finish()
+        }
+        .await;
+        if result.is_err() {
+            self.discard();
+        }
+        result
+    }
+
+    /// Like [`FileSplitter::run`], for batches that were already produced,
+    /// e.g. read back from a spool.
+    pub fn run_batches<I, E>(mut self, batches: I) -> AppResult<Vec<PathBuf>>
+    where
+        I: IntoIterator<Item = Result<RecordBatch, E>>,
+        AppError: From<E>,
+    {
+        let result = batches
+            .into_iter()
+            .try_for_each(|batch| self.write_batch(&batch?))
+            .and_then(|()| self.finish());
+        if result.is_err() {
+            self.discard();
+        }
+        result
+    }
+
+    pub fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        let partition_columns = &self.layout.partition_columns;
+        if partition_columns.is_empty() {
+            return self.write_to(Vec::new(), batch);
+        }
+
+        let options = FormatOptions::default();
+        let formatters = partition_columns
+            .iter()
+            .map(|(index, _)| ArrayFormatter::try_new(batch.column(*index).as_ref(), &options))
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        // Rows of each partition, in order of first appearance.
+        let mut groups: Vec<(Vec<Option<String>>, Vec<u32>)> = Vec::new();
+        let mut group_index: HashMap<Vec<Option<String>>, usize> = HashMap::new();
+        for row_idx in 0..batch.num_rows() {
+            let key = partition_columns
+                .iter()
+                .zip(&formatters)
+                .map(|((index, _), formatter)| {
+                    if batch.column(*index).is_null(row_idx) {
+                        None
+                    } else {
+                        Some(formatter.value(row_idx).to_string())
+                    }
+                })
+                .collect::<Vec<_>>();
+            let index = *group_index.entry(key.clone()).or_insert_with(|| {
+                groups.push((key, Vec::new()));
+                groups.len() - 1
+            });
+            groups[index].1.push(row_idx as u32);
+        }
+
+        for (key, rows) in groups {
+            let rows = take_record_batch(batch, &UInt32Array::from(rows))?;
+            self.write_to(key, &rows)?;
+        }
+        Ok(())
+    }
+
+    /// Finish every open file. An export without rows still gets its single
+    /// (empty) file unless it is partitioned.
+    pub fn finish(&mut self) -> AppResult<Vec<PathBuf>> {
+        if self.files.is_empty() && self.layout.partition_columns.is_empty() {
+            let layout = &self.layout;
+            let output = self
+                .outputs
+                .entry(Vec::new())
+                .or_insert_with_key(|key| layout.output(key));
+            output.next_file(
+                &mut self.open,
+                layout.target,
+                &mut self.files,
+                self.max_bytes,
+            )?;
+        }
+        for output in self.outputs.values_mut() {
+            if let Some(writer) = output.writer.take() {
+                writer.finish()?;
+            }
+        }
+        Ok(self.files.clone())
+    }
+
+    /// Remove the files written so far.
+    pub fn discard(self) {
+        drop(self.outputs);
+        let target = self.layout.target;
+        for file in &self.files {
+            if *file == target.path {
+                target.discard();
+            } else {
+                let _ = fs::remove_file(file);
+            }
+        }
+    }
+
+    fn write_to(&mut self, key: Vec<Option<String>>, batch: &RecordBatch) -> AppResult<()> {
+        if self.outputs.get(&key).is_none_or(|output| output.writer.is_none()) {
+            self.make_room()?;
+        }
+        self.writes += 1;
+        let (max_rows, max_bytes) = (self.max_rows, self.max_bytes);
+        let chunk_rows = if max_bytes.is_some() {
+            SIZE_CHECK_ROWS
+        } else {
+            batch.num_rows()
+        };
+        let layout = &self.layout;
+        let output = match self.outputs.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let output = layout.output(entry.key());
+                let folded = output.path.to_string_lossy().to_lowercase();
+                if let Some(other) = self.partition_paths.get(&folded) {
+                    return Err(AppError::BadRequest {
+                        message: format!(
+                            "Partitions {} and {} would be written to the same file '{}'",
+                            describe_partition(other),
+                            describe_partition(entry.key()),
+                            output.path.display()
+                        ),
+                    });
+                }
+                self.partition_paths.insert(folded, entry.key().clone());
+                entry.insert(output)
+            }
+        };
+
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            if output.writer.is_none() {
+                output.next_file(&mut self.open, layout.target, &mut self.files, max_bytes)?;
+            }
+            let mut len = chunk_rows.min(batch.num_rows() - offset);
+            if let Some(max_rows) = max_rows {
+                len = len.min(max_rows - output.rows);
+            }
+            let Some(writer) = output.writer.as_mut() else {
+                unreachable!("a file was opened above");
+            };
+            writer.write_batch(&batch.slice(offset, len))?;
+            output.rows += len;
+            output.last_write = self.writes;
+            offset += len;
+
+            let full = max_rows.is_some_and(|max_rows| output.rows >= max_rows)
+                || max_bytes
+                    .is_some_and(|max_bytes| writer.bytes_written().unwrap_or(0) >= max_bytes);
+            if full {
+                if let Some(writer) = output.writer.take() {
+                    writer.finish()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: BatchWriter, F> FileSplitter<'_, W, F> {
+    /// Finish the least recently written file if opening another one would
+    /// go over the limit.
+    fn make_room(&mut self) -> AppResult<()> {
+        let open = self.outputs.values().filter(|output| output.writer.is_some());
+        if open.count() < self.max_open_files {
+            return Ok(());
+        }
+        let oldest = self
+            .outputs
+            .values_mut()
+            .filter(|output| output.writer.is_some())
+            .min_by_key(|output| output.last_write);
+        if let Some(writer) = oldest.and_then(|output| output.writer.take()) {
+            writer.finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl Layout<'_> {
+    fn output<W>(&self, key: &[Option<String>]) -> Output<W> {
+        Output {
+            path: self.path(key),
+            writer: None,
+            numbered: self.numbered,
+            part: 0,
+            rows: 0,
+            last_write: 0,
+        }
+    }
+
+    /// Path of a partition's files before numbering.
+    fn path(&self, key: &[Option<String>]) -> PathBuf {
+        let target = &self.target.path;
+        if key.is_empty() {
+            return target.clone();
+        }
+        let directory = target.parent().unwrap_or(Path::new("")).to_path_buf();
+        match self.style {
+            PartitionStyle::Hive => {
+                let mut path = directory;
+                for ((_, name), value) in self.partition_columns.iter().zip(key) {
+                    let value = value.as_deref().unwrap_or(HIVE_NULL_PARTITION);
+                    path.push(format!(
+                        "{}={}",
+                        escape_partition_value(name),
+                        escape_partition_value(value)
+                    ));
+                }
+                path.join(target.file_name().unwrap_or_default())
+            }
+            PartitionStyle::Suffix => {
+                let (mut name, extension) = split_name(target);
+                for value in key {
+                    name.push('_');
+                    name.push_str(&escape_partition_value(value.as_deref().unwrap_or("null")));
+                }
+                directory.join(name + &extension)
+            }
+        }
+    }
+}
+
+impl<W: BatchWriter> Output<W> {
+    /// Open the next file of this output.
+    fn next_file<F: FnMut(&str) -> AppResult<W>>(
+        &mut self,
+        open: &mut F,
+        target: &ExportTarget,
+        files: &mut Vec<PathBuf>,
+        max_bytes: Option<u64>,
+    ) -> AppResult<()> {
+        self.part += 1;
+        self.rows = 0;
+        let path = if self.numbered || self.part > 1 {
+            let (stem, extension) = split_name(&self.path);
+            self.path
+                .with_file_name(format!("{}_{:04}{}", stem, self.part, extension))
+        } else {
+            self.path.clone()
+        };
+        target.check_new_file(&path)?;
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        files.push(path.clone());
+        let writer = open(&path.to_string_lossy())?;
+        if max_bytes.is_some() && writer.bytes_written().is_none() {
+            return Err(AppError::BadRequest {
+                message: "Splitting by size is not supported for this file type".to_string(),
+            });
+        }
+        self.writer = Some(writer);
+        Ok(())
+    }
+}
+
+/// Percent-encode the characters of a partition value that are not allowed
+/// in file names, as Hive does, so that e.g. `a/b`, `a:b` and `a_b` get
+/// different files. `%` itself is encoded to keep the encoding reversible.
+fn escape_partition_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '%' | '=' => {
+                escaped.push_str(&format!("%{:02X}", c as u32))
+            }
+            c if c.is_control() => {
+                let mut bytes = [0; 4];
+                for byte in c.encode_utf8(&mut bytes).bytes() {
+                    escaped.push_str(&format!("%{:02X}", byte));
+                }
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Partition values for error messages: `'east', NULL`.
+fn describe_partition(key: &[Option<String>]) -> String {
+    key.iter()
+        .map(|value| match value {
+            Some(value) => format!("'{}'", value),
+            None => "NULL".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Split a file name into its stem and extension, keeping the file type of
+/// compressed files with the extension: `("out", ".csv.gz")`.
+fn split_name(path: &Path) -> (String, String) {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extensions = if ExportCompression::from_path(path).suffix().is_some() {
+        2
+    } else {
+        1
+    };
+    let mut split = name.len();
+    for _ in 0..extensions {
+        match name[..split].rfind('.') {
+            Some(dot) if dot > 0 => split = dot,
+            _ => break,
+        }
+    }
+    (name[..split].to_string(), name[split..].to_string())
+}
@@ -0,0 +1,389 @@
+use super::csv::{CsvExportOptions, CsvWriter};
+use super::destination::{ExportDestination, ExportTarget};
+use super::excel::ExcelWriter;
+use super::split::{FileSplitter, SplitOptions};
+use crate::context::error::AppError;
+use crate::context::schema::AppResult;
+use chrono::Local;
+use datafusion::arrow::array::{ArrayRef, Int64Array, StringArray};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// id  region
+/// 1   east
+/// 2   west
+/// 3   east
+/// 4   NULL
+/// 5   east
+fn rows() -> RecordBatch {
+    RecordBatch::try_from_iter(vec![
+        (
+            "id",
+            Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef,
+        ),
+        (
+            "region",
+            Arc::new(StringArray::from(vec![
+                Some("east"),
+                Some("west"),
+                Some("east"),
+                None,
+                Some("east"),
+            ])) as ArrayRef,
+        ),
+    ])
+    .expect("batch")
+}
+
+/// Rows with ids from 1 and the given regions.
+fn region_rows(regions: Vec<Option<&str>>) -> RecordBatch {
+    let ids: Vec<i64> = (1..=regions.len() as i64).collect();
+    RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(ids)) as ArrayRef),
+        ("region", Arc::new(StringArray::from(regions)) as ArrayRef),
+    ])
+    .expect("batch")
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!("easydb_split_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).expect("temp dir");
+    path
+}
+
+fn target(dir: &Path, if_exists: Option<&str>) -> ExportTarget {
+    ExportDestination {
+        directory: Some(dir.to_string_lossy().to_string()),
+        file_name_template: Some("out".to_string()),
+        if_exists: if_exists.map(str::to_string),
+        ..Default::default()
+    }
+    .resolve("csv", None, true, Local::now())
+    .expect("target")
+}
+
+fn open_csv(path: &str) -> AppResult<CsvWriter> {
+    CsvWriter::new(path, &rows().schema(), b',', &CsvExportOptions::default())
+}
+
+/// Write `batches` as CSV and return `(path relative to dir, content)` per file.
+fn split(dir: &Path, options: &SplitOptions, batches: &[RecordBatch]) -> Vec<(String, String)> {
+    let target = target(dir, None);
+    let mut splitter =
+        FileSplitter::new(&target, &rows().schema(), options, open_csv).expect("splitter");
+    for batch in batches {
+        splitter.write_batch(batch).expect("write");
+    }
+    contents(dir, &splitter.finish().expect("finish"))
+}
+
+/// `(path relative to dir, content)` of each file.
+fn contents(dir: &Path, files: &[PathBuf]) -> Vec<(String, String)> {
+    files
+        .iter()
+        .map(|file| {
+            (
+                file.strip_prefix(dir)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                std::fs::read_to_string(file).expect("read"),
+            )
+        })
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_unsplit_writes_single_target_file() {
+    let dir = temp_dir("single");
+    let files = split(&dir, &SplitOptions::default(), &[rows()]);
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].0, "out.csv");
+    assert!(files[0].1.starts_with("id,region\n1,east\n"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_max_rows_numbers_files() {
+    let dir = temp_dir("max_rows");
+    let options = SplitOptions {
+        max_rows_per_file: Some(2),
+        ..Default::default()
+    };
+    let files = split(&dir, &options, &[rows().slice(0, 3), rows().slice(3, 2)]);
+    assert_eq!(
+        files,
+        [
+            (
+                "out_0001.csv".to_string(),
+                "id,region\n1,east\n2,west\n".to_string()
+            ),
+            (
+                "out_0002.csv".to_string(),
+                "id,region\n3,east\n4,\n".to_string()
+            ),
+            (
+                "out_0003.csv".to_string(),
+                "id,region\n5,east\n".to_string()
+            ),
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_max_bytes_starts_new_files() {
+    let dir = temp_dir("max_bytes");
+    let options = SplitOptions {
+        max_bytes_per_file: Some(1),
+        ..Default::default()
+    };
+    // Size is checked every 1024 rows, so one batch of five rows lands in
+    // the first file.
+    let files = split(&dir, &options, &[rows(), rows()]);
+    let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["out_0001.csv", "out_0002.csv"]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_hive_partitions() {
+    let dir = temp_dir("hive");
+    let options = SplitOptions {
+        partition_by: vec!["region".to_string()],
+        ..Default::default()
+    };
+    let files = split(&dir, &options, &[rows().slice(0, 2), rows().slice(2, 3)]);
+    assert_eq!(
+        files,
+        [
+            (
+                "region=east/out.csv".to_string(),
+                "id,region\n1,east\n3,east\n5,east\n".to_string()
+            ),
+            (
+                "region=west/out.csv".to_string(),
+                "id,region\n2,west\n".to_string()
+            ),
+            (
+                "region=__HIVE_DEFAULT_PARTITION__/out.csv".to_string(),
+                "id,region\n4,\n".to_string()
+            ),
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_suffix_partitions_with_max_rows() {
+    let dir = temp_dir("suffix");
+    let options = SplitOptions {
+        partition_by: vec!["region".to_string()],
+        partition_style: Some("suffix".to_string()),
+        max_rows_per_file: Some(2),
+        ..Default::default()
+    };
+    let files = split(&dir, &options, &[rows()]);
+    let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "out_east_0001.csv",
+            "out_east_0002.csv",
+            "out_west_0001.csv",
+            "out_null_0001.csv"
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_partition_values_are_percent_encoded() {
+    let dir = temp_dir("escape");
+    let options = SplitOptions {
+        partition_by: vec!["region".to_string()],
+        ..Default::default()
+    };
+    let batch = region_rows(vec![Some("a/b"), Some("a_b"), Some("a:b"), Some("50%")]);
+    let files = split(&dir, &options, &[batch]);
+    let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "region=a%2Fb/out.csv",
+            "region=a_b/out.csv",
+            "region=a%3Ab/out.csv",
+            "region=50%25/out.csv"
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_partitions_sharing_a_file_error() {
+    let dir = temp_dir("collision");
+    let target = target(&dir, None);
+    for (style, regions) in [
+        (None, vec![Some("US"), Some("us")]),
+        (Some("suffix"), vec![Some("null"), None]),
+    ] {
+        let options = SplitOptions {
+            partition_by: vec!["region".to_string()],
+            partition_style: style.map(str::to_string),
+            ..Default::default()
+        };
+        let mut splitter =
+            FileSplitter::new(&target, &rows().schema(), &options, open_csv).expect("splitter");
+        let result = splitter.write_batch(&region_rows(regions));
+        let msg = format!("{:?}", result.unwrap_err());
+        assert!(msg.contains("same file"), "{}", msg);
+        splitter.discard();
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_invalid_split_options_error() {
+    let dir = temp_dir("invalid");
+    let fresh = target(&dir, None);
+    let schema = rows().schema();
+    for options in [
+        SplitOptions {
+            partition_by: vec!["missing".to_string()],
+            ..Default::default()
+        },
+        SplitOptions {
+            partition_style: Some("folders".to_string()),
+            ..Default::default()
+        },
+        SplitOptions {
+            max_rows_per_file: Some(0),
+            ..Default::default()
+        },
+    ] {
+        assert!(FileSplitter::new(&fresh, &schema, &options, open_csv).is_err());
+    }
+
+    std::fs::write(dir.join("out.csv"), "id,region\n").expect("write");
+    let append = target(&dir, Some("append"));
+    let options = SplitOptions {
+        max_rows_per_file: Some(1),
+        ..Default::default()
+    };
+    assert!(FileSplitter::new(&append, &schema, &options, open_csv).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_xlsx_cannot_split_by_size() {
+    let dir = temp_dir("xlsx");
+    let target = target(&dir, None);
+    let options = SplitOptions {
+        max_bytes_per_file: Some(1024),
+        ..Default::default()
+    };
+    let mut splitter = FileSplitter::new(&target, &rows().schema(), &options, |path: &str| {
+        ExcelWriter::new(path.to_string(), rows().schema())
+    })
+    .expect("splitter");
+    assert!(splitter.write_batch(&rows()).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_discard_removes_files() {
+    let dir = temp_dir("discard");
+    let target = target(&dir, None);
+    let options = SplitOptions {
+        partition_by: vec!["region".to_string()],
+        max_rows_per_file: Some(1),
+        ..Default::default()
+    };
+    let mut splitter =
+        FileSplitter::new(&target, &rows().schema(), &options, open_csv).expect("splitter");
+    splitter.write_batch(&rows()).expect("write");
+    splitter.discard();
+    for partition in std::fs::read_dir(&dir).unwrap() {
+        let partition = partition.unwrap().path();
+        assert_eq!(std::fs::read_dir(&partition).unwrap().count(), 0);
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_run_batches_writes_then_discards_on_error() {
+    let dir = temp_dir("run_batches");
+    let written = target(&dir, None);
+    let options = SplitOptions {
+        max_rows_per_file: Some(2),
+        ..Default::default()
+    };
+    let splitter =
+        FileSplitter::new(&written, &rows().schema(), &options, open_csv).expect("splitter");
+    let files = splitter
+        .run_batches(vec![Ok::<_, AppError>(rows())])
+        .expect("run");
+    assert_eq!(files.len(), 3);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let dir = temp_dir("run_batches_error");
+    let failing = target(&dir, None);
+    let splitter =
+        FileSplitter::new(&failing, &rows().schema(), &options, open_csv).expect("splitter");
+    let failed = AppError::InternalServer {
+        message: "stream failed".to_string(),
+    };
+    let result = splitter.run_batches(vec![Ok(rows()), Err(failed)]);
+    assert!(result.is_err());
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_partitions_past_open_file_limit_continue_in_new_files() {
+    let dir = temp_dir("max_open");
+    let target = target(&dir, None);
+    let options = SplitOptions {
+        partition_by: vec!["region".to_string()],
+        ..Default::default()
+    };
+    let mut splitter = FileSplitter::new(&target, &rows().schema(), &options, open_csv)
+        .expect("splitter")
+        .with_max_open_files(1);
+    splitter.write_batch(&rows().slice(0, 2)).expect("write");
+    splitter.write_batch(&rows().slice(2, 3)).expect("write");
+    let files = contents(&dir, &splitter.finish().expect("finish"));
+    assert_eq!(
+        files,
+        [
+            (
+                "region=east/out.csv".to_string(),
+                "id,region\n1,east\n".to_string()
+            ),
+            (
+                "region=west/out.csv".to_string(),
+                "id,region\n2,west\n".to_string()
+            ),
+            (
+                "region=east/out_0002.csv".to_string(),
+                "id,region\n3,east\n5,east\n".to_string()
+            ),
+            (
+                "region=__HIVE_DEFAULT_PARTITION__/out.csv".to_string(),
+                "id,region\n4,\n".to_string()
+            ),
+        ]
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,56 @@
+use crate::context::schema::AppResult;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::ipc::reader::FileReader;
+use datafusion::arrow::ipc::writer::FileWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_SPOOL: AtomicU64 = AtomicU64::new(1);
+
+/// Query results written to a temporary Arrow IPC file, so an export that
+/// needs two passes over them doesn't run the query twice. The file is
+/// removed when the spool is dropped.
+pub struct Spool {
+    path: PathBuf,
+    writer: Option<FileWriter<BufWriter<File>>>,
+}
+
+impl Spool {
+    pub fn create(dir: &Path, schema: &Schema) -> AppResult<Self> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "spool-{}-{}.arrow",
+            std::process::id(),
+            NEXT_SPOOL.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = BufWriter::new(File::create(&path)?);
+        // Dropping the spool removes the file should the header fail to write.
+        let mut spool = Self { path, writer: None };
+        spool.writer = Some(FileWriter::try_new(file, schema)?);
+        Ok(spool)
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        if let Some(writer) = &mut self.writer {
+            writer.write(batch)?;
+        }
+        Ok(())
+    }
+
+    /// Complete the file and read the spooled batches back, in order.
+    pub fn read(&mut self) -> AppResult<FileReader<BufReader<File>>> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.finish()?;
+        }
+        Ok(FileReader::try_new(BufReader::new(File::open(&self.path)?), None)?)
+    }
+}
+
+impl Drop for Spool {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,55 @@
+use super::spool::Spool;
+use crate::context::schema::AppResult;
+use datafusion::arrow::array::{ArrayRef, Int64Array};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// ═══════════════════════════════════════════════════════════════════════
+// Test helpers
+// ═══════════════════════════════════════════════════════════════════════
+
+fn spool_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("easydb_spool_{}_{}", std::process::id(), name))
+}
+
+fn batch(values: Vec<i64>) -> RecordBatch {
+    RecordBatch::try_from_iter(vec![("id", Arc::new(Int64Array::from(values)) as ArrayRef)])
+        .expect("batch")
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Stability Tests
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_spool_reads_batches_back_in_order() -> AppResult<()> {
+    let dir = spool_dir("round_trip");
+    let first = batch(vec![1, 2]);
+    let second = batch(vec![3]);
+
+    let mut spool = Spool::create(&dir, &first.schema())?;
+    spool.write(&first)?;
+    spool.write(&second)?;
+    let batches = spool.read()?.collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(batches, vec![first, second]);
+    drop(spool);
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_spool_file_is_removed_on_drop() -> AppResult<()> {
+    let dir = spool_dir("drop");
+    let mut spool = Spool::create(&dir, &batch(vec![1]).schema())?;
+    spool.write(&batch(vec![1]))?;
+    let files = std::fs::read_dir(&dir)?.count();
+
+    drop(spool);
+
+    assert_eq!(files, 1);
+    assert_eq!(std::fs::read_dir(&dir)?.count(), 0);
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::context::schema::AppResult;
+use crate::sql::generator::StatementWriter;
+use crate::sql::script::SqlScript;
+use crate::writer::compression::OutputFile;
+use crate::writer::BatchWriter;
+use datafusion::arrow::record_batch::RecordBatch;
+
+/// Writes the statements of a [`StatementWriter`] into a SQL script file.
+pub struct SqlFileWriter<S: StatementWriter> {
+    statements: S,
+    script: SqlScript<OutputFile>,
+}
+
+impl<S: StatementWriter> SqlFileWriter<S> {
+    pub fn new(statements: S, script: SqlScript<OutputFile>) -> Self {
+        Self { statements, script }
+    }
+}
+
+impl<S: StatementWriter> BatchWriter for SqlFileWriter<S> {
+    fn write_batch(&mut self, batch: &RecordBatch) -> AppResult<()> {
+        self.statements.write_batch(batch, &mut self.script)
+    }
+
+    fn finish(mut self) -> AppResult<()> {
+        self.statements.finish(&mut self.script)?;
+        let terminate_line = self.statements.terminates_lines();
+        self.script.finish(terminate_line)?.finish()
+    }
+
+    /// Rows still buffered for the next INSERT are not counted yet.
+    fn bytes_written(&self) -> Option<u64> {
+        Some(self.script.get_ref().bytes())
+    }
+}
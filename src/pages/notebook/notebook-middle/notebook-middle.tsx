@@ -46,6 +46,10 @@ interface FetchResult {
   query_time: string;
 }
 
+interface PageResult extends FetchResult {
+  result_id: string;
+}
+
 interface NotebookMiddleProps {
   sql: string;
   setSql: (sql: string) => void;
@@ -84,6 +88,8 @@ function NotebookMiddle({ sql, setSql, onQuerySaved }: NotebookMiddleProps) {
   const dropAreaRef = useRef<HTMLDivElement>(null);
   const abortControllerRef = useRef<AbortController | null>(null);
   const executeIdRef = useRef(0);
+  // Cached full result that "load more" pages through, see fetch_page.
+  const resultIdRef = useRef<string | null>(null);
   const sqlRef = useRef(sql);
   sqlRef.current = sql;
   const editorRef = useRef<AceEditorInstance | null>(null);
@@ -258,6 +264,17 @@ function NotebookMiddle({ sql, setSql, onQuerySaved }: NotebookMiddleProps) {
     translate,
   ]);
 
+  // Drop the cached result of the previous query from the backend
+  const releaseResult = useCallback(() => {
+    const resultId = resultIdRef.current;
+    resultIdRef.current = null;
+    if (resultId) {
+      invoke("release_result", { resultId }).catch((error) =>
+        console.error("Failed to release result:", error),
+      );
+    }
+  }, []);
+
   // Cache query execution function with useCallback
   const resolveSqlToExecute = useCallback(
     (override?: string) => {
@@ -286,6 +303,7 @@ function NotebookMiddle({ sql, setSql, onQuerySaved }: NotebookMiddleProps) {
       if (isRunning || !sqlToExecute.trim()) return;
 
       const executeId = ++executeIdRef.current;
+      releaseResult();
 
       setIsRunning(true);
       setIsLoading(true);
@@ -347,7 +365,7 @@ function NotebookMiddle({ sql, setSql, onQuerySaved }: NotebookMiddleProps) {
         abortControllerRef.current = null;
       }
     },
-    [isRunning, resolveSqlToExecute],
+    [isRunning, releaseResult, resolveSqlToExecute],
   );
 
   // Cache cancel query function with useCallback
@@ -393,6 +411,7 @@ function NotebookMiddle({ sql, setSql, onQuerySaved }: NotebookMiddleProps) {
 
   // Cache clear data function with useCallback
   const clearData = useCallback(() => {
+    releaseResult();
     setData({
       header: [],
       columns: [],
@@ -400,20 +419,30 @@ function NotebookMiddle({ sql, setSql, onQuerySaved }: NotebookMiddleProps) {
       query_time: "",
     });
     setHasMore(true);
-  }, []);
+  }, [releaseResult]);
 
   // Load next page of data
   const loadMore = useCallback(async () => {
     if (!lastExecutedSql || isLoadingMore || !hasMore || data.rows.length === 0)
       return;
     const offset = data.rows.length;
+    const executeId = executeIdRef.current;
     setIsLoadingMore(true);
     try {
-      const results: FetchResult = await invoke("fetch_page", {
+      const results: PageResult = await invoke("fetch_page", {
         sql: lastExecutedSql,
         offset,
         limit: QUERY_PAGE_SIZE,
+        resultId: resultIdRef.current,
       });
+      if (executeId !== executeIdRef.current) {
+        // Another query ran meanwhile; its results replace these.
+        invoke("release_result", { resultId: results.result_id }).catch(
+          (error) => console.error("Failed to release result:", error),
+        );
+        return;
+      }
+      resultIdRef.current = results.result_id;
       setData((prev) => ({
         ...prev,
         rows: [...prev.rows, ...results.rows],
@@ -427,6 +456,9 @@ function NotebookMiddle({ sql, setSql, onQuerySaved }: NotebookMiddleProps) {
     }
   }, [lastExecutedSql, isLoadingMore, hasMore, data.rows.length]);
 
+  // Release the cached result when the tab closes
+  useEffect(() => releaseResult, [releaseResult]);
+
   // Listen to Tauri drag and drop events
   useEffect(() => {
     const setupDragDropListeners = async () => {